@@ -0,0 +1,37 @@
+use std::io::{Seek, SeekFrom, Write};
+
+use ssd_rs::block::PAGES_PER_BLOCK;
+use ssd_rs::capacity::OverProvisioning;
+use ssd_rs::byte_io::SsdFile;
+use ssd_rs::ssd_basic::SSD;
+
+const PAGE_SIZE: usize = 4096;
+
+// 사용법: cargo run --example file_adapter
+// SSD를 io::Write로 쓰는 순환 로그: 레코드 크기가 페이지보다 작을수록 read-modify-write가 늘어남
+fn main() {
+    let num_blocks = 64;
+    let num_lbas = OverProvisioning::Percent { pct: 25.0 }.user_pages(num_blocks, PAGES_PER_BLOCK).unwrap();
+
+    println!("{:>8} {:>10} {:>10} {:>12} {:>10} {:>10}",
+             "record", "records", "page WAF", "RMW reads", "byte WAF", "erases");
+    for record_size in [64, 512, 1000, 4096, 16384] {
+        let mut file = SsdFile::new(SSD::with_seed(num_blocks, num_lbas, 42), PAGE_SIZE).unwrap();
+        let record = vec![b'x'; record_size];
+        let total_bytes = file.size() * 3;
+
+        let mut records = 0u64;
+        while records * (record_size as u64) < total_bytes {
+            // 레코드를 다 담을 수 없으면 처음으로 돌아감 (순환 로그)
+            if file.position() + record_size as u64 > file.size() {
+                file.seek(SeekFrom::Start(0)).expect("Seek Failed!");
+            }
+            file.write_all(&record).expect("SSD Write Failed!");
+            records += 1;
+        }
+
+        let stats = file.ssd().stats();
+        println!("{:>8} {:>10} {:>10.4} {:>12} {:>10.4} {:>10}",
+                 record_size, records, stats.waf(), stats.rmw_reads, file.byte_waf(), stats.nand_erases);
+    }
+}
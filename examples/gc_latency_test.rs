@@ -0,0 +1,88 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use ssd_rs::block::PAGES_PER_BLOCK;
+use ssd_rs::capacity::OverProvisioning;
+use ssd_rs::gc::{GcPolicy, GcScheduler};
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::timing::{TimedSSD, TimingModel};
+
+// 호스트 요청 하나 (도착 시각, 읽기/쓰기, LBA)
+struct Request {
+    arrival_us: u64,
+    is_write: bool,
+    lba: usize,
+}
+
+fn main() {
+    let num_blocks = 64;
+    let num_lbas = OverProvisioning::Percent { pct: 25.0 }.user_pages(num_blocks, PAGES_PER_BLOCK).unwrap();
+    let num_requests = num_lbas * 20;
+    let seed = 42;
+
+    // 1. 모든 정책에 똑같은 요청열을 주기 위해 워크로드를 미리 생성
+    //    (평균 도착 간격 1000us, 읽기 70% / 쓰기 30%)
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut requests = Vec::with_capacity(num_lbas + num_requests);
+    let mut now = 0u64;
+
+    // 먼저 전체 LBA를 한 번 순차로 채움 (측정 전 warm-up)
+    for lba in 0..num_lbas {
+        now += 600;
+        requests.push(Request { arrival_us: now, is_write: true, lba });
+    }
+    let warmup = requests.len();
+
+    for _ in 0..num_requests {
+        // 지수 분포 도착 간격
+        let u: f64 = rng.r#gen::<f64>().max(f64::MIN_POSITIVE);
+        now += (-u.ln() * 1000.0) as u64;
+        requests.push(Request {
+            arrival_us: now,
+            is_write: rng.gen_bool(0.3),
+            lba: rng.gen_range(0..num_lbas),
+        });
+    }
+
+    let policies = [
+        ("Stop-the-world", GcPolicy::StopTheWorld),
+        ("Lazy", GcPolicy::Lazy),
+        ("Greedy-preemptive", GcPolicy::GreedyPreemptive),
+        ("Token-bucket", GcPolicy::TokenBucket { rate_per_sec: 700.0, burst: 32 }),
+    ];
+
+    println!("=== GC Scheduling Latency Experiment ===");
+    println!("    Blocks = {}, LBAs = {}, Requests = {}, Seed = {}\n", num_blocks, num_lbas, num_requests, seed);
+
+    for (name, policy) in policies {
+        let scheduler = GcScheduler::new(policy, 2, 4);
+        let mut ssd = TimedSSD::new(SSD::with_seed(num_blocks, num_lbas, seed), TimingModel::default(), scheduler);
+
+        for req in &requests[..warmup] {
+            ssd.write(req.lba, 0xDEADBEEF, req.arrival_us).expect("warm-up write failed");
+        }
+        ssd.reset_stats();
+
+        // 2. 측정 구간 (warm-up 통계는 위에서 초기화)
+        for req in &requests[warmup..] {
+            if req.is_write {
+                ssd.write(req.lba, 0xDEADBEEF, req.arrival_us).expect("write failed");
+            } else {
+                ssd.read(req.lba, req.arrival_us).expect("read failed");
+            }
+        }
+        let read_lat = ssd.read_latency();
+        let write_lat = ssd.write_latency();
+
+        println!(">>> {}", name);
+        println!("    WAF: {:.4}  (GC steps: background {}, foreground {})",
+                 ssd.ssd().get_waf(), ssd.background_steps(), ssd.foreground_steps());
+        println!("    Read  latency(us): mean {:>8.1} | p50 {:>6} | p99 {:>6} | p99.9 {:>6} | max {:>6}",
+                 read_lat.mean(), read_lat.percentile(50.0), read_lat.percentile(99.0),
+                 read_lat.percentile(99.9), read_lat.max());
+        println!("    Write latency(us): mean {:>8.1} | p50 {:>6} | p99 {:>6} | p99.9 {:>6} | max {:>6}",
+                 write_lat.mean(), write_lat.percentile(50.0), write_lat.percentile(99.0),
+                 write_lat.percentile(99.9), write_lat.max());
+        println!("----------------------------------------\n");
+    }
+}
@@ -0,0 +1,42 @@
+use std::env;
+
+use ssd_rs::block::PAGES_PER_BLOCK;
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::visualize::*;
+use ssd_rs::workload::*;
+
+// 사용법: cargo run --example heatmap -- [출력 디렉터리] [svg|ppm]
+// Hot/Cold 80/20 워크로드를 돌린 뒤 터미널 히트맵을 출력하고 이미지 3장을 저장
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let out_dir = args.get(1).cloned().unwrap_or_else(|| env::temp_dir().to_string_lossy().to_string());
+    let ext = args.get(2).map(String::as_str).unwrap_or("svg");
+
+    let num_blocks = 128;
+    let num_lbas = num_blocks * PAGES_PER_BLOCK * 85 / 100;
+    let mut ssd = SSD::with_seed(num_blocks, num_lbas, 7);
+
+    let phases = [
+        Phase { workload: WorkloadSpec::new(AddressPattern::Sequential { start: 0 }), requests: num_lbas as u64 },
+        Phase {
+            workload: WorkloadSpec::new(AddressPattern::HotCold { hot_access_pct: 80.0, hot_lba_pct: 20.0 }),
+            requests: num_lbas as u64 * 5,
+        },
+    ];
+    for (i, request) in Phased::new(&phases, num_lbas, 7).expect("Invalid workload").enumerate() {
+        execute(&mut ssd, &request, i as u32).expect("SSD Write Failed!");
+    }
+
+    ssd.print_heatmap();
+
+    let outputs = [
+        ("block_state", block_state_raster(ssd.blocks()), 4),
+        ("erase_count", erase_count_raster(ssd.blocks(), 16), 16),
+        ("lba_frequency", lba_frequency_raster(&ssd.stats().lba_writes, 128), 4),
+    ];
+    for (name, raster, scale) in outputs {
+        let path = format!("{}/{}.{}", out_dir, name, ext);
+        raster.save(&path, scale).expect("Failed to save image");
+        println!("Saved {} ({}x{})", path, raster.width * scale, raster.height * scale);
+    }
+}
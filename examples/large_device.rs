@@ -0,0 +1,48 @@
+use std::time::Instant;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ssd_rs::block::PAGES_PER_BLOCK;
+use ssd_rs::ssd_basic::SSD;
+
+// 장치 크기별 basic FTL의 호스트 쓰기 처리 속도 (GC가 도는 정상 상태에서)
+// victim 선정 / 빈 블록 수 / 역방향 매핑이 색인으로 유지되므로 블록 수가 늘어도 쓰기 한 번의 비용이 거의 같아야 함
+// (wear leveling FTL은 정적 WL 검사가 check_interval마다 블록을 한 번 훑으므로 쓰기 비용이 블록 수에 비례해 늘어남)
+// 사용법: cargo run --release --example large_device [측정할 쓰기 수]
+fn main() -> Result<(), String> {
+    let writes: usize = match std::env::args().nth(1) {
+        Some(arg) => arg.parse().map_err(|_| format!("Invalid write count: {}", arg))?,
+        None => 200_000,
+    };
+    let seed = 5;
+
+    println!("=== Large Device Benchmark ({} random writes after fill, 7% OP, seed {}) ===\n", writes, seed);
+    println!("{:>8} {:>10} {:>10} {:>8} {:>12} {:>10}", "blocks", "LBAs", "fill(s)", "GCs", "writes/s", "us/write");
+
+    for num_blocks in [1_000, 10_000, 100_000] {
+        let num_lbas = num_blocks * PAGES_PER_BLOCK * 100 / 107;
+        let mut ssd = SSD::new(num_blocks, num_lbas);
+
+        // 한 번 채우고 무작위로 덮어써서 빈 블록을 다 쓸 때까지 (여기서부터 GC가 매번 돎)
+        let start = Instant::now();
+        for lba in 0..num_lbas {
+            ssd.write(lba, lba as u32)?;
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        while ssd.stats().gc_invocations == 0 {
+            ssd.write(rng.gen_range(0..num_lbas), 0)?;
+        }
+        let fill_secs = start.elapsed().as_secs_f64();
+
+        ssd.reset_stats();
+        let start = Instant::now();
+        for i in 0..writes {
+            ssd.write(rng.gen_range(0..num_lbas), i as u32)?;
+        }
+        let secs = start.elapsed().as_secs_f64();
+
+        println!("{:>8} {:>10} {:>10.2} {:>8} {:>12.0} {:>10.2}",
+                 num_blocks, num_lbas, fill_secs, ssd.stats().gc_invocations, writes as f64 / secs, secs * 1e6 / writes as f64);
+    }
+    Ok(())
+}
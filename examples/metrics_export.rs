@@ -0,0 +1,42 @@
+use std::env;
+
+use ssd_rs::block::PAGES_PER_BLOCK;
+use ssd_rs::capacity::OverProvisioning;
+use ssd_rs::metrics::{CsvSink, JsonlSink, MetricsRecorder, MetricsSink};
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::workload::*;
+
+// 사용법: cargo run --example metrics_export -- [출력 경로(.csv 또는 .jsonl)] [샘플 간격]
+// WAF가 수렴하는 모습과 빈 블록 수 변화를 그래프로 그리기 위한 시계열 출력
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let default_path = env::temp_dir().join("ssd_metrics.csv").to_string_lossy().to_string();
+    let path = args.get(1).cloned().unwrap_or(default_path);
+    let interval: u64 = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(500);
+
+    let sink: Box<dyn MetricsSink> = if path.ends_with(".jsonl") {
+        Box::new(JsonlSink::create(&path).expect("Failed to create output"))
+    } else {
+        Box::new(CsvSink::create(&path).expect("Failed to create output"))
+    };
+
+    let num_blocks = 64;
+    let num_lbas = OverProvisioning::Percent { pct: 25.0 }.user_pages(num_blocks, PAGES_PER_BLOCK).unwrap();
+    let mut ssd = SSD::with_seed(num_blocks, num_lbas, 42);
+    ssd.attach_metrics(MetricsRecorder::new(sink, interval));
+
+    let phases = [
+        Phase { workload: WorkloadSpec::new(AddressPattern::Sequential { start: 0 }), requests: num_lbas as u64 },
+        Phase {
+            workload: WorkloadSpec::new(AddressPattern::HotCold { hot_access_pct: 80.0, hot_lba_pct: 20.0 }),
+            requests: num_lbas as u64 * 20,
+        },
+    ];
+    for (i, request) in Phased::new(&phases, num_lbas, 42).expect("Invalid workload").enumerate() {
+        execute(&mut ssd, &request, i as u32).expect("SSD Write Failed!");
+    }
+    ssd.detach_metrics().expect("Failed to flush metrics");
+
+    println!("Final WAF: {:.4}", ssd.get_waf());
+    println!("Metrics written to {} (every {} host writes)", path, interval);
+}
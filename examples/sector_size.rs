@@ -0,0 +1,40 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ssd_rs::block::PAGES_PER_BLOCK;
+use ssd_rs::device::BlockDevice;
+use ssd_rs::sector::{SectorConfig, SectorDevice};
+use ssd_rs::ssd_basic::SSD;
+
+fn main() -> Result<(), String> {
+    let num_blocks = 64;
+    let seed = 42;
+
+    println!("=== Logical Block Size vs NAND Page Size ({} blocks at 80% fill, seed {}) ===\n", num_blocks, seed);
+    println!("{:<8} {:<8} {:>10} {:>10} {:>10} {:>8} {:>8}",
+             "sector", "page", "map size", "rmw reads", "nand wr", "xlate", "total");
+
+    for (sector_size, page_size) in [(4096, 16384), (512, 4096)] {
+        let config = SectorConfig { sector_size, page_size };
+        let num_lbas = num_blocks * PAGES_PER_BLOCK * 80 / 100;
+        let mut dev = SectorDevice::new(SSD::new(num_blocks, num_lbas), config)?;
+
+        // 페이지 단위로 한 번 채운 뒤 섹터 하나 크기의 무작위 덮어쓰기
+        let num_sectors = dev.num_lbas();
+        let spp = config.sectors_per_page();
+        for start in (0..num_sectors).step_by(spp) {
+            dev.write_range(start, &vec![0; spp])?;
+        }
+        dev.reset_stats();
+        let mut rng = StdRng::seed_from_u64(seed);
+        for i in 0..num_sectors as u32 * 2 {
+            dev.write(rng.gen_range(0..num_sectors), i)?;
+        }
+
+        println!("{:<8} {:<8} {:>10} {:>10} {:>10} {:>8.3} {:>8.3}",
+                 sector_size, page_size, dev.device().num_lbas(),
+                 dev.sector_stats().rmw_reads, dev.stats().nand_writes,
+                 dev.translation_amplification(), dev.total_amplification());
+    }
+    println!("\nnand wr: NAND page programs, xlate: bytes sent to the FTL / host bytes, total: bytes programmed / host bytes");
+    Ok(())
+}
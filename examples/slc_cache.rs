@@ -0,0 +1,69 @@
+use ssd_rs::cell::CellMode;
+use ssd_rs::device::BlockDevice;
+use ssd_rs::ssd_slc_cache::{SlcCacheConfig, SlcCachePolicy, SSD};
+
+// 큐 깊이 1: 앞 쓰기가 끝나자마자 다음 쓰기 (창별 평균 = 쓰기 한 번의 서비스 시간)
+const WINDOW: usize = 256;
+
+fn main() -> Result<(), String> {
+    let num_blocks = 128;
+    let dense_pages = num_blocks * CellMode::Tlc.pages_per_block();
+    let num_lbas = dense_pages * 70 / 100;
+
+    let configs = [
+        ("no cache", SlcCachePolicy::Static { blocks: 0 }),
+        ("static 8", SlcCachePolicy::Static { blocks: 8 }),
+        ("dynamic 25%", SlcCachePolicy::Dynamic { free_space_pct: 25.0 }),
+    ];
+
+    println!("SLC write cache: {} TLC blocks, {} LBAs, queue depth 1", num_blocks, num_lbas);
+    println!("Sequential burst of {} writes, 1s idle, then a burst of {}\n", num_lbas / 2, num_lbas / 8);
+
+    let mut columns = Vec::new();
+    for (name, policy) in configs {
+        let mut ssd = SSD::new(num_blocks, num_lbas, SlcCacheConfig { policy, dense_mode: CellMode::Tlc })?;
+        let mut windows = burst(&mut ssd, 0, num_lbas / 2)?;
+
+        // 유휴 시간 동안 캐시를 접어서 비움
+        let resume = ssd.now_us() + 1_000_000;
+        windows.extend(burst(&mut ssd, resume, num_lbas / 8)?);
+        println!("[{}] {}", name, ssd.stats().to_string().lines().find(|l| l.contains("SLC")).unwrap_or("  SLC:   (no cache)").trim());
+        columns.push((name, windows));
+    }
+
+    println!("\nMean write latency (us) per {} writes:", WINDOW);
+    print!("{:>8}", "writes");
+    for (name, _) in &columns {
+        print!(" {:>12}", name);
+    }
+    println!();
+    let rows = columns.iter().map(|(_, w)| w.len()).max().unwrap_or(0);
+    for row in 0..rows {
+        print!("{:>8}", (row + 1) * WINDOW);
+        for (_, windows) in &columns {
+            match windows.get(row) {
+                Some(mean) => print!(" {:>12.0}", mean),
+                None => print!(" {:>12}", "-"),
+            }
+        }
+        println!();
+    }
+    Ok(())
+}
+
+// start_us부터 count개 순차 쓰기, 창(window)별 평균 지연 시간 반환
+fn burst(ssd: &mut SSD, start_us: u64, count: usize) -> Result<Vec<f64>, String> {
+    let mut windows = Vec::new();
+    let mut sum = 0u64;
+    for i in 0..count {
+        let arrival = if i == 0 { start_us } else { ssd.now_us() };
+        ssd.set_arrival(arrival);
+        BlockDevice::write(ssd, i % ssd.num_lbas(), i as u32)?;
+        sum += ssd.now_us() - arrival;
+        if (i + 1) % WINDOW == 0 {
+            windows.push(sum as f64 / WINDOW as f64);
+            sum = 0;
+        }
+    }
+    Ok(windows)
+}
@@ -1,6 +1,3 @@
-use ssd_rs::block::*;
-use ssd_rs::mapping::*;
-use ssd_rs::page::*;
 use ssd_rs::ssd_basic::*;
 fn main() {
     // 블록 5개, LBA 100개짜리 SSD 생성
@@ -11,7 +8,7 @@ fn main() {
         //my_ssd.print_blocks();
         match res {
             Ok(_) => {},
-            Err(err) => {panic!("error!!!!!");}
+            Err(err) => {panic!("error!!!!! {}", err);}
         };
     }
     
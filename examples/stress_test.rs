@@ -1,6 +1,3 @@
-use ssd_rs::block::*;
-use ssd_rs::mapping::*;
-use ssd_rs::page::*;
 use ssd_rs::ssd_basic::*;
 
 fn main() {
@@ -22,5 +19,7 @@ fn main() {
         }
     }
     println!("waf: {}", ssd.get_waf());
+    println!("{}", ssd.stats());
+    println!("Erase distribution: {:?}", ssd.erase_distribution());
     println!("Test Passed! SSD survived.");
 }
\ No newline at end of file
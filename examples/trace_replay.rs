@@ -0,0 +1,56 @@
+use std::env;
+
+use ssd_rs::block::PAGES_PER_BLOCK;
+use ssd_rs::capacity::OverProvisioning;
+use ssd_rs::device::BlockDevice;
+use ssd_rs::gc::{GcPolicy, GcScheduler};
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::timing::{TimedSSD, TimingModel};
+use ssd_rs::trace::*;
+
+// 사용법: cargo run --example trace_replay -- <msr|spc|blkparse> <trace 파일> [--timed]
+// 인자가 없으면 test/traces/ 아래의 샘플 트레이스 3개를 모두 재생
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let runs: Vec<(TraceFormat, String)> = if args.len() >= 3 {
+        let format = TraceFormat::from_name(&args[1]).expect("Unknown trace format");
+        vec![(format, args[2].clone())]
+    } else {
+        vec![
+            (TraceFormat::Msr, "./test/traces/sample_msr.csv".to_string()),
+            (TraceFormat::Spc, "./test/traces/sample_spc.csv".to_string()),
+            (TraceFormat::Blkparse, "./test/traces/sample_blkparse.txt".to_string()),
+        ]
+    };
+    let timed = args.iter().any(|a| a == "--timed");
+
+    let num_blocks = 64;
+    let num_lbas = OverProvisioning::Percent { pct: 25.0 }.user_pages(num_blocks, PAGES_PER_BLOCK).unwrap();
+    let config = ReplayConfig::default();
+
+    println!("=== Trace Replay ===\n");
+
+    for (format, path) in runs {
+        let records = load_trace(&path, format).expect("Failed to load trace");
+        println!(">>> {} ({:?}, {} requests)", path, format, records.len());
+
+        let mut device: Box<dyn BlockDevice> = if timed {
+            let scheduler = GcScheduler::new(GcPolicy::GreedyPreemptive, 2, 4);
+            Box::new(TimedSSD::new(SSD::new(num_blocks, num_lbas), TimingModel::default(), scheduler))
+        } else {
+            Box::new(SSD::new(num_blocks, num_lbas))
+        };
+        let summary = replay(device.as_mut(), &records, &config).expect("Replay failed");
+
+        println!("    Pages: read {} (unmapped {}), write {}, trim {}",
+                 summary.read_pages, summary.unmapped_reads, summary.write_pages, summary.trim_pages);
+        if timed {
+            println!("    Request latency(us): mean {:.1} | p99 {} | max {}",
+                     summary.request_latency.mean(), summary.request_latency.percentile(99.0),
+                     summary.request_latency.max());
+        }
+        println!("    WAF: {:.4}", device.stats().waf());
+        println!("----------------------------------------\n");
+    }
+}
@@ -0,0 +1,54 @@
+use ssd_rs::block::PAGES_PER_BLOCK;
+use ssd_rs::capacity::OverProvisioning;
+use ssd_rs::device::BlockDevice;
+use ssd_rs::ssd_basic::SSD as BasicSSD;
+use ssd_rs::ssd_wear_leveling::SSD as WearLevelingSSD;
+use ssd_rs::workload::*;
+
+fn main() {
+    let num_blocks = 64;
+    let num_lbas = OverProvisioning::Percent { pct: 25.0 }.user_pages(num_blocks, PAGES_PER_BLOCK).unwrap();
+    let seed = 42;
+
+    // 모든 실험은 "순차로 한 번 채우기 -> 패턴별 랜덤 덮어쓰기(용량의 10배)" 순서
+    let fill = Phase {
+        workload: WorkloadSpec::new(AddressPattern::Sequential { start: 0 }),
+        requests: num_lbas as u64,
+    };
+    let patterns = [
+        ("Uniform", AddressPattern::Uniform),
+        ("Zipfian(0.99)", AddressPattern::Zipfian { theta: 0.99 }),
+        ("Hot/Cold 80/20", AddressPattern::HotCold { hot_access_pct: 80.0, hot_lba_pct: 20.0 }),
+        ("Hot/Cold 90/10", AddressPattern::HotCold { hot_access_pct: 90.0, hot_lba_pct: 10.0 }),
+        ("Shifting hotspot", AddressPattern::ShiftingHotspot {
+            hot_access_pct: 80.0, hot_lba_pct: 20.0, shift_every: num_lbas as u64,
+        }),
+    ];
+
+    println!("=== Workload Pattern WAF Experiment (seed {}) ===\n", seed);
+
+    for (name, pattern) in patterns {
+        let phases = [
+            fill.clone(),
+            Phase { workload: WorkloadSpec::new(pattern), requests: num_lbas as u64 * 10 },
+        ];
+
+        println!(">>> {}", name);
+        run("basic", &mut BasicSSD::new(num_blocks, num_lbas), &phases, seed);
+        run("wear leveling", &mut WearLevelingSSD::new(num_blocks, num_lbas), &phases, seed);
+        println!("----------------------------------------\n");
+    }
+}
+
+// 같은 요청열을 어떤 FTL에든 그대로 실행
+fn run<D: BlockDevice>(label: &str, device: &mut D, phases: &[Phase], seed: u64) {
+    for (i, request) in Phased::new(phases, device.num_lbas(), seed).expect("Invalid workload").enumerate() {
+        execute(device, &request, i as u32).expect("SSD Write Failed!");
+    }
+
+    let stats = device.stats();
+    let erase = device.erase_distribution();
+    println!("    [{}] WAF: {:.4} | GC: {} invocations, {:.2} valid pages per victim",
+             label, stats.waf(), stats.gc_invocations, stats.mean_victim_valid_pages());
+    println!("    [{}] Erase: min {} | max {} | std-dev {:.2}", label, erase.min, erase.max, erase.std_dev);
+}
@@ -0,0 +1,49 @@
+use ssd_rs::block::PAGES_PER_BLOCK;
+use ssd_rs::capacity::OverProvisioning;
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::workload::*;
+use ssd_rs::write_buffer::{EvictionPolicy, WriteBufferConfig};
+
+fn main() -> Result<(), String> {
+    let num_blocks = 64;
+    let num_lbas = OverProvisioning::Percent { pct: 25.0 }.user_pages(num_blocks, PAGES_PER_BLOCK)?;
+    let seed = 42;
+
+    // 순차로 한 번 채운 뒤 Zipfian 덮어쓰기 (같은 LBA가 짧은 간격으로 자주 다시 쓰임)
+    let phases = [
+        Phase { workload: WorkloadSpec::new(AddressPattern::Sequential { start: 0 }), requests: num_lbas as u64 },
+        Phase { workload: WorkloadSpec::new(AddressPattern::Zipfian { theta: 0.99 }), requests: num_lbas as u64 * 5 },
+    ];
+
+    println!("=== DRAM Write Buffer ({} blocks, {} LBAs, zipf 0.99, seed {}) ===", num_blocks, num_lbas, seed);
+    println!("Flush every 1000 writes\n");
+    println!("{:<12} {:>8} {:>10} {:>10} {:>10} {:>8}", "policy", "pages", "absorbed", "padding", "nand wr", "WAF");
+
+    let configs = [None]
+        .into_iter()
+        .chain([64, 256, 1024].into_iter().flat_map(|capacity| {
+            [EvictionPolicy::Lru, EvictionPolicy::Fifo, EvictionPolicy::PaddingAware]
+                .map(|policy| Some(WriteBufferConfig { capacity, policy }))
+        }));
+
+    for config in configs {
+        let mut ssd = SSD::new(num_blocks, num_lbas);
+        ssd.set_write_buffer(config)?;
+        for (i, request) in Phased::new(&phases, num_lbas, seed)?.enumerate() {
+            execute(&mut ssd, &request, i as u32)?;
+            if (i + 1) % 1000 == 0 {
+                ssd.flush()?;
+            }
+        }
+        ssd.flush()?;
+
+        let stats = ssd.stats();
+        let (policy, pages) = match config {
+            Some(c) => (format!("{:?}", c.policy), c.capacity.to_string()),
+            None => ("none".to_string(), "-".to_string()),
+        };
+        println!("{:<12} {:>8} {:>10} {:>10} {:>10} {:>8.4}",
+                 policy, pages, stats.buffer_absorbed, stats.padding_pages, stats.nand_writes, stats.waf());
+    }
+    Ok(())
+}
@@ -11,7 +11,7 @@ fn write(block: &mut Block, data:u32) -> Option<PhysicalAddress> {
         let cur_page = block.read(page_offset);
         if cur_page.state == PageState::Free {
             block.program(page_offset, data);
-            return Some(PhysicalAddress { block_id: block.id, page_offset: page_offset });
+            return Some(PhysicalAddress { block_id: block.id, page_offset });
         }
         page_offset += 1;
     }
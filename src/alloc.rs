@@ -0,0 +1,211 @@
+// 빈 블록 할당: 다음 Active Block으로 어떤 빈 블록을 쓸지
+// 빈 블록을 번호 순 / erase 횟수 순으로 정렬해 두고 무작위 선택용 목록도 함께 유지해서
+// 어느 정책이든 전체 스캔 없이 O(log n)에 고름
+use std::collections::BTreeSet;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::block::{Block, BlockState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AllocPolicy {
+    // 번호가 가장 작은 빈 블록 (기존 동작: 앞쪽 블록만 계속 재사용됨)
+    #[default]
+    FirstFree,
+    // erase 횟수가 가장 적은 빈 블록 (같으면 번호 순)
+    LowestErase,
+    // 마지막으로 할당한 블록의 다음 번호부터 차례로 (끝에 닿으면 처음부터)
+    RoundRobin,
+    // 무작위 (SSD seed 사용)
+    Random,
+    // 호스트 쓰기(hot)는 가장 덜 닳은 블록, GC가 옮기는 데이터(cold)는 가장 많이 닳은 블록
+    HotCold,
+}
+
+impl AllocPolicy {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "first_free" => Ok(AllocPolicy::FirstFree),
+            "lowest_erase" => Ok(AllocPolicy::LowestErase),
+            "round_robin" => Ok(AllocPolicy::RoundRobin),
+            "random" => Ok(AllocPolicy::Random),
+            "hot_cold" => Ok(AllocPolicy::HotCold),
+            _ => Err(format!("Unknown allocation policy: {} (first_free, lowest_erase, round_robin, random, hot_cold)", name)),
+        }
+    }
+}
+
+// 새 블록에 쓸 데이터의 출처 (HotCold 정책만 구분)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteStream {
+    Host,
+    Gc,
+}
+
+// 할당할 수 있는 빈 블록 (배드 블록과 Active Block 제외)
+// 블록이 들어있는 동안 erase 횟수는 바뀌지 않으므로 (erase 직후에 넣고 할당할 때 뺌) 정렬 키로 그대로 씀
+#[derive(Debug, Clone, Default)]
+pub struct FreeBlockPool {
+    by_index: BTreeSet<usize>,
+    by_erase: BTreeSet<(u32, usize)>,
+    // Random: 빈 블록 목록과 블록별 (목록 내 위치, erase 횟수) - swap_remove로 O(1) 삭제
+    list: Vec<usize>,
+    slots: Vec<Option<(usize, u32)>>,
+    // 풀에 있는 블록들의 페이지 수 합 (블록마다 셀 모드가 다를 수 있음)
+    pages: usize,
+    page_counts: Vec<usize>,
+    // RoundRobin: 마지막으로 할당한 블록
+    cursor: usize,
+}
+
+impl FreeBlockPool {
+    pub fn new(num_blocks: usize) -> Self {
+        FreeBlockPool { slots: vec![None; num_blocks], page_counts: vec![0; num_blocks], ..Default::default() }
+    }
+
+    // 블록 상태에서 다시 만듦 (스냅샷 복원, 배드 블록 배치 뒤)
+    pub fn from_blocks(blocks: &[Block], active_block_idx: usize, cursor: usize) -> Self {
+        Self::from_blocks_where(blocks, cursor, |idx, block| is_allocatable(block) && idx != active_block_idx)
+    }
+
+    // 풀에 넣을 블록을 FTL이 정함 (SLC 캐시 FTL: 정적 캐시 블록과 Active Block 두 개 제외)
+    pub fn from_blocks_where(blocks: &[Block], cursor: usize, allocatable: impl Fn(usize, &Block) -> bool) -> Self {
+        let mut pool = FreeBlockPool::new(blocks.len());
+        for (idx, block) in blocks.iter().enumerate() {
+            if allocatable(idx, block) {
+                pool.insert(block);
+            }
+        }
+        pool.cursor = cursor;
+        pool
+    }
+
+    // 막 지워진 블록을 넣음 (블록 id = 블록 번호)
+    pub fn insert(&mut self, block: &Block) {
+        let (idx, erase_count) = (block.id as usize, block.erase_count);
+        if self.slots[idx].is_some() {
+            return;
+        }
+        self.pages += block.pages.len();
+        self.page_counts[idx] = block.pages.len();
+        self.by_index.insert(idx);
+        self.by_erase.insert((erase_count, idx));
+        self.slots[idx] = Some((self.list.len(), erase_count));
+        self.list.push(idx);
+    }
+
+    pub fn remove(&mut self, idx: usize) -> bool {
+        let Some((pos, erase_count)) = self.slots[idx].take() else { return false };
+        self.pages -= self.page_counts[idx];
+        self.by_index.remove(&idx);
+        self.by_erase.remove(&(erase_count, idx));
+        self.list.swap_remove(pos);
+        if let Some(&moved) = self.list.get(pos) {
+            self.slots[moved] = Some((pos, self.slots[moved].expect("listed block has a slot").1));
+        }
+        true
+    }
+
+    // 풀에 있는 블록의 셀 모드가 바뀌어 페이지 수가 달라짐 (목록 순서는 그대로)
+    pub fn update_pages(&mut self, block: &Block) {
+        let idx = block.id as usize;
+        if self.slots[idx].is_some() {
+            self.pages = self.pages - self.page_counts[idx] + block.pages.len();
+            self.page_counts[idx] = block.pages.len();
+        }
+    }
+
+    // 정책에 따라 빈 블록 하나를 골라 풀에서 뺌
+    pub fn allocate(&mut self, policy: AllocPolicy, stream: WriteStream, rng: &mut impl Rng) -> Option<usize> {
+        let idx = self.choose(policy, stream, rng)?;
+        self.take(idx);
+        Some(idx)
+    }
+
+    // 정책이 고를 블록 (풀은 그대로, 고른 블록을 쓰려면 take)
+    pub fn choose(&self, policy: AllocPolicy, stream: WriteStream, rng: &mut impl Rng) -> Option<usize> {
+        match (policy, stream) {
+            (AllocPolicy::FirstFree, _) => self.first(),
+            (AllocPolicy::LowestErase, _) | (AllocPolicy::HotCold, WriteStream::Host) => {
+                self.by_erase.first().map(|&(_, idx)| idx)
+            }
+            (AllocPolicy::HotCold, WriteStream::Gc) => self.by_erase.last().map(|&(_, idx)| idx),
+            (AllocPolicy::RoundRobin, _) => {
+                self.by_index.range(self.cursor + 1..).next().or_else(|| self.by_index.first()).copied()
+            }
+            (AllocPolicy::Random, _) => {
+                (!self.list.is_empty()).then(|| self.list[rng.gen_range(0..self.list.len())])
+            }
+        }
+    }
+
+    // 블록을 할당한 것으로 풀에서 뺌 (RoundRobin은 다음에 이 블록 다음부터)
+    pub fn take(&mut self, idx: usize) -> bool {
+        let removed = self.remove(idx);
+        if removed {
+            self.cursor = idx;
+        }
+        removed
+    }
+
+    // 번호가 가장 작은 빈 블록 (FirstFree)
+    pub fn first(&self) -> Option<usize> {
+        self.by_index.first().copied()
+    }
+
+    // erase 횟수가 가장 적은 빈 블록 (erase 횟수, 번호)
+    pub fn lowest_erase(&self) -> Option<(u32, usize)> {
+        self.by_erase.first().copied()
+    }
+
+    pub fn contains(&self, idx: usize) -> bool {
+        self.slots[idx].is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    // 풀의 블록을 모두 열었을 때 프로그램할 수 있는 페이지 수
+    pub fn free_pages(&self) -> usize {
+        self.pages
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    // 풀이 블록 상태와 맞는지 (빈 블록 = Free 상태이고 배드가 아닌 Active 외 블록, erase 횟수도 같아야 함)
+    pub fn check(&self, blocks: &[Block], active_block_idx: usize) -> Result<(), String> {
+        self.check_where(blocks, |idx, block| is_allocatable(block) && idx != active_block_idx)
+    }
+
+    pub fn check_where(&self, blocks: &[Block], allocatable: impl Fn(usize, &Block) -> bool) -> Result<(), String> {
+        for (idx, block) in blocks.iter().enumerate() {
+            let expected = allocatable(idx, block);
+            match self.slots[idx] {
+                Some(_) if !expected => return Err(format!("Block {} is in the free pool but is not allocatable", idx)),
+                None if expected => return Err(format!("Free Block {} is missing from the free pool", idx)),
+                Some((_, erase_count)) if erase_count != block.erase_count => {
+                    return Err(format!("Free pool has Block {} at {} erases, but it has {}", idx, erase_count, block.erase_count));
+                }
+                _ => {}
+            }
+        }
+        let pages: usize = self.list.iter().map(|&idx| blocks[idx].pages.len()).sum();
+        if pages != self.pages {
+            return Err(format!("Free pool counts {} pages, but its blocks have {}", self.pages, pages));
+        }
+        Ok(())
+    }
+}
+
+fn is_allocatable(block: &Block) -> bool {
+    block.state == BlockState::Free && !block.is_bad
+}
@@ -0,0 +1,360 @@
+// ssd-sim: Rust 코드를 작성하지 않고 시뮬레이션을 돌리기 위한 명령줄 도구
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io::BufWriter;
+use std::process;
+
+use ssd_rs::cell::CellMode;
+use ssd_rs::experiment::{self, Experiment, ExperimentPhase, Geometry, Sweep};
+use ssd_rs::gc::{GcPolicy, GcScheduler};
+use ssd_rs::nbd::NbdServer;
+use ssd_rs::shell::{Outcome, Shell};
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::timing::{TimedSSD, TimingModel};
+use ssd_rs::trace::{self, ReplayConfig, TraceFormat};
+use ssd_rs::workload::*;
+use ssd_rs::write_buffer::{EvictionPolicy, WriteBufferConfig};
+
+const USAGE: &str = "\
+Usage: ssd-sim <command> [options]
+
+Commands:
+  run <experiment.json> [--csv FILE]
+      Run a declarative experiment (all sweeps and repetitions) and print the results table.
+
+  sweep-op [--blocks N] [--from P] [--to P] [--step P] [--pattern PAT]
+           [--multiple X] [--reps R] [--seed S] [--csv FILE]
+      Fill the device sequentially, then overwrite it X times with PAT for every OP ratio.
+
+  workload [--blocks N] [--op P] [--pattern PAT] [--requests N | --multiple X] [--fill]
+           [--read R] [--trim T] [--seed S] [--load SNAP] [--save SNAP]
+           [--stats-json FILE] [--heatmap]
+      Run one synthetic workload against a new (or restored) device.
+
+  replay <msr|spc|blkparse> <trace> [--blocks N] [--op P] [--lba-size BYTES] [--timed]
+           [--load SNAP] [--save SNAP] [--stats-json FILE] [--heatmap]
+      Replay a block I/O trace file.
+
+  stats <snapshot> [--json FILE] [--heatmap]
+      Print statistics of a saved device snapshot.
+
+  shell [--blocks N] [--op P] [--load SNAP] [--script FILE] [--save SNAP]
+      Interactive shell for stepping the FTL by hand (type 'help' inside).
+      With --script, replays the commands in FILE and exits.
+
+  nbd [--blocks N] [--op P] [--unix PATH | --listen ADDR] [--page-size BYTES]
+      [--once] [--load SNAP] [--save SNAP]
+      Serve the device over the NBD protocol (default: unix socket /tmp/ssd-sim.sock).
+      On non-Unix platforms --listen is required.
+      Statistics are printed (and --save written) after every client disconnects.
+      e.g. nbd-client -unix /tmp/ssd-sim.sock /dev/nbd0 && mkfs.ext4 /dev/nbd0
+
+Patterns (PAT): seq | uniform | zipf:THETA | hotcold:ACCESS/LBAS | shifting:ACCESS/LBAS/EVERY
+New devices (workload, replay, shell, nbd) also take --cell-mode slc|mlc|tlc|qlc
+and --write-buffer PAGES[:lru|fifo|padding] (DRAM write buffer, default policy lru).
+--reserved-blocks N sets OP by reserving N blocks instead of a ratio (--op).
+Defaults: --blocks 256, --op 25, --pattern uniform, --seed 0, --cell-mode mlc";
+
+// 위치 인자 + "--key value" / "--flag" 옵션
+struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+// 값을 받지 않는 옵션
+const FLAGS: [&str; 4] = ["--fill", "--timed", "--heatmap", "--once"];
+
+impl Args {
+    fn parse(raw: &[String]) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut iter = raw.iter();
+        while let Some(arg) = iter.next() {
+            if FLAGS.contains(&arg.as_str()) {
+                options.insert(arg.clone(), String::new());
+            } else if arg.starts_with("--") {
+                let value = iter.next().ok_or_else(|| format!("Option {} needs a value", arg))?;
+                options.insert(arg.clone(), value.clone());
+            } else {
+                positional.push(arg.clone());
+            }
+        }
+        Ok(Args { positional, options })
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    fn num<T: std::str::FromStr>(&self, name: &str, default: T) -> Result<T, String> {
+        match self.get(name) {
+            Some(v) => v.parse().map_err(|_| format!("Invalid value for {}: '{}'", name, v)),
+            None => Ok(default),
+        }
+    }
+
+    fn positional(&self, idx: usize, what: &str) -> Result<&str, String> {
+        self.positional.get(idx).map(String::as_str).ok_or_else(|| format!("Missing {}", what))
+    }
+}
+
+fn main() {
+    let raw: Vec<String> = env::args().skip(1).collect();
+    let Some(command) = raw.first() else {
+        println!("{}", USAGE);
+        return;
+    };
+
+    let result = Args::parse(&raw[1..]).and_then(|args| match command.as_str() {
+        "run" => cmd_run(&args),
+        "sweep-op" => cmd_sweep_op(&args),
+        "workload" => cmd_workload(&args),
+        "replay" => cmd_replay(&args),
+        "stats" => cmd_stats(&args),
+        "shell" => cmd_shell(&args),
+        "nbd" => cmd_nbd(&args),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        other => Err(format!("Unknown command '{}'\n\n{}", other, USAGE)),
+    });
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn cmd_run(args: &Args) -> Result<(), String> {
+    let path = args.positional(0, "experiment file")?;
+    let exp = experiment::load(path)?;
+    run_experiment(&exp, args.get("--csv"))
+}
+
+fn cmd_sweep_op(args: &Args) -> Result<(), String> {
+    let from: f64 = args.num("--from", 5.0)?;
+    let to: f64 = args.num("--to", 50.0)?;
+    let step: f64 = args.num("--step", 5.0)?;
+    if step <= 0.0 || to < from {
+        return Err("--from/--to/--step must describe an increasing range".to_string());
+    }
+
+    let mut values = Vec::new();
+    let mut op = from;
+    while op <= to + 1e-9 {
+        values.push(serde_json::json!(op));
+        op += step;
+    }
+
+    let pattern = AddressPattern::parse(args.get("--pattern").unwrap_or("uniform"))?;
+    let exp = Experiment {
+        name: "op_sweep".to_string(),
+        geometry: Geometry { num_blocks: args.num("--blocks", 256)?, num_lbas: None, op_pct: Some(from), reserved_blocks: None, bad_blocks: 0, cell_mode: CellMode::default() },
+        ftl: Default::default(),
+        timing: None,
+        warmup: vec![ExperimentPhase {
+            workload: WorkloadSpec::new(AddressPattern::Sequential { start: 0 }),
+            requests: None,
+            capacity_multiple: Some(1.0),
+        }],
+        measure: vec![ExperimentPhase {
+            workload: WorkloadSpec::new(pattern),
+            requests: None,
+            capacity_multiple: Some(args.num("--multiple", 10.0)?),
+        }],
+        seed: args.num("--seed", 0)?,
+        repetitions: args.num("--reps", 1)?,
+        sweep: vec![Sweep { param: "geometry.op_pct".to_string(), values }],
+    };
+    run_experiment(&exp, args.get("--csv"))
+}
+
+fn run_experiment(exp: &Experiment, csv: Option<&str>) -> Result<(), String> {
+    println!("=== Experiment '{}' ===\n", exp.name);
+    let rows = experiment::run(exp)?;
+    experiment::print_table(&rows);
+
+    if let Some(path) = csv {
+        experiment::write_csv(&rows, path)?;
+        println!("\nResults written to {}", path);
+    }
+    Ok(())
+}
+
+// --load 스냅샷이 있으면 복원, 없으면 --blocks/--op로 새 장치 생성
+fn open_device(args: &Args) -> Result<SSD, String> {
+    if let Some(path) = args.get("--load") {
+        println!("Restored device from {}", path);
+        return SSD::load_snapshot(path);
+    }
+
+    let num_blocks: usize = args.num("--blocks", 256)?;
+    // --reserved-blocks가 있으면 --op 대신 예약 블록 수로 OP를 정함
+    let (op_pct, reserved_blocks) = match args.get("--reserved-blocks") {
+        Some(_) => (None, Some(args.num("--reserved-blocks", 0)?)),
+        None => (Some(args.num("--op", 25.0)?), None),
+    };
+    let cell_mode = CellMode::parse(args.get("--cell-mode").unwrap_or("mlc"))?;
+    let geometry = Geometry { num_blocks, num_lbas: None, op_pct, reserved_blocks, bad_blocks: 0, cell_mode };
+    let mut ssd = SSD::with_seed(num_blocks, geometry.resolve_num_lbas()?, args.num("--seed", 0)?);
+    for idx in 0..num_blocks {
+        ssd.set_cell_mode(idx, cell_mode)?;
+    }
+    if let Some(spec) = args.get("--write-buffer") {
+        let (capacity, policy) = spec.split_once(':').unwrap_or((spec, "lru"));
+        let capacity = capacity.parse().map_err(|_| format!("Invalid --write-buffer size '{}'", capacity))?;
+        ssd.set_write_buffer(Some(WriteBufferConfig { capacity, policy: EvictionPolicy::parse(policy)? }))?;
+    }
+    Ok(ssd)
+}
+
+fn cmd_workload(args: &Args) -> Result<(), String> {
+    let mut ssd = open_device(args)?;
+    let num_lbas = ssd.num_lbas();
+    let seed: u64 = args.num("--seed", 0)?;
+
+    let read: f64 = args.num("--read", 0.0)?;
+    let trim: f64 = args.num("--trim", 0.0)?;
+    let mut spec = WorkloadSpec::new(AddressPattern::parse(args.get("--pattern").unwrap_or("uniform"))?);
+    spec.mix = OpMix { read, write: (1.0 - read - trim).max(0.0), trim };
+
+    let requests = match args.get("--requests") {
+        Some(_) => args.num("--requests", 0u64)?,
+        None => (num_lbas as f64 * args.num("--multiple", 10.0)?) as u64,
+    };
+
+    let mut phases = Vec::new();
+    if args.flag("--fill") {
+        phases.push(Phase {
+            workload: WorkloadSpec::new(AddressPattern::Sequential { start: 0 }),
+            requests: num_lbas as u64,
+        });
+    }
+    phases.push(Phase { workload: spec, requests });
+
+    for (i, request) in Phased::new(&phases, num_lbas, seed)?.enumerate() {
+        execute(&mut ssd, &request, i as u32)?;
+    }
+
+    finish(args, &mut ssd)
+}
+
+fn cmd_replay(args: &Args) -> Result<(), String> {
+    let format = TraceFormat::from_name(args.positional(0, "trace format")?)?;
+    let path = args.positional(1, "trace file")?;
+    let records = trace::load_trace(path, format)?;
+    let config = ReplayConfig::new(args.num("--lba-size", 4096)?, true)?;
+    let ssd = open_device(args)?;
+
+    println!("Replaying {} ({} requests)", path, records.len());
+    let mut ssd = if args.flag("--timed") {
+        let scheduler = GcScheduler::new(GcPolicy::GreedyPreemptive, 2, 4);
+        let mut timed = TimedSSD::new(ssd, TimingModel::default(), scheduler);
+        let summary = trace::replay(&mut timed, &records, &config)?;
+        let lat = &summary.request_latency;
+        println!("Request latency(us): mean {:.1} | p99 {} | p99.9 {} | max {}",
+                 lat.mean(), lat.percentile(99.0), lat.percentile(99.9), lat.max());
+        timed.into_inner()
+    } else {
+        let mut ssd = ssd;
+        trace::replay(&mut ssd, &records, &config)?;
+        ssd
+    };
+
+    finish(args, &mut ssd)
+}
+
+fn cmd_stats(args: &Args) -> Result<(), String> {
+    let mut ssd = SSD::load_snapshot(args.positional(0, "snapshot file")?)?;
+    let mut rest = Args { positional: Vec::new(), options: args.options.clone() };
+    if let Some(json) = args.get("--json") {
+        rest.options.insert("--stats-json".to_string(), json.to_string());
+    }
+    finish(&rest, &mut ssd)
+}
+
+fn cmd_shell(args: &Args) -> Result<(), String> {
+    let mut shell = Shell::new(open_device(args)?);
+    match args.get("--script") {
+        Some(path) => {
+            if let Outcome::Output(out) = shell.run_script(path)? {
+                println!("{}", out);
+            }
+        }
+        None => shell.run_interactive()?,
+    }
+
+    if let Some(path) = args.get("--save") {
+        shell.into_inner().save_snapshot(path)?;
+        println!("Snapshot saved to {}", path);
+    }
+    Ok(())
+}
+
+fn cmd_nbd(args: &Args) -> Result<(), String> {
+    let mut server = NbdServer::new(open_device(args)?, args.num("--page-size", 4096)?)?;
+    println!("Exporting {} bytes ({} LBAs)", server.size(), server.ssd().num_lbas());
+
+    let once = args.flag("--once");
+    let mut result = Ok(());
+    let on_disconnect = |server: &mut NbdServer<SSD>| {
+        println!("Client disconnected");
+        result = finish(args, server.ssd_mut());
+        result.is_ok() && !once
+    };
+
+    match args.get("--listen") {
+        Some(addr) => {
+            println!("Listening on {}", addr);
+            server.listen_tcp(addr, on_disconnect)?
+        }
+        #[cfg(unix)]
+        None => {
+            let path = args.get("--unix").unwrap_or("/tmp/ssd-sim.sock");
+            println!("Listening on {}", path);
+            server.listen_unix(path, on_disconnect)?
+        }
+        // 유닉스 소켓이 없는 플랫폼에서는 --listen 이 필수
+        #[cfg(not(unix))]
+        None => return Err("Unix sockets are not supported on this platform; use --listen <addr>".to_string()),
+    }
+    result
+}
+
+// 공통 마무리: 통계 출력, 히트맵, 통계 JSON 저장, 스냅샷 저장
+fn finish(args: &Args, ssd: &mut SSD) -> Result<(), String> {
+    let total_pages: usize = ssd.blocks().iter().map(|b| b.pages.len()).sum();
+    println!("\nDevice: {} blocks ({} pages), {} LBAs, OP {:.2}%",
+             ssd.blocks().len(), total_pages, ssd.num_lbas(),
+             experiment::op_ratio(total_pages, ssd.num_lbas()));
+    println!("{}", ssd.capacity());
+    println!("{}", ssd.stats());
+
+    let erase = ssd.erase_distribution();
+    println!("  Erase: min {} | p50 {} | p99 {} | max {} | std-dev {:.2}",
+             erase.min, erase.p50, erase.p99, erase.max, erase.std_dev);
+
+    if args.flag("--heatmap") {
+        println!();
+        ssd.print_heatmap();
+    }
+
+    if let Some(path) = args.get("--stats-json") {
+        let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+        let report = serde_json::json!({ "stats": ssd.stats(), "erase_distribution": erase });
+        serde_json::to_writer_pretty(BufWriter::new(file), &report).map_err(|e| e.to_string())?;
+        println!("Statistics written to {}", path);
+    }
+
+    if let Some(path) = args.get("--save") {
+        ssd.save_snapshot(path)?;
+        println!("Snapshot saved to {}", path);
+    }
+    Ok(())
+}
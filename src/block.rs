@@ -1,7 +1,10 @@
 use std::fmt;
+use serde::{Deserialize, Serialize};
 use super::page::{Page, PageState};
+use super::cell::CellMode;
 
-pub const PAGES_PER_BLOCK: usize = 64;
+// 기본 셀 모드(MLC) 블록의 페이지 수 (모드별 페이지 수는 CellMode::pages_per_block)
+pub const PAGES_PER_BLOCK: usize = CellMode::Mlc.pages_per_block();
 
 #[derive(Debug)]
 pub struct WearStats {
@@ -12,44 +15,77 @@ pub struct WearStats {
 }
 
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum BlockState {
     Free,
     Active,
     Full,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Block {
     pub id: u32,
     pub pages: Vec<Page>,
     pub erase_count: u32,
     pub is_bad: bool,
     pub state: BlockState,
+    // 셀 모드 (옛 스냅샷에는 없으므로 기본값 MLC)
+    #[serde(default)]
+    pub cell_mode: CellMode,
 }
 
 impl Block {
     pub fn new(id: u32) -> Self {
-        let mut pages = Vec::with_capacity(PAGES_PER_BLOCK);
-        for _ in 0..PAGES_PER_BLOCK {
-            pages.push(Page {
-                content: 0,
-                state: PageState::Free,
-            });
-        }
+        Self::with_mode(id, CellMode::default())
+    }
 
+    pub fn with_mode(id: u32, cell_mode: CellMode) -> Self {
         Block {
             id,
-            pages,
+            pages: Self::free_pages(cell_mode),
             erase_count: 0,
             is_bad: false,
             state: BlockState::Free,
+            cell_mode,
+        }
+    }
+
+    fn free_pages(cell_mode: CellMode) -> Vec<Page> {
+        let mut pages = Vec::with_capacity(cell_mode.pages_per_block());
+        for _ in 0..cell_mode.pages_per_block() {
+            pages.push(Page {
+                content: 0,
+                state: PageState::Free,
+            });
         }
+        pages
+    }
+
+    // 셀 모드 변경: 지워진(Free) 블록만 가능 (erase 횟수는 그대로 - 같은 물리 셀)
+    pub fn set_cell_mode(&mut self, cell_mode: CellMode) -> Result<(), String> {
+        if self.is_bad {
+            return Err(format!("Block {}: Cannot change the cell mode of a BAD block", self.id));
+        }
+        if self.state != BlockState::Free {
+            return Err(format!("Block {}: Cell mode can only change on an erased block (State: {:?})", self.id, self.state));
+        }
+        self.cell_mode = cell_mode;
+        self.pages = Self::free_pages(cell_mode);
+        Ok(())
+    }
+
+    pub fn pages_per_block(&self) -> usize {
+        self.pages.len()
+    }
+
+    // 보장 P/E 사이클을 다 썼는지
+    pub fn is_worn_out(&self) -> bool {
+        self.erase_count >= self.cell_mode.endurance()
     }
 
     // 2. 읽기 (Read): 특정 오프셋의 페이지를 읽음
     pub fn read(&self, page_offset: usize) -> &Page {
-        if page_offset >= PAGES_PER_BLOCK {
+        if page_offset >= self.pages.len() {
             panic!("Block {}: Page offset {} is out of bounds!", self.id, page_offset);
         }
         &self.pages[page_offset]
@@ -60,9 +96,18 @@ impl Block {
         if self.is_bad {
             panic!("Block {}: Cannot write to a BAD block!", self.id);
         }
-        if page_offset >= PAGES_PER_BLOCK {
+        if page_offset >= self.pages.len() {
             panic!("Block {}: Page offset {} out of bounds", self.id, page_offset);
         }
+        // [Constraint] 멀티 비트 셀: 같은 워드라인의 아래 페이지, 다음 워드라인의 아래 페이지가 먼저 프로그램돼야 함
+        if let Some(missing) = self.cell_mode.program_prerequisites(page_offset)
+            .find(|&p| self.pages[p].state == PageState::Free)
+        {
+            panic!(
+                "Block {} Page {}: Paired page {} must be programmed first ({:?} order)",
+                self.id, page_offset, missing, self.cell_mode
+            );
+        }
 
         let page = &mut self.pages[page_offset];
 
@@ -84,7 +129,7 @@ impl Block {
         }
 
         // 만약 마지막 페이지까지 다 썼다면 Full로 변경
-        if page_offset == PAGES_PER_BLOCK - 1 {
+        if page_offset == self.pages.len() - 1 {
             self.state = BlockState::Full;
         }
     }
@@ -114,8 +159,9 @@ impl fmt::Debug for Block {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "=== Physical Block #{} ===", self.id)?;
         writeln!(f, "  State:      {:?}", self.state)?;
+        writeln!(f, "  Cell Mode:  {:?}", self.cell_mode)?;
         writeln!(f, "  Erase Cnt:  {}", self.erase_count)?;
-        writeln!(f, "  Valid Pgs:  {}/{}", self.count_valid_pages(), PAGES_PER_BLOCK)?;
+        writeln!(f, "  Valid Pgs:  {}/{}", self.count_valid_pages(), self.pages.len())?;
         writeln!(f, "  Is Bad:     {}", self.is_bad)?;
         write!(f, "  Map: [")?;
 
@@ -0,0 +1,254 @@
+// SSD를 파일처럼 다루는 바이트 단위 어댑터 (std::io::Read + Write + Seek)
+// BlockDevice면 어떤 FTL이든(시간 모델, 섹터 장치 포함) 그대로 감쌀 수 있습니다.
+// LBA 공간을 [0, num_lbas * page_size) 바이트로 펼치고, 페이지 경계에 맞지 않는 쓰기는
+// 기존 페이지를 읽어 합친 뒤 페이지 전체를 다시 쓰는 read-modify-write로 처리합니다.
+// FTL은 페이지마다 u32 하나만 저장하므로 실제 바이트는 LBA별로 따로 보관하고,
+// SSD에는 페이지 체크섬을 써서 매핑/GC/통계는 그대로 FTL이 담당하게 합니다.
+// 읽을 때 FTL이 돌려준 체크섬과 보관한 바이트를 비교해서, 전원 차단으로 사라진 쓰기의 바이트는 돌려주지 않습니다.
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use super::device::BlockDevice;
+
+// 바이트 단위 I/O 통계 (페이지 단위 통계는 BlockDevice::stats())
+#[derive(Debug, Clone, Default)]
+pub struct ByteStats {
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+    // 페이지 일부만 덮은 쓰기 횟수 (페이지 단위)
+    pub partial_page_writes: u64,
+}
+
+pub struct SsdFile<D: BlockDevice> {
+    ssd: D,
+    page_size: usize,
+    // LBA별 실제 바이트 내용 (한 번도 안 쓴 LBA / trim된 LBA는 None)
+    // SSD 용량이 바뀔 수 있으므로(resize) 접근할 때마다 num_lbas에 맞춤
+    data: Vec<Option<Vec<u8>>>,
+    pos: u64,
+    stats: ByteStats,
+}
+
+impl<D: BlockDevice> SsdFile<D> {
+    // page_size: LBA 하나가 담는 바이트 수 (보통 4096)
+    pub fn new(ssd: D, page_size: usize) -> Result<Self, String> {
+        if page_size == 0 {
+            return Err("Page size must be at least 1 byte".to_string());
+        }
+        let num_lbas = ssd.num_lbas();
+        Ok(SsdFile {
+            ssd,
+            page_size,
+            data: vec![None; num_lbas],
+            pos: 0,
+            stats: ByteStats::default(),
+        })
+    }
+
+    // 장치 전체 크기 (바이트)
+    pub fn size(&self) -> u64 {
+        (self.ssd.num_lbas() * self.page_size) as u64
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    pub fn byte_stats(&self) -> &ByteStats {
+        &self.stats
+    }
+
+    // 호스트가 실제로 보낸 바이트 대비 NAND에 프로그램된 바이트 (부분 쓰기의 패딩 + GC 이동 포함)
+    pub fn byte_waf(&self) -> f64 {
+        if self.stats.bytes_written == 0 { return 0.0 }
+        (self.ssd.stats().nand_writes * self.page_size as u64) as f64 / self.stats.bytes_written as f64
+    }
+
+    pub fn ssd(&self) -> &D {
+        &self.ssd
+    }
+
+    pub fn ssd_mut(&mut self) -> &mut D {
+        &mut self.ssd
+    }
+
+    pub fn into_inner(self) -> D {
+        self.ssd
+    }
+
+    // offset부터 buf 전체를 읽음 (안 쓴 영역은 0), 걸치는 페이지들을 범위 읽기 요청 하나로
+    pub fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+        self.sync_capacity();
+        self.check_range(offset, buf.len())?;
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        let (first, _) = self.locate(offset);
+        let (last, _) = self.locate(offset + buf.len() as u64 - 1);
+        let mapped = self.ssd.read_range(first, last - first + 1).map_err(io::Error::other)?;
+
+        let mut done = 0;
+        while done < buf.len() {
+            let (lba, in_page) = self.locate(offset + done as u64);
+            let n = (self.page_size - in_page).min(buf.len() - done);
+
+            match mapped[lba - first] {
+                Some(stored) => {
+                    let page = self.page_data(lba, stored)?;
+                    buf[done..done + n].copy_from_slice(&page[in_page..in_page + n]);
+                }
+                None => buf[done..done + n].fill(0),
+            }
+            done += n;
+        }
+        self.stats.bytes_read += buf.len() as u64;
+        Ok(())
+    }
+
+    // offset부터 buf 전체를 범위 쓰기 요청 하나로 씀 (페이지 일부만 덮으면 read-modify-write)
+    pub fn write_at(&mut self, offset: u64, buf: &[u8]) -> io::Result<()> {
+        self.sync_capacity();
+        self.check_range(offset, buf.len())?;
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        let (first, _) = self.locate(offset);
+        let mut pages = Vec::new();
+        let mut done = 0;
+        while done < buf.len() {
+            let (lba, in_page) = self.locate(offset + done as u64);
+            let n = (self.page_size - in_page).min(buf.len() - done);
+
+            let mut page = if n == self.page_size {
+                vec![0u8; self.page_size]
+            } else {
+                self.stats.partial_page_writes += 1;
+                // 기존 내용이 있으면 NAND에서 읽어와 합침
+                match self.ssd.read_for_update(lba).map_err(io::Error::other)? {
+                    Some(stored) => self.page_data(lba, stored)?.to_vec(),
+                    None => vec![0u8; self.page_size],
+                }
+            };
+            page[in_page..in_page + n].copy_from_slice(&buf[done..done + n]);
+            pages.push(page);
+            done += n;
+        }
+
+        let checksums: Vec<u32> = pages.iter().map(|page| checksum(page)).collect();
+        let result = self.ssd.write_range(first, &checksums);
+        for (i, page) in pages.into_iter().enumerate() {
+            self.data[first + i] = Some(page);
+        }
+        result.map_err(io::Error::other)?;
+        self.stats.bytes_written += buf.len() as u64;
+        Ok(())
+    }
+
+    // 페이지 전체가 덮이는 LBA만 trim (일부만 걸치는 페이지는 그대로 둠)
+    pub fn trim_at(&mut self, offset: u64, len: u64) -> io::Result<()> {
+        self.sync_capacity();
+        self.check_range(offset, len as usize)?;
+
+        let page_size = self.page_size as u64;
+        let first = offset.div_ceil(page_size) as usize;
+        let last = ((offset + len) / page_size) as usize;
+        if first < last {
+            self.ssd.trim_range(first, last - first).map_err(io::Error::other)?;
+            self.data[first..last].fill(None);
+        }
+        Ok(())
+    }
+
+    // FTL이 돌려준 체크섬과 맞는 바이트만 유효
+    // 다르면 FTL에 남은 건 이전 버전이고(전원 차단으로 최신 쓰기가 사라짐) 그 바이트는 보관하지 않았으므로 에러
+    fn page_data(&self, lba: usize, stored: u32) -> io::Result<&[u8]> {
+        match &self.data[lba] {
+            Some(page) if checksum(page) == stored => Ok(page),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("LBA {} holds stale data (its latest write was lost)", lba),
+            )),
+        }
+    }
+
+    // SSD를 ssd_mut()으로 resize했으면 바이트 보관소도 같은 LBA 수로 (줄어든 LBA의 내용은 버림)
+    fn sync_capacity(&mut self) {
+        let num_lbas = self.ssd.num_lbas();
+        if self.data.len() != num_lbas {
+            self.data.resize(num_lbas, None);
+        }
+    }
+
+    fn locate(&self, offset: u64) -> (usize, usize) {
+        let offset = offset as usize;
+        (offset / self.page_size, offset % self.page_size)
+    }
+
+    fn check_range(&self, offset: u64, len: usize) -> io::Result<()> {
+        match offset.checked_add(len as u64) {
+            Some(end) if end <= self.size() => Ok(()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("range {}+{} is beyond the device size {}", offset, len, self.size()),
+            )),
+        }
+    }
+}
+
+// 파일처럼 현재 위치부터 읽고, 장치 끝(또는 seek으로 그 너머)에서는 0바이트(EOF)
+impl<D: BlockDevice> Read for SsdFile<D> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos >= self.size() {
+            return Ok(0);
+        }
+        let n = (self.size().saturating_sub(self.pos) as usize).min(buf.len());
+        self.read_at(self.pos, &mut buf[..n])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+// 장치 끝을 넘는 부분은 쓰지 않음 (꽉 차면 0을 반환 -> write_all은 WriteZero 에러)
+impl<D: BlockDevice> Write for SsdFile<D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.pos >= self.size() {
+            return Ok(0);
+        }
+        let n = (self.size().saturating_sub(self.pos) as usize).min(buf.len());
+        self.write_at(self.pos, &buf[..n])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    // SSD의 쓰기 버퍼를 NAND에 내려씀 (버퍼가 없으면 할 일 없음)
+    fn flush(&mut self) -> io::Result<()> {
+        self.ssd.flush().map_err(io::Error::other)
+    }
+}
+
+impl<D: BlockDevice> Seek for SsdFile<D> {
+    fn seek(&mut self, from: SeekFrom) -> io::Result<u64> {
+        let (base, delta) = match from {
+            SeekFrom::Start(offset) => {
+                self.pos = offset;
+                return Ok(offset);
+            }
+            SeekFrom::End(delta) => (self.size(), delta),
+            SeekFrom::Current(delta) => (self.pos, delta),
+        };
+        self.pos = base
+            .checked_add_signed(delta)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "seek to a negative position"))?;
+        Ok(self.pos)
+    }
+}
+
+// FTL에 쓸 페이지 대표값 (FNV-1a)
+fn checksum(page: &[u8]) -> u32 {
+    page.iter().fold(0x811c_9dc5u32, |hash, &b| (hash ^ b as u32).wrapping_mul(0x0100_0193))
+}
@@ -0,0 +1,122 @@
+// Over-provisioning(OP) 설정과 용량 보고
+// raw: 배드 블록을 뺀 물리 페이지, user: 호스트에 보이는 LBA 수, spare: raw - user (고정 OP)
+// trim되었거나 한 번도 안 쓴 LBA의 공간도 GC가 쓸 수 있으므로 실제 여유 공간은 raw - 유효 페이지 (동적 OP)
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::block::Block;
+use super::page::PageState;
+
+// OP 비율(%) = (물리 페이지 - 논리 페이지) / 논리 페이지 * 100
+pub fn op_ratio(physical_pages: usize, num_lbas: usize) -> f64 {
+    (physical_pages as f64 - num_lbas as f64) / num_lbas as f64 * 100.0
+}
+
+// OP를 정하는 방법: 비율 또는 예약 블록 수
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OverProvisioning {
+    Percent { pct: f64 },
+    // 호스트 용량에서 빼둘 블록 수 (user = (블록 수 - reserved) * 블록당 페이지)
+    ReservedBlocks { blocks: usize },
+}
+
+impl OverProvisioning {
+    // 블록 num_blocks개 (블록당 pages_per_block 페이지)에서 호스트에 내보낼 LBA 수
+    // GC가 돌려면 여유 공간이 최소 한 블록은 있어야 함
+    pub fn user_pages(&self, num_blocks: usize, pages_per_block: usize) -> Result<usize, String> {
+        let raw = num_blocks * pages_per_block;
+        let user = match *self {
+            OverProvisioning::Percent { pct } => {
+                if !pct.is_finite() || pct < 0.0 {
+                    return Err(format!("Invalid OP ratio {}%", pct));
+                }
+                (raw as f64 / (1.0 + pct / 100.0)) as usize
+            }
+            OverProvisioning::ReservedBlocks { blocks } => num_blocks.saturating_sub(blocks) * pages_per_block,
+        };
+        check_user_pages(user, raw, pages_per_block)?;
+        Ok(user)
+    }
+}
+
+// 내보낼 용량이 0이거나 여유 공간이 한 블록보다 작으면 에러
+pub fn check_user_pages(user: usize, raw: usize, pages_per_block: usize) -> Result<(), String> {
+    if user == 0 || user + pages_per_block > raw {
+        return Err(format!("Cannot export {} LBAs from {} raw pages: at least one spare block ({} pages) is needed",
+            user, raw, pages_per_block));
+    }
+    Ok(())
+}
+
+// trim된 공간을 GC가 언제 회수하는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpMode {
+    // trim된 페이지를 바로 무효화 -> victim 선정 때 회수 가능한 공간(여분 OP)으로 계산됨
+    #[default]
+    Dynamic,
+    // trim은 매핑에서만 숨기고 페이지는 유효한 채로 둠 -> GC가 계속 옮기고, 여유 공간은 고정 OP뿐
+    // (같은 LBA를 다시 쓸 때 비로소 무효화)
+    Static,
+}
+
+impl OpMode {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "dynamic" => Ok(OpMode::Dynamic),
+            "static" => Ok(OpMode::Static),
+            _ => Err(format!("Unknown OP mode: {} (dynamic, static)", name)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capacity {
+    // 배드 블록을 뺀 물리 페이지
+    pub raw_pages: usize,
+    // 호스트에 보이는 용량 (LBA 수)
+    pub user_pages: usize,
+    // 유효 데이터가 든 물리 페이지 (고정 OP 모드에서 아직 회수하지 않은 trim 페이지 포함)
+    pub valid_pages: usize,
+}
+
+impl Capacity {
+    pub fn of(blocks: &[Block], user_pages: usize) -> Self {
+        let good = || blocks.iter().filter(|b| !b.is_bad);
+        Capacity {
+            raw_pages: good().map(|b| b.pages.len()).sum(),
+            user_pages,
+            valid_pages: good().flat_map(|b| &b.pages).filter(|p| p.state == PageState::Valid).count(),
+        }
+    }
+
+    // 고정 OP: 호스트가 전부 채워도 남는 공간
+    pub fn spare_pages(&self) -> usize {
+        self.raw_pages.saturating_sub(self.user_pages)
+    }
+
+    // 동적 OP: 지금 유효 데이터가 없는 모든 공간
+    pub fn effective_spare_pages(&self) -> usize {
+        self.raw_pages.saturating_sub(self.valid_pages)
+    }
+
+    pub fn op_pct(&self) -> f64 {
+        op_ratio(self.raw_pages, self.user_pages)
+    }
+
+    // 유효 데이터 대비 여유 공간 비율 (유효 페이지가 없으면 무한대)
+    pub fn effective_op_pct(&self) -> f64 {
+        op_ratio(self.raw_pages, self.valid_pages)
+    }
+}
+
+impl fmt::Display for Capacity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  Raw: {} pages | User: {} LBAs | Spare: {} pages (OP {:.2}%)",
+                 self.raw_pages, self.user_pages, self.spare_pages(), self.op_pct())?;
+        write!(f, "  Valid: {} pages | Effective spare: {} pages (OP {:.2}%)",
+               self.valid_pages, self.effective_spare_pages(), self.effective_op_pct())
+    }
+}
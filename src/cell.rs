@@ -0,0 +1,156 @@
+// 셀 모드 (셀당 비트 수)별 블록 특성
+// 같은 물리 블록이라도 셀당 비트 수가 늘면 페이지 수는 늘지만 수명은 줄고 프로그램/읽기는 느려집니다.
+// 한 워드라인에 bits개의 페이지(lower/upper/extra/top)가 겹쳐 있어서, 프로그램 순서에 제약이 있습니다.
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use super::timing::TimingModel;
+
+// 블록 하나의 워드라인 수 (모드와 무관한 물리 구조)
+pub const WORDLINES_PER_BLOCK: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CellMode {
+    Slc,
+    // 기본값: 페이지 64개 (기존 블록 구조와 같음)
+    #[default]
+    Mlc,
+    Tlc,
+    Qlc,
+}
+
+// 워드라인 안에서 페이지의 위치 (bit 0부터 순서대로)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageType {
+    Lower,
+    Upper,
+    Extra,
+    Top,
+}
+
+impl CellMode {
+    pub const ALL: [CellMode; 4] = [CellMode::Slc, CellMode::Mlc, CellMode::Tlc, CellMode::Qlc];
+
+    pub const fn bits_per_cell(self) -> usize {
+        match self {
+            CellMode::Slc => 1,
+            CellMode::Mlc => 2,
+            CellMode::Tlc => 3,
+            CellMode::Qlc => 4,
+        }
+    }
+
+    pub const fn pages_per_block(self) -> usize {
+        WORDLINES_PER_BLOCK * self.bits_per_cell()
+    }
+
+    // 보장 P/E 사이클 (대략적인 데이터시트 수치)
+    pub fn endurance(self) -> u32 {
+        match self {
+            CellMode::Slc => 100_000,
+            CellMode::Mlc => 10_000,
+            CellMode::Tlc => 3_000,
+            CellMode::Qlc => 1_000,
+        }
+    }
+
+    // 페이지 읽기 / 프로그램, 블록 erase 지연 시간 (us)
+    pub fn timing(self) -> TimingModel {
+        let (read_us, program_us, erase_us) = match self {
+            CellMode::Slc => (25, 200, 1500),
+            CellMode::Mlc => (40, 350, 2500),
+            CellMode::Tlc => (50, 500, 3000),
+            CellMode::Qlc => (90, 1500, 3500),
+        };
+        TimingModel { read_us, program_us, erase_us }
+    }
+
+    // 페이지 오프셋 -> (워드라인, 페이지 종류)
+    // 오프셋 순서 자체가 합법적인 프로그램 순서: (wl + bit, bit) 오름차순
+    //   MLC: L0 L1 U0 L2 U1 L3 U2 ...   TLC: L0 L1 U0 L2 U1 X0 L3 U2 X1 ...
+    pub fn page_location(self, page_offset: usize) -> (usize, PageType) {
+        let (wordline, bit) = self.program_order()[page_offset];
+        (wordline, PageType::from_bit(bit))
+    }
+
+    // (워드라인, bit) -> 페이지 오프셋
+    pub fn page_offset(self, wordline: usize, bit: usize) -> usize {
+        self.tables().offsets[wordline * self.bits_per_cell() + bit]
+    }
+
+    // 같은 워드라인을 공유하는 다른 페이지들 (상위 페이지 프로그램 중 전원이 끊기면 같이 깨질 수 있음)
+    pub fn paired_pages(self, page_offset: usize) -> Vec<usize> {
+        let (wordline, _) = self.program_order()[page_offset];
+        (0..self.bits_per_cell())
+            .map(|bit| self.page_offset(wordline, bit))
+            .filter(|&offset| offset != page_offset)
+            .collect()
+    }
+
+    // 이 페이지를 프로그램하기 전에 먼저 프로그램돼 있어야 하는 페이지들
+    // 같은 워드라인의 아래 bit, 그리고 다음 워드라인의 아래 bit (이웃 셀 간섭을 줄이는 shadow 순서)
+    pub fn program_prerequisites(self, page_offset: usize) -> impl Iterator<Item = usize> {
+        let (wordline, bit) = self.program_order()[page_offset];
+        let same_wordline = (bit > 0).then(|| self.page_offset(wordline, bit - 1));
+        let next_wordline = (bit > 0 && wordline + 1 < WORDLINES_PER_BLOCK)
+            .then(|| self.page_offset(wordline + 1, bit - 1));
+        same_wordline.into_iter().chain(next_wordline)
+    }
+
+    fn program_order(self) -> &'static [(usize, usize)] {
+        &self.tables().order
+    }
+
+    // 프로그램할 때마다 쓰이므로 모드별로 한 번만 계산
+    fn tables(self) -> &'static OrderTables {
+        static TABLES: OnceLock<Vec<OrderTables>> = OnceLock::new();
+        let tables = TABLES.get_or_init(|| CellMode::ALL.iter().map(|&mode| OrderTables::new(mode)).collect());
+        &tables[self.bits_per_cell() - 1]
+    }
+
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "slc" => Ok(CellMode::Slc),
+            "mlc" => Ok(CellMode::Mlc),
+            "tlc" => Ok(CellMode::Tlc),
+            "qlc" => Ok(CellMode::Qlc),
+            _ => Err(format!("Unknown cell mode: {} (use slc, mlc, tlc or qlc)", name)),
+        }
+    }
+}
+
+struct OrderTables {
+    // 오프셋 -> (워드라인, bit)
+    order: Vec<(usize, usize)>,
+    // 워드라인 * bits + bit -> 오프셋
+    offsets: Vec<usize>,
+}
+
+impl OrderTables {
+    fn new(mode: CellMode) -> Self {
+        let bits = mode.bits_per_cell();
+        let mut order: Vec<(usize, usize)> = (0..WORDLINES_PER_BLOCK)
+            .flat_map(|wl| (0..bits).map(move |bit| (wl, bit)))
+            .collect();
+        order.sort_by_key(|&(wl, bit)| (wl + bit, bit));
+
+        let mut offsets = vec![0; order.len()];
+        for (offset, &(wl, bit)) in order.iter().enumerate() {
+            offsets[wl * bits + bit] = offset;
+        }
+        OrderTables { order, offsets }
+    }
+}
+
+impl PageType {
+    fn from_bit(bit: usize) -> Self {
+        match bit {
+            0 => PageType::Lower,
+            1 => PageType::Upper,
+            2 => PageType::Extra,
+            _ => PageType::Top,
+        }
+    }
+}
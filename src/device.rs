@@ -0,0 +1,77 @@
+// 모든 FTL 구현이 공유하는 호스트 인터페이스
+// 워크로드 실행, 트레이스 재생, 실험 실행기는 이 트레이트만 보고 동작하므로 FTL을 바꿔 끼워도 코드가 그대로입니다.
+// 시간 모델이 있는 장치(TimedSSD)는 set_arrival로 요청 도착 시각을 받고 clock_us로 현재 시각을 알려줍니다.
+use super::block::Block;
+use super::capacity::Capacity;
+use super::stats::{EraseDistribution, Stats};
+
+pub trait BlockDevice {
+    // 한 번도 안 쓴(또는 trim된) LBA는 None
+    fn read(&mut self, lba: usize) -> Result<Option<u32>, String>;
+
+    fn write(&mut self, lba: usize, data: u32) -> Result<(), String>;
+
+    // 부분 쓰기의 read-modify-write용 읽기 (기본 구현은 호스트 읽기로 집계됨)
+    fn read_for_update(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        self.read(lba)
+    }
+
+    fn trim(&mut self, lba: usize) -> Result<(), String>;
+
+    // 연속된 LBA 범위 요청 (start_lba부터)
+    // 기본 구현은 페이지마다 단일 연산을 반복하므로 FTL이 요청 단위로 처리하려면 직접 구현
+    fn write_range(&mut self, start_lba: usize, data: &[u32]) -> Result<(), String> {
+        for (i, &page) in data.iter().enumerate() {
+            self.write(start_lba + i, page)?;
+        }
+        Ok(())
+    }
+
+    fn read_range(&mut self, start_lba: usize, len: usize) -> Result<Vec<Option<u32>>, String> {
+        (start_lba..start_lba + len).map(|lba| self.read(lba)).collect()
+    }
+
+    fn trim_range(&mut self, start_lba: usize, len: usize) -> Result<(), String> {
+        (start_lba..start_lba + len).try_for_each(|lba| self.trim(lba))
+    }
+
+    // 휘발성 상태(쓰기 버퍼 등)를 NAND에 내려씀 (캐시가 없는 장치는 할 일 없음)
+    fn flush(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    // 호스트에 보이는 용량 (LBA 개수)
+    fn num_lbas(&self) -> usize;
+
+    // 물리 / 호스트 / 여유 용량
+    fn capacity(&self) -> Capacity {
+        Capacity::of(self.blocks(), self.num_lbas())
+    }
+
+    fn stats(&self) -> &Stats;
+
+    // warm-up 구간을 지우고 측정 구간만 집계할 때 (블록의 erase 횟수 등 물리 상태는 그대로)
+    fn reset_stats(&mut self);
+
+    fn blocks(&self) -> &[Block];
+
+    fn erase_distribution(&self) -> EraseDistribution {
+        EraseDistribution::from_blocks(self.blocks())
+    }
+
+    // 내부 일관성 검사 (검사기가 없는 장치는 항상 Ok)
+    fn check_invariants(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    // 장치 상태를 파일로 저장 (복원은 구현마다 load_snapshot 생성자로)
+    fn save_snapshot(&self, path: &str) -> Result<(), String>;
+
+    // 다음 요청의 도착 시각 (시간 모델이 없는 장치는 무시)
+    fn set_arrival(&mut self, _arrival_us: u64) {}
+
+    // 시간 모델이 있는 장치의 현재 시각 (없으면 None)
+    fn clock_us(&self) -> Option<u64> {
+        None
+    }
+}
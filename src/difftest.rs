@@ -0,0 +1,163 @@
+// 차등 테스트: 같은 무작위 연산열을 FTL과 HashMap 오라클에 동시에 실행해서 읽기 결과가 항상 같은지 확인
+// GC가 데이터를 옮기다 잃어버리거나 오래된 값을 돌려주면 바로 드러납니다.
+// 실패하면 연산열을 줄여가며(shrink) 같은 실패가 재현되는 가장 짧은 연산열을 찾아줍니다.
+use std::collections::HashMap;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::device::BlockDevice;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Write { lba: usize, data: u32 },
+    Read { lba: usize },
+    Trim { lba: usize },
+}
+
+// 전체 LBA를 오라클과 비교하는 주기 (연산 수), 마지막 연산 뒤에도 항상 비교
+const SWEEP_EVERY: usize = 64;
+
+// shrink 중 check를 다시 돌리는 최대 횟수
+const MAX_SHRINK_RUNS: usize = 2000;
+
+#[derive(Debug, Clone)]
+pub struct Failure {
+    // 실패를 재현하는 연산열 (shrink 후에는 최소화된 것)
+    pub ops: Vec<Op>,
+    // 실패가 드러난 연산 위치 (ops.len()이면 마지막 전체 비교에서 발견)
+    pub step: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} (at step {} of {})", self.message, self.step, self.ops.len())?;
+        writeln!(f, "Reproducer:")?;
+        for (i, op) in self.ops.iter().enumerate() {
+            writeln!(f, "  {:>4}: {:?}", i, op)?;
+        }
+        Ok(())
+    }
+}
+
+// 쓰기 70% / 읽기 20% / Trim 10%, 주소는 80%가 앞쪽 20% LBA에 몰리도록 (GC victim에 유효/무효 페이지가 섞이게)
+// 쓰는 값은 연산 번호라 오래된 값을 돌려주면 구분됨
+pub fn random_ops(num_lbas: usize, count: usize, seed: u64) -> Vec<Op> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let hot = (num_lbas / 5).max(1);
+
+    (0..count)
+        .map(|i| {
+            let lba = if rng.gen_bool(0.8) { rng.gen_range(0..hot) } else { rng.gen_range(0..num_lbas) };
+            match rng.gen_range(0..10) {
+                0..=6 => Op::Write { lba, data: i as u32 + 1 },
+                7..=8 => Op::Read { lba },
+                _ => Op::Trim { lba },
+            }
+        })
+        .collect()
+}
+
+// 새 장치에 ops를 실행하며 오라클과 비교 (장치 에러, panic, 내부 일관성 위반도 실패로 취급)
+pub fn check<D: BlockDevice>(make: &impl Fn() -> D, ops: &[Op]) -> Result<(), Failure> {
+    let fail = |step: usize, message: String| Failure { ops: ops.to_vec(), step, message };
+    let mut device = make();
+    let mut oracle: HashMap<usize, u32> = HashMap::new();
+
+    for (step, op) in ops.iter().enumerate() {
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| apply(&mut device, &mut oracle, op)));
+        match outcome {
+            Ok(Ok(())) => {}
+            Ok(Err(message)) => return Err(fail(step, message)),
+            Err(payload) => return Err(fail(step, format!("device panicked: {}", panic_message(&payload)))),
+        }
+        device.check_invariants().map_err(|message| fail(step, format!("invariant violated: {}", message)))?;
+
+        if (step + 1).is_multiple_of(SWEEP_EVERY) {
+            sweep(&mut device, &oracle).map_err(|message| fail(step, message))?;
+        }
+    }
+    sweep(&mut device, &oracle).map_err(|message| fail(ops.len(), message))
+}
+
+// 연산 하나를 양쪽에 실행하고, 읽기 결과 / 방금 바꾼 LBA를 비교
+fn apply<D: BlockDevice>(device: &mut D, oracle: &mut HashMap<usize, u32>, op: &Op) -> Result<(), String> {
+    let lba = match *op {
+        Op::Write { lba, data } => {
+            device.write(lba, data)?;
+            oracle.insert(lba, data);
+            lba
+        }
+        Op::Trim { lba } => {
+            device.trim(lba)?;
+            oracle.remove(&lba);
+            lba
+        }
+        Op::Read { lba } => lba,
+    };
+    compare(device, oracle, lba)
+}
+
+fn compare<D: BlockDevice>(device: &mut D, oracle: &HashMap<usize, u32>, lba: usize) -> Result<(), String> {
+    let actual = device.read(lba)?;
+    let expected = oracle.get(&lba).copied();
+    if actual != expected {
+        return Err(format!("LBA {}: device returned {:?}, oracle expected {:?}", lba, actual, expected));
+    }
+    Ok(())
+}
+
+fn sweep<D: BlockDevice>(device: &mut D, oracle: &HashMap<usize, u32>) -> Result<(), String> {
+    (0..device.num_lbas()).try_for_each(|lba| compare(device, oracle, lba))
+}
+
+fn panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+        .unwrap_or_else(|| "(non-string panic)".to_string())
+}
+
+// 실패를 유지하는 한 연산을 덩어리째(절반 -> 1/4 -> ... -> 1개) 빼면서 연산열을 줄임
+pub fn shrink<D: BlockDevice>(make: &impl Fn() -> D, failure: Failure) -> Failure {
+    let mut best = failure;
+    // 실패가 드러난 지점 이후의 연산은 필요 없음
+    best.ops.truncate(best.step + 1);
+    if let Err(f) = check(make, &best.ops) {
+        best = f;
+    }
+
+    let mut runs = 0;
+    let mut chunk = best.ops.len() / 2;
+    while chunk >= 1 && runs < MAX_SHRINK_RUNS {
+        let mut start = 0;
+        let mut removed_any = false;
+        while start < best.ops.len() && runs < MAX_SHRINK_RUNS {
+            let mut candidate = best.ops.clone();
+            candidate.drain(start..(start + chunk).min(candidate.len()));
+            runs += 1;
+            match check(make, &candidate) {
+                Err(f) => {
+                    best = f;
+                    removed_any = true;
+                }
+                Ok(()) => start += chunk,
+            }
+        }
+        if !removed_any {
+            chunk /= 2;
+        }
+    }
+    best
+}
+
+// count개의 무작위 연산으로 차등 테스트, 실패하면 최소 재현 연산열을 돌려줌
+pub fn run<D: BlockDevice>(make: impl Fn() -> D, count: usize, seed: u64) -> Result<(), Failure> {
+    let num_lbas = make().num_lbas();
+    let ops = random_ops(num_lbas, count, seed);
+    check(&make, &ops).map_err(|failure| shrink(&make, failure))
+}
@@ -0,0 +1,430 @@
+// 선언형 실험 설정(JSON) + 배치 실행기
+// 실험 하나 = 장치 구성(geometry, FTL, GC 스케줄링) + warm-up 단계 + 측정 단계 + seed/반복 횟수
+// sweep에 적힌 파라미터 조합마다 실험을 복제해서 돌리고, 결과를 표(또는 CSV)로 정리합니다.
+use std::fs::File;
+use std::io::{BufReader, Write};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::alloc::AllocPolicy;
+use super::capacity::{OpMode, OverProvisioning};
+pub use super::capacity::op_ratio;
+use super::cell::CellMode;
+use super::device::BlockDevice;
+use super::gc::{GcPolicy, GcScheduler, VictimPolicy};
+use super::seed::*;
+use super::ssd_basic::SSD as BasicSSD;
+use super::ssd_wear_leveling::{StaticWlConfig, SSD as WearLevelingSSD};
+use super::ssd_slc_cache::{SlcCacheConfig, SlcCachePolicy, SSD as SlcCacheSSD};
+use super::timing::{LatencyStats, TimedSSD, TimingModel};
+use super::write_buffer::WriteBufferConfig;
+use super::workload::*;
+use log::info;
+
+// 블록 수 + (LBA 수 / OP 비율 / 예약 블록 수) 중 하나
+// OP(%) = (물리 페이지 - 논리 페이지) / 논리 페이지 * 100
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Geometry {
+    pub num_blocks: usize,
+    #[serde(default)]
+    pub num_lbas: Option<usize>,
+    #[serde(default)]
+    pub op_pct: Option<f64>,
+    #[serde(default)]
+    pub reserved_blocks: Option<usize>,
+    // 공장 출하 배드 블록 수 (위치는 seed로 결정, basic FTL만 지원)
+    #[serde(default)]
+    pub bad_blocks: usize,
+    // 모든 블록의 셀 모드 (블록당 페이지 수가 달라짐)
+    #[serde(default)]
+    pub cell_mode: CellMode,
+}
+
+impl Geometry {
+    pub fn physical_pages(&self) -> usize {
+        self.num_blocks * self.cell_mode.pages_per_block()
+    }
+
+    pub fn resolve_num_lbas(&self) -> Result<usize, String> {
+        let op = match (self.num_lbas, self.op_pct, self.reserved_blocks) {
+            (Some(num_lbas), None, None) => return Ok(num_lbas),
+            (None, Some(pct), None) => OverProvisioning::Percent { pct },
+            (None, None, Some(blocks)) => OverProvisioning::ReservedBlocks { blocks },
+            _ => return Err("geometry: exactly one of num_lbas / op_pct / reserved_blocks must be set".to_string()),
+        };
+        op.user_pages(self.num_blocks, self.cell_mode.pages_per_block())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FtlConfig {
+    Basic {
+        #[serde(default)]
+        victim_policy: VictimPolicy,
+        // DRAM 쓰기 버퍼 (없으면 쓰기가 바로 NAND로)
+        #[serde(default)]
+        write_buffer: Option<WriteBufferConfig>,
+        // trim된 공간을 GC가 여분 OP로 회수하는지 (기본 dynamic)
+        #[serde(default)]
+        op_mode: OpMode,
+        // 다음 Active Block으로 쓸 빈 블록을 고르는 방식 (기본 first_free)
+        #[serde(default)]
+        alloc_policy: AllocPolicy,
+    },
+    // 정적 wear leveling 조건 (gap_threshold / age_threshold / check_interval)
+    WearLeveling(StaticWlConfig),
+    // SLC 쓰기 캐시 + 고밀도 블록 (고밀도 블록의 셀 모드는 geometry.cell_mode)
+    SlcCache { policy: SlcCachePolicy },
+}
+
+impl Default for FtlConfig {
+    fn default() -> Self {
+        FtlConfig::Basic {
+            victim_policy: VictimPolicy::Greedy,
+            write_buffer: None,
+            op_mode: OpMode::Dynamic,
+            alloc_policy: AllocPolicy::FirstFree,
+        }
+    }
+}
+
+// 설정하면 시간 모델(TimedSSD) 위에서 실행하고 지연 시간 백분위수도 기록 (basic FTL)
+// SLC 캐시 FTL은 셀 모드별 지연 시간으로 자체 시계를 쓰므로 여기서는 도착 간격만 사용
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimingConfig {
+    #[serde(default)]
+    pub model: TimingModel,
+    pub gc_policy: GcPolicy,
+    pub low_watermark: usize,
+    pub high_watermark: usize,
+    // 요청 도착 간격 평균 (지수 분포)
+    pub mean_interarrival_us: f64,
+}
+
+// 단계의 길이는 요청 개수 또는 "LBA 용량의 몇 배" 중 하나로 지정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentPhase {
+    pub workload: WorkloadSpec,
+    #[serde(default)]
+    pub requests: Option<u64>,
+    #[serde(default)]
+    pub capacity_multiple: Option<f64>,
+}
+
+impl ExperimentPhase {
+    fn resolve(&self, num_lbas: usize) -> Result<Phase, String> {
+        let requests = match (self.requests, self.capacity_multiple) {
+            (Some(n), None) => n,
+            (None, Some(multiple)) => (num_lbas as f64 * multiple) as u64,
+            _ => return Err("phase: exactly one of requests / capacity_multiple must be set".to_string()),
+        };
+        Ok(Phase { workload: self.workload.clone(), requests })
+    }
+}
+
+// param: 실험 설정 안의 점(.)으로 구분된 경로 (예: "geometry.op_pct", "ftl")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sweep {
+    pub param: String,
+    pub values: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Experiment {
+    pub name: String,
+    pub geometry: Geometry,
+    #[serde(default)]
+    pub ftl: FtlConfig,
+    #[serde(default)]
+    pub timing: Option<TimingConfig>,
+    #[serde(default)]
+    pub warmup: Vec<ExperimentPhase>,
+    pub measure: Vec<ExperimentPhase>,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default = "default_repetitions")]
+    pub repetitions: u32,
+    #[serde(default)]
+    pub sweep: Vec<Sweep>,
+}
+
+fn default_repetitions() -> u32 {
+    1
+}
+
+// 실험 결과 한 줄 (sweep 조합 x 반복 1회)
+#[derive(Debug, Clone, Serialize)]
+pub struct ResultRow {
+    pub experiment: String,
+    pub params: String,
+    pub repetition: u32,
+    pub seed: u64,
+    pub num_blocks: usize,
+    pub num_lbas: usize,
+    pub op_pct: f64,
+    // 측정 단계만의 WAF (warm-up 제외)
+    pub waf: f64,
+    pub erase_min: u32,
+    pub erase_max: u32,
+    pub erase_gap: u32,
+    pub read_p99_us: Option<u64>,
+    pub write_p99_us: Option<u64>,
+    pub write_p999_us: Option<u64>,
+    pub gc_invocations: u64,
+    pub gc_migrated_pages: u64,
+    pub erase_std_dev: f64,
+}
+
+pub fn load(path: &str) -> Result<Experiment, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|e| format!("Failed to parse {}: {}", path, e))
+}
+
+// sweep 조합을 모두 펼침: (파라미터 설명, sweep이 적용된 실험) 목록
+pub fn expand(experiment: &Experiment) -> Result<Vec<(String, Experiment)>, String> {
+    let mut base = experiment.clone();
+    base.sweep.clear();
+    let base_value = serde_json::to_value(&base).map_err(|e| e.to_string())?;
+
+    let mut variants = vec![(Vec::new(), base_value)];
+    for sweep in &experiment.sweep {
+        let mut next = Vec::new();
+        for (labels, value) in &variants {
+            for v in &sweep.values {
+                let mut value = value.clone();
+                set_path(&mut value, &sweep.param, v.clone())?;
+                let mut labels: Vec<String> = labels.clone();
+                labels.push(format!("{}={}", sweep.param, describe(v)));
+                next.push((labels, value));
+            }
+        }
+        variants = next;
+    }
+
+    variants
+        .into_iter()
+        .map(|(labels, value)| {
+            let exp: Experiment = serde_json::from_value(value).map_err(|e| format!("sweep: {}", e))?;
+            Ok((labels.join(" "), exp))
+        })
+        .collect()
+}
+
+// 결과 표에 쓸 짧은 값 표현: {"type":"wear_leveling","gap_threshold":5} -> wear_leveling(gap_threshold=5)
+fn describe(value: &Value) -> String {
+    let Some(obj) = value.as_object() else {
+        return value.to_string();
+    };
+    let Some(kind) = obj.get("type").and_then(Value::as_str) else {
+        return value.to_string();
+    };
+
+    let fields: Vec<String> = obj
+        .iter()
+        .filter(|(k, _)| k.as_str() != "type")
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+    if fields.is_empty() {
+        kind.to_string()
+    } else {
+        format!("{}({})", kind, fields.join(","))
+    }
+}
+
+fn set_path(value: &mut Value, path: &str, new_value: Value) -> Result<(), String> {
+    let mut cur = value;
+    let keys: Vec<&str> = path.split('.').collect();
+    for key in &keys[..keys.len() - 1] {
+        cur = cur
+            .get_mut(*key)
+            .ok_or_else(|| format!("sweep: unknown parameter '{}'", path))?;
+    }
+
+    let obj = cur
+        .as_object_mut()
+        .ok_or_else(|| format!("sweep: '{}' is not inside an object", path))?;
+    obj.insert(keys[keys.len() - 1].to_string(), new_value);
+    Ok(())
+}
+
+// 실험 1개(모든 sweep 조합 x 반복)를 실행
+pub fn run(experiment: &Experiment) -> Result<Vec<ResultRow>, String> {
+    let mut rows = Vec::new();
+    for (params, exp) in expand(experiment)? {
+        for rep in 0..exp.repetitions {
+            let seed = exp.seed.wrapping_add(rep as u64);
+            info!("[Experiment] {} [{}] repetition {} (seed {})", exp.name, params, rep, seed);
+            let mut row = run_once(&exp, seed)?;
+            row.params = params.clone();
+            row.repetition = rep;
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+fn run_once(exp: &Experiment, seed: u64) -> Result<ResultRow, String> {
+    let num_blocks = exp.geometry.num_blocks;
+    let num_lbas = exp.geometry.resolve_num_lbas()?;
+
+    let cell_mode = exp.geometry.cell_mode;
+
+    let basic = |victim_policy: VictimPolicy, write_buffer: Option<WriteBufferConfig>, op_mode: OpMode, alloc_policy: AllocPolicy|
+                 -> Result<BasicSSD, String> {
+        let mut ssd = BasicSSD::with_seed(num_blocks, num_lbas, seed);
+        for idx in 0..num_blocks {
+            ssd.set_cell_mode(idx, cell_mode)?;
+        }
+        ssd.set_victim_policy(victim_policy);
+        ssd.set_alloc_policy(alloc_policy);
+        ssd.place_bad_blocks(exp.geometry.bad_blocks)?;
+        ssd.set_write_buffer(write_buffer)?;
+        ssd.set_op_mode(op_mode);
+        Ok(ssd)
+    };
+
+    // FTL 종류와 시간 모델 사용 여부에 따라 장치 구성 (이후로는 BlockDevice로만 다룸)
+    let mut device: Box<dyn BlockDevice> = match (&exp.ftl, &exp.timing) {
+        (FtlConfig::Basic { victim_policy, write_buffer, op_mode, alloc_policy }, None) => {
+            Box::new(basic(*victim_policy, *write_buffer, *op_mode, *alloc_policy)?)
+        }
+        (FtlConfig::WearLeveling(wl_config), None) => {
+            if exp.geometry.bad_blocks > 0 {
+                return Err("bad blocks are only supported with the basic FTL".to_string());
+            }
+            let mut ssd = WearLevelingSSD::with_seed(num_blocks, num_lbas, seed);
+            for idx in 0..num_blocks {
+                ssd.set_cell_mode(idx, cell_mode)?;
+            }
+            ssd.set_static_wl(*wl_config);
+            Box::new(ssd)
+        }
+        (FtlConfig::Basic { victim_policy, write_buffer, op_mode, alloc_policy }, Some(timing)) => {
+            let scheduler = GcScheduler::new(timing.gc_policy, timing.low_watermark, timing.high_watermark);
+            Box::new(TimedSSD::new(basic(*victim_policy, *write_buffer, *op_mode, *alloc_policy)?, timing.model, scheduler))
+        }
+        (FtlConfig::WearLeveling(_), Some(_)) => {
+            return Err("timing model is only supported with the basic FTL".to_string());
+        }
+        (FtlConfig::SlcCache { policy }, _) => {
+            if exp.geometry.bad_blocks > 0 {
+                return Err("bad blocks are only supported with the basic FTL".to_string());
+            }
+            let config = SlcCacheConfig { policy: *policy, dense_mode: cell_mode };
+            Box::new(SlcCacheSSD::new(num_blocks, num_lbas, config)?)
+        }
+    };
+
+    let warmup: Vec<Phase> = exp.warmup.iter().map(|p| p.resolve(num_lbas)).collect::<Result<_, _>>()?;
+    let measure: Vec<Phase> = exp.measure.iter().map(|p| p.resolve(num_lbas)).collect::<Result<_, _>>()?;
+
+    // 도착 시각도 seed로 결정 (워크로드와 다른 스트림)
+    let mut arrival_rng = StdRng::seed_from_u64(derive_seed(seed, STREAM_ARRIVAL));
+    let mut now_us = 0u64;
+    let mut data = 0u32;
+    let mut next_arrival = |rng: &mut StdRng| {
+        if let Some(timing) = &exp.timing {
+            let u: f64 = rng.r#gen::<f64>().max(f64::MIN_POSITIVE);
+            now_us += (-u.ln() * timing.mean_interarrival_us) as u64;
+        }
+        now_us
+    };
+
+    // 1. Warm-up
+    for request in Phased::new(&warmup, num_lbas, derive_seed(seed, STREAM_WARMUP))? {
+        data = data.wrapping_add(1);
+        device.set_arrival(next_arrival(&mut arrival_rng));
+        execute(device.as_mut(), &request, data)?;
+    }
+
+    // 2. 측정 구간: 통계를 초기화하고 이 시점부터만 집계
+    device.reset_stats();
+    let mut read_latency = LatencyStats::new();
+    let mut write_latency = LatencyStats::new();
+
+    for request in Phased::new(&measure, num_lbas, derive_seed(seed, STREAM_MEASURE))? {
+        data = data.wrapping_add(1);
+        let arrival_us = next_arrival(&mut arrival_rng);
+        device.set_arrival(arrival_us);
+        execute(device.as_mut(), &request, data)?;
+
+        if let Some(now_us) = device.clock_us() {
+            // 요청 완료 시각 기준 지연 시간 (요청 안의 마지막 페이지가 끝난 시점)
+            let latency = now_us.saturating_sub(arrival_us);
+            match request.op {
+                IoOp::Read => read_latency.record(latency),
+                IoOp::Write => write_latency.record(latency),
+                IoOp::Trim => {}
+            }
+        }
+    }
+
+    let stats = device.stats();
+    let wear = device.erase_distribution();
+    let timed = exp.timing.is_some();
+
+    Ok(ResultRow {
+        experiment: exp.name.clone(),
+        params: String::new(),
+        repetition: 0,
+        seed,
+        num_blocks,
+        num_lbas,
+        op_pct: op_ratio(exp.geometry.physical_pages(), num_lbas),
+        waf: stats.waf(),
+        erase_min: wear.min,
+        erase_max: wear.max,
+        erase_gap: wear.max - wear.min,
+        read_p99_us: timed.then(|| read_latency.percentile(99.0)),
+        write_p99_us: timed.then(|| write_latency.percentile(99.0)),
+        write_p999_us: timed.then(|| write_latency.percentile(99.9)),
+        gc_invocations: stats.gc_invocations,
+        gc_migrated_pages: stats.gc_migrated_pages,
+        erase_std_dev: wear.std_dev,
+    })
+}
+
+pub fn print_table(rows: &[ResultRow]) {
+    println!(
+        "{:<32} {:>4} {:>7} {:>7} {:>8} {:>8} {:>7} {:>7} {:>10} {:>10}",
+        "params", "rep", "blocks", "lbas", "OP(%)", "WAF", "ers-min", "ers-max", "wr-p99(us)", "wr-p999"
+    );
+    for row in rows {
+        let fmt_opt = |v: Option<u64>| v.map(|x| x.to_string()).unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:<32} {:>4} {:>7} {:>7} {:>8.2} {:>8.4} {:>7} {:>7} {:>10} {:>10}",
+            row.params, row.repetition, row.num_blocks, row.num_lbas, row.op_pct, row.waf,
+            row.erase_min, row.erase_max, fmt_opt(row.write_p99_us), fmt_opt(row.write_p999_us)
+        );
+    }
+}
+
+pub fn write_csv(rows: &[ResultRow], path: &str) -> Result<(), String> {
+    let mut file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let opt = |v: Option<u64>| v.map(|x| x.to_string()).unwrap_or_default();
+
+    // 기존 열 순서는 유지하고 새 열은 뒤에 붙임 (열 위치로 읽는 스크립트가 있음)
+    writeln!(file, "experiment,params,repetition,seed,num_blocks,num_lbas,op_pct,waf,erase_min,erase_max,erase_gap,read_p99_us,write_p99_us,write_p999_us,gc_invocations,gc_migrated_pages,erase_std_dev")
+        .map_err(|e| e.to_string())?;
+    for row in rows {
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{:.4},{:.6},{},{},{},{},{},{},{},{},{:.4}",
+            csv_quote(&row.experiment), csv_quote(&row.params), row.repetition, row.seed, row.num_blocks, row.num_lbas,
+            row.op_pct, row.waf, row.erase_min, row.erase_max, row.erase_gap,
+            opt(row.read_p99_us), opt(row.write_p99_us), opt(row.write_p999_us),
+            row.gc_invocations, row.gc_migrated_pages, row.erase_std_dev
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// 쉼표나 따옴표가 들어갈 수 있는 문자열 열 (따옴표로 감싸고 안의 따옴표는 두 번)
+fn csv_quote(field: &str) -> String {
+    format!("\"{}\"", field.replace('"', "\"\""))
+}
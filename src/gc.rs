@@ -0,0 +1,240 @@
+// 점진적(incremental) GC를 위한 자료구조와 스케줄링 정책
+// GC 한 번(victim 하나 정리)을 "페이지 1개 복사" 또는 "블록 1개 erase" 단위의 step으로 쪼개서,
+// 호스트 I/O가 들어오면 현재 step만 끝내고 양보할 수 있게 합니다.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::block::{Block, BlockState};
+use super::page::PageState;
+
+// 진행 중인 GC 작업: 어떤 victim을 어디까지 대피시켰는지 기억합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GcJob {
+    pub victim_idx: usize,
+    pub next_page: usize,
+}
+
+// gc_step() 한 번이 실제로 한 일
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GcStep {
+    // 유효 페이지 1개를 victim에서 Active Block으로 복사함 (NAND read + program)
+    Copied { victim_idx: usize, page_offset: usize },
+    // victim의 유효 페이지를 모두 옮기고 erase까지 끝냄 -> 빈 블록 1개 확보
+    Erased { victim_idx: usize },
+}
+
+// victim 선정 방식
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VictimPolicy {
+    // 유효 페이지가 가장 적은 블록 (VictimIndex에서 무효 페이지가 많은 순으로 찾으므로 블록을 스캔하지 않음)
+    #[default]
+    Greedy,
+    // 후보 블록 d개를 무작위로 (중복 없이) 뽑아 그중 유효 페이지가 가장 적은 블록 (SSD seed 사용)
+    DChoices { d: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GcPolicy {
+    // 기존 동작: 빈 블록이 바닥나면 호스트 쓰기 도중에 victim 하나를 통째로 정리
+    StopTheWorld,
+    // 유휴 시간에만, 빈 블록이 low watermark 이하로 떨어졌을 때만 GC 진행
+    Lazy,
+    // 빈 블록이 high watermark 미만이면 유휴 시간마다 GC 진행 (호스트 요청이 오면 현재 step 후 양보)
+    GreedyPreemptive,
+    // GreedyPreemptive와 같지만 step마다 토큰 1개를 소모 (초당 rate개 충전, 최대 burst개 저장)
+    TokenBucket { rate_per_sec: f64, burst: u32 },
+}
+
+pub struct GcScheduler {
+    pub policy: GcPolicy,
+    pub low_watermark: usize,
+    pub high_watermark: usize,
+    tokens: f64,
+    last_refill_us: u64,
+}
+
+impl GcScheduler {
+    pub fn new(policy: GcPolicy, low_watermark: usize, high_watermark: usize) -> Self {
+        let tokens = match policy {
+            GcPolicy::TokenBucket { burst, .. } => burst as f64,
+            _ => 0.0,
+        };
+
+        GcScheduler {
+            policy,
+            low_watermark,
+            high_watermark,
+            tokens,
+            last_refill_us: 0,
+        }
+    }
+
+    // 유휴 시간에 GC step을 하나 더 진행해야 하는지 판단 (free_blocks: 현재 빈 블록 수)
+    pub fn wants_background(&self, free_blocks: usize, job_in_progress: bool) -> bool {
+        match self.policy {
+            GcPolicy::StopTheWorld => false,
+            GcPolicy::Lazy => job_in_progress || free_blocks <= self.low_watermark,
+            GcPolicy::GreedyPreemptive | GcPolicy::TokenBucket { .. } => {
+                job_in_progress || free_blocks < self.high_watermark
+            }
+        }
+    }
+
+    // 다음 step을 시작할 수 있는 가장 이른 시각 (토큰 버킷이면 토큰이 찰 때까지 기다려야 할 수 있음)
+    pub fn next_step_time(&mut self, now_us: u64) -> u64 {
+        let GcPolicy::TokenBucket { rate_per_sec, burst } = self.policy else {
+            return now_us;
+        };
+
+        self.refill(now_us, rate_per_sec, burst);
+        if self.tokens >= 1.0 || rate_per_sec <= 0.0 {
+            return now_us;
+        }
+
+        let wait_us = ((1.0 - self.tokens) / rate_per_sec * 1_000_000.0).ceil() as u64;
+        now_us + wait_us
+    }
+
+    // step 하나를 시작하면서 토큰 1개 소모 (토큰 버킷이 아니면 아무 일도 안 함)
+    pub fn consume(&mut self, now_us: u64) {
+        if let GcPolicy::TokenBucket { rate_per_sec, burst } = self.policy {
+            self.refill(now_us, rate_per_sec, burst);
+            self.tokens = (self.tokens - 1.0).max(0.0);
+        }
+    }
+
+    fn refill(&mut self, now_us: u64, rate_per_sec: f64, burst: u32) {
+        if now_us > self.last_refill_us {
+            let elapsed_sec = (now_us - self.last_refill_us) as f64 / 1_000_000.0;
+            self.tokens = (self.tokens + elapsed_sec * rate_per_sec).min(burst as f64);
+            self.last_refill_us = now_us;
+        }
+    }
+}
+
+// Greedy victim 선정용 색인: GC 후보 블록(Active / 빈 / 배드 블록 제외)을 무효 페이지 수별 버킷에 번호 순으로 보관
+// 페이지가 무효가 될 때마다 갱신하므로 victim을 고를 때 블록을 스캔하지 않음 (버킷 수 = 블록당 페이지 수 + 1)
+// 후보는 끝까지 프로그램된 블록이라, 셀 모드가 같으면 무효 페이지가 많은 순서 = 유효 페이지가 적은 순서
+// 덜 찬 후보(wear leveling FTL에서 cold 데이터를 받은 블록)는 남은 빈 페이지도 erase로 돌려받으므로 무효 페이지로 셈
+#[derive(Debug, Clone, Default)]
+pub struct VictimIndex {
+    buckets: Vec<BTreeSet<usize>>,
+    // 블록별 무효 페이지 수 (후보가 아니면 None)
+    invalid: Vec<Option<usize>>,
+    // 후보 전체 (번호 순, DChoices가 무작위로 뽑을 때 사용)
+    members: BTreeSet<usize>,
+}
+
+impl VictimIndex {
+    pub fn new(num_blocks: usize) -> Self {
+        VictimIndex { invalid: vec![None; num_blocks], ..Default::default() }
+    }
+
+    // 블록 상태에서 다시 만듦 (스냅샷 복원)
+    pub fn from_blocks(blocks: &[Block], active_block_idx: usize) -> Self {
+        Self::from_blocks_where(blocks, |idx, block| is_candidate(block, idx, active_block_idx))
+    }
+
+    // 후보를 FTL이 정함 (SLC 캐시 FTL: 고밀도 블록만)
+    pub fn from_blocks_where(blocks: &[Block], is_candidate: impl Fn(usize, &Block) -> bool) -> Self {
+        let mut index = VictimIndex::new(blocks.len());
+        for (idx, block) in blocks.iter().enumerate() {
+            if is_candidate(idx, block) {
+                index.insert(idx, count_invalid(block));
+            }
+        }
+        index
+    }
+
+    // Active Block에서 물러난 블록을 후보로 넣음
+    pub fn insert(&mut self, idx: usize, invalid_pages: usize) {
+        self.remove(idx);
+        if invalid_pages >= self.buckets.len() {
+            self.buckets.resize(invalid_pages + 1, BTreeSet::new());
+        }
+        self.buckets[invalid_pages].insert(idx);
+        self.invalid[idx] = Some(invalid_pages);
+        self.members.insert(idx);
+    }
+
+    // erase된 블록을 뺌
+    pub fn remove(&mut self, idx: usize) -> bool {
+        let Some(invalid_pages) = self.invalid[idx].take() else { return false };
+        self.buckets[invalid_pages].remove(&idx);
+        self.members.remove(&idx);
+        true
+    }
+
+    // 페이지 하나가 무효가 됨 (후보가 아닌 블록 - Active Block - 은 후보가 될 때 다시 셈)
+    pub fn invalidate(&mut self, idx: usize) {
+        if let Some(invalid_pages) = self.invalid[idx] {
+            self.insert(idx, invalid_pages + 1);
+        }
+    }
+
+    pub fn contains(&self, idx: usize) -> bool {
+        self.invalid[idx].is_some()
+    }
+
+    pub fn invalid_pages(&self, idx: usize) -> Option<usize> {
+        self.invalid[idx]
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    // 후보 전체 (번호 순)
+    pub fn candidates(&self) -> impl Iterator<Item = usize> + '_ {
+        self.members.iter().copied()
+    }
+
+    // 무효 페이지가 많은 순, 같으면 번호 순 (Greedy가 보는 순서)
+    pub fn by_invalid_desc(&self) -> impl Iterator<Item = usize> + '_ {
+        self.buckets.iter().rev().flat_map(|bucket| bucket.iter().copied())
+    }
+
+    // 무효 페이지가 하나라도 있는 후보가 있는지
+    pub fn has_reclaimable(&self) -> bool {
+        self.buckets.iter().skip(1).any(|bucket| !bucket.is_empty())
+    }
+
+    // 색인이 블록 상태와 맞는지 (후보 집합과 블록별 무효 페이지 수)
+    pub fn check(&self, blocks: &[Block], active_block_idx: usize) -> Result<(), String> {
+        self.check_where(blocks, |idx, block| is_candidate(block, idx, active_block_idx))
+    }
+
+    pub fn check_where(&self, blocks: &[Block], is_candidate: impl Fn(usize, &Block) -> bool) -> Result<(), String> {
+        for (idx, block) in blocks.iter().enumerate() {
+            let expected = is_candidate(idx, block).then(|| count_invalid(block));
+            if self.invalid[idx] != expected {
+                return Err(format!("Victim index has Block {} at {:?} invalid pages, expected {:?}", idx, self.invalid[idx], expected));
+            }
+            if let Some(invalid_pages) = expected
+                && !self.buckets.get(invalid_pages).is_some_and(|bucket| bucket.contains(&idx)) {
+                return Err(format!("Block {} is missing from victim bucket {}", idx, invalid_pages));
+            }
+        }
+        let bucketed: usize = self.buckets.iter().map(BTreeSet::len).sum();
+        if bucketed != self.members.len() {
+            return Err(format!("Victim index buckets hold {} blocks, but it has {} candidates", bucketed, self.members.len()));
+        }
+        Ok(())
+    }
+}
+
+fn is_candidate(block: &Block, idx: usize, active_block_idx: usize) -> bool {
+    idx != active_block_idx && block.state != BlockState::Free && !block.is_bad
+}
+
+fn count_invalid(block: &Block) -> usize {
+    block.pages.iter().filter(|p| p.state != PageState::Valid).count()
+}
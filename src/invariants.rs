@@ -0,0 +1,170 @@
+// FTL 내부 일관성 검사 (두 FTL 공용)
+// 매핑 테이블 <-> 페이지 상태 <-> 블록 상태 <-> 통계 카운터가 서로 맞는지 확인하고, 첫 번째 위반을 에러로 돌려줍니다.
+// 호스트 연산/GC step 사이 어느 시점에서 불러도 성립하는 조건만 검사합니다.
+use super::block::{Block, BlockState};
+use super::mapping::MappingTable;
+use super::page::PageState;
+use super::stats::{Stats, FREE_BLOCK_SAMPLES};
+
+pub fn check(blocks: &[Block], mapping_table: &MappingTable, active_block_idx: usize, stats: &Stats) -> Result<(), String> {
+    check_mapping(blocks, mapping_table)?;
+    check_blocks(blocks, active_block_idx)?;
+    check_counters(blocks, stats)
+}
+
+// 모든 매핑은 Valid 페이지를 가리키고, 모든 Valid 페이지는 정확히 한 LBA가 가리켜야 함
+fn check_mapping(blocks: &[Block], mapping_table: &MappingTable) -> Result<(), String> {
+    // 블록마다 페이지 수가 다를 수 있으므로(셀 모드) 블록별 시작 위치를 따로 계산
+    let mut base = Vec::with_capacity(blocks.len());
+    let mut total_pages = 0;
+    for block in blocks {
+        base.push(total_pages);
+        total_pages += block.pages.len();
+    }
+    let mut owner: Vec<Option<usize>> = vec![None; total_pages];
+
+    for (lba, entry) in mapping_table.entries().iter().enumerate() {
+        let Some(pba) = entry else { continue };
+        let block_idx = pba.block_id as usize;
+        if block_idx >= blocks.len() || pba.page_offset >= blocks[block_idx].pages.len() {
+            return Err(format!("LBA {} maps to Block {} Page {}, which does not exist", lba, pba.block_id, pba.page_offset));
+        }
+        let block = &blocks[block_idx];
+        if block.is_bad {
+            return Err(format!("LBA {} maps to bad Block {}", lba, block_idx));
+        }
+        let state = block.pages[pba.page_offset].state;
+        if state != PageState::Valid {
+            return Err(format!("LBA {} maps to Block {} Page {}, which is {:?}", lba, block_idx, pba.page_offset, state));
+        }
+        let slot = &mut owner[base[block_idx] + pba.page_offset];
+        if let Some(other) = slot {
+            return Err(format!("LBA {} and LBA {} both map to Block {} Page {}", other, lba, block_idx, pba.page_offset));
+        }
+        *slot = Some(lba);
+    }
+
+    for (block_idx, block) in blocks.iter().enumerate() {
+        for (page_offset, page) in block.pages.iter().enumerate() {
+            if page.state == PageState::Valid && owner[base[block_idx] + page_offset].is_none() {
+                return Err(format!("Block {} Page {} is Valid but no LBA maps to it", block_idx, page_offset));
+            }
+        }
+    }
+    mapping_table.check_reverse()
+}
+
+// 페이지는 앞에서부터 순서대로(셀 모드의 프로그램 순서대로) 프로그램되므로, 프로그램된 페이지 수로 블록 상태가 정해짐
+// (0개 -> Free, 전부 -> Full, 그 사이 -> Active)
+fn check_blocks(blocks: &[Block], active_block_idx: usize) -> Result<(), String> {
+    if active_block_idx >= blocks.len() {
+        return Err(format!("Active block {} does not exist ({} blocks)", active_block_idx, blocks.len()));
+    }
+    if blocks[active_block_idx].is_bad {
+        return Err(format!("Active block {} is a bad block", active_block_idx));
+    }
+
+    for (idx, block) in blocks.iter().enumerate() {
+        if block.id as usize != idx {
+            return Err(format!("Block at index {} has id {}", idx, block.id));
+        }
+        if block.pages.len() != block.cell_mode.pages_per_block() {
+            return Err(format!("Block {} has {} pages but {:?} blocks have {}",
+                idx, block.pages.len(), block.cell_mode, block.cell_mode.pages_per_block()));
+        }
+        if block.is_bad {
+            if block.pages.iter().any(|p| p.state != PageState::Free) {
+                return Err(format!("Bad block {} has programmed pages", idx));
+            }
+            continue;
+        }
+
+        let programmed = block.pages.iter().take_while(|p| p.state != PageState::Free).count();
+        if let Some(offset) = block.pages[programmed..].iter().position(|p| p.state != PageState::Free) {
+            return Err(format!("Block {} Page {} is programmed after free Page {}", idx, programmed + offset, programmed));
+        }
+
+        let expected = match programmed {
+            0 => BlockState::Free,
+            n if n == block.pages.len() => BlockState::Full,
+            _ => BlockState::Active,
+        };
+        if block.state != expected {
+            return Err(format!("Block {} is {:?} but has {} programmed pages (expected {:?})", idx, block.state, programmed, expected));
+        }
+    }
+
+    // 빈 블록 수는 블록 상태(Free)와 페이지 상태(전부 Free) 양쪽에서 센 값이 같아야 함
+    let free_by_state = blocks.iter().filter(|b| !b.is_bad && b.state == BlockState::Free).count();
+    let free_by_pages = blocks.iter()
+        .filter(|b| !b.is_bad && b.pages.iter().all(|p| p.state == PageState::Free))
+        .count();
+    if free_by_state != free_by_pages {
+        return Err(format!("Free block count mismatch: {} by block state, {} by page state", free_by_state, free_by_pages));
+    }
+    Ok(())
+}
+
+// reset_stats() 뒤에도 성립하는 관계만 검사 (블록의 erase 횟수는 리셋되지 않음)
+fn check_counters(blocks: &[Block], stats: &Stats) -> Result<(), String> {
+    // NAND 프로그램은 호스트 쓰기, GC 이동, 정적 WL 이동, SLC 접기, 패딩에서만 생김
+    let internal_writes = stats.gc_migrated_pages + stats.wl_migrated_pages + stats.folded_pages;
+    if stats.nand_writes > stats.host_writes + internal_writes + stats.padding_pages {
+        return Err(format!("nand_writes {} exceeds host_writes {} + migrated pages {} (gc {} / wl {} / folded {}) + padding_pages {}",
+            stats.nand_writes, stats.host_writes, internal_writes, stats.gc_migrated_pages, stats.wl_migrated_pages,
+            stats.folded_pages, stats.padding_pages));
+    }
+    if internal_writes + stats.padding_pages > stats.nand_writes {
+        return Err(format!("migrated pages {} (gc {} / wl {} / folded {}) + padding_pages {} exceeds nand_writes {}",
+            internal_writes, stats.gc_migrated_pages, stats.wl_migrated_pages, stats.folded_pages, stats.padding_pages, stats.nand_writes));
+    }
+    // GC 이동 / WL 이동 / SLC 접기 / RMW 모두 NAND 읽기를 한 번씩 함
+    if internal_writes + stats.rmw_reads > stats.nand_reads {
+        return Err(format!("migrated pages {} (gc {} / wl {} / folded {}) + rmw_reads {} exceeds nand_reads {}",
+            internal_writes, stats.gc_migrated_pages, stats.wl_migrated_pages, stats.folded_pages, stats.rmw_reads, stats.nand_reads));
+    }
+    // 버퍼가 흡수한 쓰기는 NAND에 가지 않은 호스트 쓰기
+    if stats.buffer_absorbed + stats.buffer_lost > stats.host_writes {
+        return Err(format!("buffer_absorbed {} + buffer_lost {} exceeds host_writes {}",
+            stats.buffer_absorbed, stats.buffer_lost, stats.host_writes));
+    }
+    if stats.slc_writes + stats.slc_overflows > stats.host_writes {
+        return Err(format!("slc_writes {} + slc_overflows {} exceeds host_writes {}",
+            stats.slc_writes, stats.slc_overflows, stats.host_writes));
+    }
+
+    // 요청마다 페이지가 1개 이상 (빈 범위 요청은 기록하지 않음)
+    if stats.read_requests > stats.host_reads || stats.write_requests > stats.host_writes || stats.trim_requests > stats.host_trims {
+        return Err(format!("Requests (reads {} / writes {} / trims {}) exceed pages (reads {} / writes {} / trims {})",
+            stats.read_requests, stats.write_requests, stats.trim_requests, stats.host_reads, stats.host_writes, stats.host_trims));
+    }
+    let sized: u64 = stats.request_size_histogram.iter().sum();
+    if sized != stats.read_requests + stats.write_requests + stats.trim_requests {
+        return Err(format!("Request size histogram has {} requests but {} were counted",
+            sized, stats.read_requests + stats.write_requests + stats.trim_requests));
+    }
+
+    let lba_writes: u64 = stats.lba_writes.iter().sum();
+    if lba_writes != stats.host_writes {
+        return Err(format!("Per-LBA writes sum to {} but host_writes is {}", lba_writes, stats.host_writes));
+    }
+    let histogram: u64 = stats.victim_valid_histogram.iter().sum();
+    let free_histogram: u64 = stats.free_block_histogram.iter().sum();
+    if histogram != stats.gc_invocations || free_histogram != stats.gc_invocations {
+        return Err(format!("gc_invocations {} but victim histogram has {} and free block histogram {}",
+            stats.gc_invocations, histogram, free_histogram));
+    }
+    // 시계열은 GC 시작 free_block_sample_stride번마다 하나
+    let stride = stats.free_block_sample_stride.max(1);
+    if stats.free_block_samples.len() as u64 != stats.gc_invocations.div_ceil(stride)
+        || stats.free_block_samples.len() > FREE_BLOCK_SAMPLES {
+        return Err(format!("{} GC starts at stride {} but {} free block samples",
+            stats.gc_invocations, stride, stats.free_block_samples.len()));
+    }
+
+    let erase_counts: u64 = blocks.iter().map(|b| b.erase_count as u64).sum();
+    if stats.nand_erases > erase_counts {
+        return Err(format!("nand_erases {} exceeds the sum of block erase counts {}", stats.nand_erases, erase_counts));
+    }
+    Ok(())
+}
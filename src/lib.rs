@@ -1,5 +1,26 @@
 pub mod block;
 pub mod page;
+pub mod cell;
 pub mod mapping;
+pub mod gc;
+pub mod seed;
+pub mod stats;
+pub mod capacity;
+pub mod alloc;
+pub mod device;
+pub mod invariants;
+pub mod write_buffer;
+pub mod difftest;
+pub mod metrics;
+pub mod visualize;
+pub mod timing;
+pub mod trace;
+pub mod workload;
+pub mod experiment;
+pub mod shell;
+pub mod byte_io;
+pub mod sector;
+pub mod nbd;
 pub mod ssd_basic;
-pub mod ssd_wear_leveling;
\ No newline at end of file
+pub mod ssd_wear_leveling;
+pub mod ssd_slc_cache;
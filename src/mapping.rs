@@ -1,17 +1,46 @@
 use std::fmt;
+use serde::{Deserialize, Serialize};
 
 // 물리 주소를 표현하는 구조체 (어느 블록, 어느 페이지인지)
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct PhysicalAddress {
     pub block_id: u32,
     pub page_offset: usize,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "MappingEntries")]
 pub struct MappingTable {
     // 인덱스(Index)가 곧 LBA(Logical Block Address)입니다.
     // 값(Value)은 해당 LBA가 저장된 물리 주소(PBA)입니다.
     // Option::None이면 "아직 데이터가 안 쓰인 주소(Unmapped)"라는 뜻입니다.
     entries: Vec<Option<PhysicalAddress>>,
+    // 역방향 매핑: [블록][페이지] -> LBA (GC가 옮길 페이지의 LBA를 테이블 전체 스캔 없이 찾음)
+    // 스냅샷에는 저장하지 않고 entries에서 다시 만듦
+    #[serde(skip)]
+    reverse: Vec<Vec<Option<u32>>>,
+    // 매핑된 LBA 수 (스냅샷에는 저장하지 않음)
+    #[serde(skip)]
+    mapped: usize,
+}
+
+// 스냅샷에 저장되는 부분
+#[derive(Deserialize)]
+struct MappingEntries {
+    entries: Vec<Option<PhysicalAddress>>,
+}
+
+impl From<MappingEntries> for MappingTable {
+    fn from(saved: MappingEntries) -> Self {
+        let mut table = MappingTable { entries: saved.entries, reverse: Vec::new(), mapped: 0 };
+        for lba in 0..table.entries.len() {
+            if let Some(pba) = table.entries[lba] {
+                table.set_reverse(pba, Some(lba));
+                table.mapped += 1;
+            }
+        }
+        table
+    }
 }
 
 impl MappingTable {
@@ -19,6 +48,8 @@ impl MappingTable {
     pub fn new(total_lbas: usize) -> Self {
         MappingTable {
             entries: vec![None; total_lbas],
+            reverse: Vec::new(),
+            mapped: 0,
         }
     }
 
@@ -40,6 +71,11 @@ impl MappingTable {
 
         let old_pba = self.entries[lba]; // 기존에 가리키던 주소 (없으면 None)
         self.entries[lba] = Some(new_pba); // 새 주소로 갱신
+        match old_pba {
+            Some(old) => self.set_reverse(old, None),
+            None => self.mapped += 1,
+        }
+        self.set_reverse(new_pba, Some(lba));
         
         old_pba // 옛날 주소 반환 (GC 처리를 위해 필수)
     }
@@ -52,12 +88,77 @@ impl MappingTable {
         
         let old_pba = self.entries[lba];
         self.entries[lba] = None;
+        if let Some(old) = old_pba {
+            self.set_reverse(old, None);
+            self.mapped -= 1;
+        }
         old_pba
     }
 
     pub fn entries(&self) -> &Vec<Option<PhysicalAddress>> {
         &self.entries
     }
+
+    // 매핑된 LBA 수 (테이블을 스캔하지 않음)
+    pub fn mapped_count(&self) -> usize {
+        self.mapped
+    }
+
+    // 역방향 조회: 이 물리 페이지를 가리키는 LBA (없으면 None)
+    pub fn lba_at(&self, pba: PhysicalAddress) -> Option<usize> {
+        self.reverse.get(pba.block_id as usize)?.get(pba.page_offset).copied().flatten().map(|lba| lba as usize)
+    }
+
+    // 역방향 색인이 entries와 정확히 같은 매핑을 담고 있는지
+    pub fn check_reverse(&self) -> Result<(), String> {
+        let mut reverse_mapped = 0;
+        for (block, pages) in self.reverse.iter().enumerate() {
+            for (page_offset, lba) in pages.iter().enumerate() {
+                let Some(lba) = lba.map(|lba| lba as usize) else { continue };
+                let pba = PhysicalAddress { block_id: block as u32, page_offset };
+                if self.entries.get(lba) != Some(&Some(pba)) {
+                    return Err(format!("Reverse map says Block {} Page {} holds LBA {}, but the LBA maps elsewhere", block, page_offset, lba));
+                }
+                reverse_mapped += 1;
+            }
+        }
+        let mapped = self.entries.iter().filter(|e| e.is_some()).count();
+        if reverse_mapped != mapped {
+            return Err(format!("Reverse map has {} entries, but {} LBAs are mapped", reverse_mapped, mapped));
+        }
+        if self.mapped != mapped {
+            return Err(format!("Mapping table counts {} mapped LBAs, but {} are mapped", self.mapped, mapped));
+        }
+        Ok(())
+    }
+
+    // 블록/페이지 목록은 처음 쓰일 때 늘림 (블록마다 페이지 수가 다를 수 있음)
+    fn set_reverse(&mut self, pba: PhysicalAddress, lba: Option<usize>) {
+        let block = pba.block_id as usize;
+        if block >= self.reverse.len() {
+            self.reverse.resize(block + 1, Vec::new());
+        }
+        let pages = &mut self.reverse[block];
+        if pba.page_offset >= pages.len() {
+            pages.resize(pba.page_offset + 1, None);
+        }
+        pages[pba.page_offset] = lba.map(|lba| lba as u32);
+    }
+
+    // 5. 용량 변경: 늘리면 새 LBA는 Unmapped, 줄이면 잘려나가는 LBA는 미리 unmap해 두어야 함
+    pub fn resize(&mut self, total_lbas: usize) {
+        debug_assert!(self.entries.iter().skip(total_lbas).all(Option::is_none));
+        self.entries.resize(total_lbas, None);
+    }
+
+    // 6. 범위 검사: [start_lba, start_lba + len)이 모두 테이블 안에 있어야 함 (벗어난 첫 LBA를 에러로)
+    pub fn check_range(&self, start_lba: usize, len: usize) -> Result<(), String> {
+        let total_lbas = self.entries.len();
+        if start_lba >= total_lbas || len > total_lbas - start_lba {
+            return Err(format!("LBA {} is out of range!", start_lba.max(total_lbas)));
+        }
+        Ok(())
+    }
 }
 
 // PhysicalAddress는 간단하므로 기존 derive를 유지해도 되지만, 
@@ -76,7 +177,6 @@ impl fmt::Debug for MappingTable {
 
         // 2. 매핑된 항목만 골라서 출력 (Sparse View)
         // 만약 매핑된 게 너무 많으면 앞부분 50개만 보여주는 식으로 제한을 둘 수도 있습니다.
-        let mut count = 0;
         for (lba, entry) in self.entries.iter().enumerate() {
             if let Some(pba) = entry {
                 // 보기 좋게 정렬: LBA는 5자리 확보, 화살표, PBA 정보
@@ -85,13 +185,7 @@ impl fmt::Debug for MappingTable {
                     "  LBA [{:<5}] -> Block {:<4} | Page {:<3}", 
                     lba, pba.block_id, pba.page_offset
                 )?;
-                
-                count += 1;
-                // (선택) 로그가 너무 길어지는 것을 방지하려면 주석 해제
-                // if count >= 20 {
-                //     writeln!(f, "  ... (remaining entries hidden) ...")?;
-                //     break;
-                // }
+
             }
         }
 
@@ -0,0 +1,191 @@
+// 시계열 메트릭: 호스트 쓰기 N번마다 WAF, 빈 블록 수, GC 횟수, 마모 편차를 샘플링해서 sink로 내보냄
+// SSD에 MetricsRecorder를 붙이지 않으면(None) 쓰기마다 분기 하나만 추가되므로 오버헤드가 거의 없습니다.
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use log::warn;
+use serde::Serialize;
+
+use super::block::Block;
+use super::stats::{EraseDistribution, Stats};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsSample {
+    pub host_writes: u64,
+    pub nand_writes: u64,
+    // 직전 샘플 이후 구간의 WAF
+    pub instant_waf: f64,
+    pub cumulative_waf: f64,
+    pub free_blocks: usize,
+    pub gc_count: u64,
+    pub wear_gap: u32,
+    pub erase_variance: f64,
+}
+
+// 샘플을 받아 어딘가에 기록하는 출력 대상
+pub trait MetricsSink {
+    fn record(&mut self, sample: &MetricsSample) -> Result<(), String>;
+
+    fn flush(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+const CSV_HEADER: &str = "host_writes,nand_writes,instant_waf,cumulative_waf,free_blocks,gc_count,wear_gap,erase_variance";
+
+pub struct CsvSink<W: Write> {
+    out: W,
+    header_written: bool,
+}
+
+impl<W: Write> CsvSink<W> {
+    pub fn new(out: W) -> Self {
+        CsvSink { out, header_written: false }
+    }
+}
+
+impl CsvSink<BufWriter<File>> {
+    pub fn create(path: &str) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+        Ok(CsvSink::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write> MetricsSink for CsvSink<W> {
+    fn record(&mut self, s: &MetricsSample) -> Result<(), String> {
+        if !self.header_written {
+            writeln!(self.out, "{}", CSV_HEADER).map_err(|e| e.to_string())?;
+            self.header_written = true;
+        }
+        writeln!(
+            self.out,
+            "{},{},{:.6},{:.6},{},{},{},{:.6}",
+            s.host_writes, s.nand_writes, s.instant_waf, s.cumulative_waf,
+            s.free_blocks, s.gc_count, s.wear_gap, s.erase_variance
+        )
+        .map_err(|e| e.to_string())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        self.out.flush().map_err(|e| e.to_string())
+    }
+}
+
+// JSON Lines: 샘플 하나당 JSON 객체 한 줄
+pub struct JsonlSink<W: Write> {
+    out: W,
+}
+
+impl<W: Write> JsonlSink<W> {
+    pub fn new(out: W) -> Self {
+        JsonlSink { out }
+    }
+}
+
+impl JsonlSink<BufWriter<File>> {
+    pub fn create(path: &str) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+        Ok(JsonlSink::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write> MetricsSink for JsonlSink<W> {
+    fn record(&mut self, sample: &MetricsSample) -> Result<(), String> {
+        let line = serde_json::to_string(sample).map_err(|e| e.to_string())?;
+        writeln!(self.out, "{}", line).map_err(|e| e.to_string())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        self.out.flush().map_err(|e| e.to_string())
+    }
+}
+
+// SSD에 붙여서 쓰는 샘플러: interval번의 호스트 쓰기마다 sink에 샘플 1개 기록
+// 범위 쓰기는 호스트 쓰기를 한 번에 여러 개 올리므로 배수에 딱 맞는지가 아니라 다음 기준점을 넘었는지로 판단
+// sink 에러는 호스트 쓰기를 실패시키지 않고 보관했다가 flush(detach) 때 돌려줌
+pub struct MetricsRecorder {
+    sink: Box<dyn MetricsSink>,
+    interval: u64,
+    next_due: u64,
+    last_host_writes: u64,
+    last_nand_writes: u64,
+    error: Option<String>,
+    dropped: u64,
+}
+
+impl MetricsRecorder {
+    pub fn new(sink: Box<dyn MetricsSink>, interval: u64) -> Self {
+        let interval = interval.max(1);
+        MetricsRecorder {
+            sink,
+            interval,
+            next_due: interval,
+            last_host_writes: 0,
+            last_nand_writes: 0,
+            error: None,
+            dropped: 0,
+        }
+    }
+
+    // 이미 쓰기가 진행된 SSD에 붙일 때: 지금 위치 다음 배수부터 샘플링
+    pub fn start_at(&mut self, host_writes: u64) {
+        self.next_due = (host_writes / self.interval + 1) * self.interval;
+    }
+
+    pub fn is_due(&self, host_writes: u64) -> bool {
+        host_writes >= self.next_due
+    }
+
+    pub fn sample(&mut self, stats: &Stats, blocks: &[Block], free_blocks: usize) {
+        let host_delta = stats.host_writes.saturating_sub(self.last_host_writes);
+        let nand_delta = stats.nand_writes.saturating_sub(self.last_nand_writes);
+        let erase = EraseDistribution::from_blocks(blocks);
+
+        let sample = MetricsSample {
+            host_writes: stats.host_writes,
+            nand_writes: stats.nand_writes,
+            instant_waf: if host_delta == 0 { 0.0 } else { nand_delta as f64 / host_delta as f64 },
+            cumulative_waf: stats.waf(),
+            free_blocks,
+            gc_count: stats.gc_invocations,
+            wear_gap: erase.max - erase.min,
+            erase_variance: erase.std_dev * erase.std_dev,
+        };
+
+        self.last_host_writes = stats.host_writes;
+        self.last_nand_writes = stats.nand_writes;
+        // 요청 하나가 구간을 여러 개 건너뛰었어도 샘플은 1개, 기준점은 지금 위치 다음 배수로
+        self.start_at(stats.host_writes);
+        if let Err(e) = self.sink.record(&sample) {
+            warn!("[Metrics] Dropped sample at {} host writes: {}", stats.host_writes, e);
+            self.dropped += 1;
+            self.error.get_or_insert(e);
+        }
+    }
+
+    // 처음 난 sink 에러 (없으면 None)
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    // sink 에러로 기록하지 못한 샘플 수
+    pub fn dropped_samples(&self) -> u64 {
+        self.dropped
+    }
+
+    // SSD 통계가 리셋될 때 구간 WAF 기준점도 처음부터 다시 잡음
+    pub fn reset_baseline(&mut self) {
+        self.last_host_writes = 0;
+        self.last_nand_writes = 0;
+        self.next_due = self.interval;
+    }
+
+    // sink를 flush하고, 그동안 보관한 에러가 있으면 함께 보고
+    pub fn flush(&mut self) -> Result<(), String> {
+        self.sink.flush()?;
+        match &self.error {
+            Some(e) => Err(format!("Metrics sink dropped {} samples: {}", self.dropped, e)),
+            None => Ok(()),
+        }
+    }
+}
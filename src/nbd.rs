@@ -0,0 +1,447 @@
+// NBD(Network Block Device) 서버: 시뮬레이션 SSD를 진짜 블록 장치처럼 노출
+// 리눅스에서 nbd-client로 붙이면 mkfs, 파일시스템 벤치마크가 만드는 실제 I/O 패턴을 FTL에 흘려보낼 수 있습니다.
+// 프로토콜은 fixed newstyle 핸드셰이크 + simple reply만 지원합니다. (structured reply, 다중 연결 없음)
+// 바이트 <-> 페이지 변환(부분 쓰기의 read-modify-write 포함)은 SsdFile이 담당합니다.
+use std::io::{self, Read, Write};
+use std::net::TcpListener;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+
+use log::{debug, info, warn};
+
+use super::byte_io::SsdFile;
+use super::device::BlockDevice;
+
+const NBDMAGIC: u64 = 0x4e42_444d_4147_4943;
+const IHAVEOPT: u64 = 0x4948_4156_454f_5054;
+const REPLY_MAGIC_OPT: u64 = 0x0003_e889_0455_65a9;
+const REQUEST_MAGIC: u32 = 0x2560_9513;
+const SIMPLE_REPLY_MAGIC: u32 = 0x6744_6698;
+
+// 핸드셰이크 플래그
+const FLAG_FIXED_NEWSTYLE: u16 = 1 << 0;
+const FLAG_NO_ZEROES: u16 = 1 << 1;
+const CLIENT_FLAG_FIXED_NEWSTYLE: u32 = 1 << 0;
+const CLIENT_FLAG_NO_ZEROES: u32 = 1 << 1;
+
+// 옵션 / 옵션 응답
+const OPT_EXPORT_NAME: u32 = 1;
+const OPT_ABORT: u32 = 2;
+const OPT_LIST: u32 = 3;
+const OPT_INFO: u32 = 6;
+const OPT_GO: u32 = 7;
+const REP_ACK: u32 = 1;
+const REP_SERVER: u32 = 2;
+const REP_INFO: u32 = 3;
+const REP_ERR_UNSUP: u32 = (1 << 31) + 1;
+const INFO_EXPORT: u16 = 0;
+
+// 전송 단계 플래그 / 명령
+const TFLAG_HAS_FLAGS: u16 = 1 << 0;
+const TFLAG_SEND_FLUSH: u16 = 1 << 2;
+const TFLAG_SEND_FUA: u16 = 1 << 3;
+const TFLAG_SEND_TRIM: u16 = 1 << 5;
+const CMD_READ: u16 = 0;
+const CMD_WRITE: u16 = 1;
+const CMD_DISC: u16 = 2;
+const CMD_FLUSH: u16 = 3;
+const CMD_TRIM: u16 = 4;
+const CMD_FLAG_FUA: u16 = 1 << 0;
+
+// 에러 코드 (errno 값)
+const EIO: u32 = 5;
+const EINVAL: u32 = 22;
+const ENOSPC: u32 = 28;
+
+// 요청 하나의 최대 크기 (옵션 데이터 / 쓰기 payload)
+const MAX_PAYLOAD: u32 = 32 * 1024 * 1024;
+
+pub struct NbdServer<D: BlockDevice> {
+    file: SsdFile<D>,
+}
+
+impl<D: BlockDevice> NbdServer<D> {
+    // page_size: LBA 하나가 담는 바이트 수 (보통 4096)
+    pub fn new(ssd: D, page_size: usize) -> Result<Self, String> {
+        Ok(NbdServer { file: SsdFile::new(ssd, page_size)? })
+    }
+
+    // 클라이언트에게 보이는 장치 크기 (바이트)
+    pub fn size(&self) -> u64 {
+        self.file.size()
+    }
+
+    pub fn file(&self) -> &SsdFile<D> {
+        &self.file
+    }
+
+    pub fn ssd(&self) -> &D {
+        self.file.ssd()
+    }
+
+    pub fn ssd_mut(&mut self) -> &mut D {
+        self.file.ssd_mut()
+    }
+
+    // 연결을 하나씩 순서대로 처리 (끝날 때마다 on_disconnect 호출, false를 돌려주면 종료)
+    // 클라이언트 하나의 프로토콜 에러는 그 연결만 끊고 다음 연결을 계속 받음
+    #[cfg(unix)]
+    pub fn listen_unix(&mut self, path: &str, mut on_disconnect: impl FnMut(&mut Self) -> bool) -> Result<(), String> {
+        let _ = std::fs::remove_file(path);
+        let listener = UnixListener::bind(path).map_err(|e| format!("Failed to bind {}: {}", path, e))?;
+        info!("[NBD] Listening on unix:{}", path);
+        for stream in listener.incoming() {
+            self.serve_logged(stream.map_err(|e| e.to_string())?);
+            if !on_disconnect(self) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn listen_tcp(&mut self, addr: &str, mut on_disconnect: impl FnMut(&mut Self) -> bool) -> Result<(), String> {
+        let listener = TcpListener::bind(addr).map_err(|e| format!("Failed to bind {}: {}", addr, e))?;
+        info!("[NBD] Listening on tcp:{}", addr);
+        for stream in listener.incoming() {
+            self.serve_logged(stream.map_err(|e| e.to_string())?);
+            if !on_disconnect(self) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    // 클라이언트 연결 하나를 끝까지 처리 (핸드셰이크 -> 전송 단계 -> 연결 종료)
+    pub fn serve<S: Read + Write>(&mut self, mut stream: S) -> Result<(), String> {
+        if self.handshake(&mut stream).map_err(|e| format!("NBD handshake failed: {}", e))? {
+            self.transmission(&mut stream).map_err(|e| format!("NBD connection failed: {}", e))?;
+        }
+        Ok(())
+    }
+
+    fn serve_logged<S: Read + Write>(&mut self, stream: S) {
+        if let Err(e) = self.serve(stream) {
+            warn!("[NBD] Client dropped: {}", e);
+        }
+    }
+
+    // 반환값: 전송 단계로 넘어가면 true, 클라이언트가 중단하면 false
+    fn handshake<S: Read + Write>(&mut self, stream: &mut S) -> io::Result<bool> {
+        put_u64(stream, NBDMAGIC)?;
+        put_u64(stream, IHAVEOPT)?;
+        put_u16(stream, FLAG_FIXED_NEWSTYLE | FLAG_NO_ZEROES)?;
+        stream.flush()?;
+
+        let client_flags = get_u32(stream)?;
+        let no_zeroes = client_flags & CLIENT_FLAG_NO_ZEROES != 0;
+
+        loop {
+            if get_u64(stream)? != IHAVEOPT {
+                return Err(invalid("bad option magic"));
+            }
+            let option = get_u32(stream)?;
+            let len = get_u32(stream)?;
+            if len > MAX_PAYLOAD {
+                return Err(invalid("option data too large"));
+            }
+            let mut data = vec![0u8; len as usize];
+            stream.read_exact(&mut data)?;
+            debug!("[NBD] Option {} ({} bytes)", option, len);
+
+            match option {
+                OPT_EXPORT_NAME => {
+                    put_u64(stream, self.size())?;
+                    put_u16(stream, transmission_flags())?;
+                    if !no_zeroes {
+                        stream.write_all(&[0u8; 124])?;
+                    }
+                    stream.flush()?;
+                    return Ok(true);
+                }
+                OPT_ABORT => {
+                    option_reply(stream, option, REP_ACK, &[])?;
+                    return Ok(false);
+                }
+                OPT_LIST => {
+                    // 이름 없는 기본 export 하나만 제공
+                    option_reply(stream, option, REP_SERVER, &0u32.to_be_bytes())?;
+                    option_reply(stream, option, REP_ACK, &[])?;
+                }
+                OPT_INFO | OPT_GO => {
+                    let mut info = Vec::with_capacity(12);
+                    info.extend_from_slice(&INFO_EXPORT.to_be_bytes());
+                    info.extend_from_slice(&self.size().to_be_bytes());
+                    info.extend_from_slice(&transmission_flags().to_be_bytes());
+                    option_reply(stream, option, REP_INFO, &info)?;
+                    option_reply(stream, option, REP_ACK, &[])?;
+                    if option == OPT_GO {
+                        return Ok(true);
+                    }
+                }
+                _ => option_reply(stream, option, REP_ERR_UNSUP, &[])?,
+            }
+        }
+    }
+
+    fn transmission<S: Read + Write>(&mut self, stream: &mut S) -> io::Result<()> {
+        loop {
+            let mut header = [0u8; 28];
+            match stream.read_exact(&mut header) {
+                Ok(()) => {}
+                // 클라이언트가 DISC 없이 연결을 끊은 경우
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+                Err(e) => return Err(e),
+            }
+
+            let magic = u32::from_be_bytes(header[0..4].try_into().unwrap());
+            if magic != REQUEST_MAGIC {
+                return Err(invalid("bad request magic"));
+            }
+            let flags = u16::from_be_bytes(header[4..6].try_into().unwrap());
+            let command = u16::from_be_bytes(header[6..8].try_into().unwrap());
+            let handle = u64::from_be_bytes(header[8..16].try_into().unwrap());
+            let offset = u64::from_be_bytes(header[16..24].try_into().unwrap());
+            let len = u32::from_be_bytes(header[24..28].try_into().unwrap());
+
+            match command {
+                CMD_READ => match self.read_bytes(offset, len) {
+                    Ok(data) => {
+                        simple_reply(stream, 0, handle)?;
+                        stream.write_all(&data)?;
+                    }
+                    Err(errno) => simple_reply(stream, errno, handle)?,
+                },
+                CMD_WRITE => {
+                    if len > MAX_PAYLOAD {
+                        return Err(invalid("write payload too large"));
+                    }
+                    let mut payload = vec![0u8; len as usize];
+                    stream.read_exact(&mut payload)?;
+                    let mut result = self.write_bytes(offset, &payload);
+                    // FUA: 응답 전에 NAND에 있어야 하므로 쓰기 버퍼를 내려씀
+                    if result.is_ok() && flags & CMD_FLAG_FUA != 0 {
+                        result = self.flush();
+                    }
+                    simple_reply(stream, result.err().unwrap_or(0), handle)?;
+                }
+                CMD_TRIM => {
+                    let errno = self.trim_bytes(offset, len).err().unwrap_or(0);
+                    simple_reply(stream, errno, handle)?;
+                }
+                CMD_FLUSH => {
+                    let errno = self.flush().err().unwrap_or(0);
+                    simple_reply(stream, errno, handle)?;
+                }
+                CMD_DISC => return Ok(()),
+                _ => simple_reply(stream, EINVAL, handle)?,
+            }
+            stream.flush()?;
+        }
+    }
+
+    // 길이는 클라이언트가 보낸 값이므로 버퍼를 잡기 전에 범위부터 확인
+    fn read_bytes(&mut self, offset: u64, len: u32) -> Result<Vec<u8>, u32> {
+        if len > MAX_PAYLOAD || offset.checked_add(len as u64).is_none_or(|end| end > self.size()) {
+            return Err(EINVAL);
+        }
+        let mut data = vec![0u8; len as usize];
+        self.file.read_at(offset, &mut data).map_err(|e| errno(&e, EINVAL))?;
+        Ok(data)
+    }
+
+    fn write_bytes(&mut self, offset: u64, payload: &[u8]) -> Result<(), u32> {
+        self.file.write_at(offset, payload).map_err(|e| errno(&e, ENOSPC))
+    }
+
+    fn trim_bytes(&mut self, offset: u64, len: u32) -> Result<(), u32> {
+        self.file.trim_at(offset, len as u64).map_err(|e| errno(&e, EINVAL))
+    }
+
+    fn flush(&mut self) -> Result<(), u32> {
+        self.file.flush().map_err(|e| errno(&e, EIO))
+    }
+}
+
+// 범위 밖 요청은 out_of_range, 나머지(FTL 에러)는 EIO
+fn errno(e: &io::Error, out_of_range: u32) -> u32 {
+    if e.kind() == io::ErrorKind::InvalidInput { out_of_range } else { EIO }
+}
+
+fn transmission_flags() -> u16 {
+    TFLAG_HAS_FLAGS | TFLAG_SEND_FLUSH | TFLAG_SEND_FUA | TFLAG_SEND_TRIM
+}
+
+// 테스트나 도구에서 서버를 직접 구동하기 위한 최소 클라이언트 (OPT_GO 핸드셰이크 + simple reply)
+pub struct NbdClient<S: Read + Write> {
+    stream: S,
+    size: u64,
+    next_handle: u64,
+}
+
+impl<S: Read + Write> NbdClient<S> {
+    pub fn connect(mut stream: S, export_name: &str) -> Result<Self, String> {
+        let io_err = |e: io::Error| format!("NBD handshake failed: {}", e);
+
+        if get_u64(&mut stream).map_err(io_err)? != NBDMAGIC || get_u64(&mut stream).map_err(io_err)? != IHAVEOPT {
+            return Err("Not an NBD newstyle server".to_string());
+        }
+        let server_flags = get_u16(&mut stream).map_err(io_err)?;
+        if server_flags & FLAG_FIXED_NEWSTYLE == 0 {
+            return Err("Server does not support fixed newstyle negotiation".to_string());
+        }
+        let mut client_flags = CLIENT_FLAG_FIXED_NEWSTYLE;
+        if server_flags & FLAG_NO_ZEROES != 0 {
+            client_flags |= CLIENT_FLAG_NO_ZEROES;
+        }
+        put_u32(&mut stream, client_flags).map_err(io_err)?;
+
+        // OPT_GO: 이름 길이 + 이름 + 정보 요청 0개
+        let mut data = Vec::new();
+        data.extend_from_slice(&(export_name.len() as u32).to_be_bytes());
+        data.extend_from_slice(export_name.as_bytes());
+        data.extend_from_slice(&0u16.to_be_bytes());
+        put_u64(&mut stream, IHAVEOPT).map_err(io_err)?;
+        put_u32(&mut stream, OPT_GO).map_err(io_err)?;
+        put_u32(&mut stream, data.len() as u32).map_err(io_err)?;
+        stream.write_all(&data).map_err(io_err)?;
+        stream.flush().map_err(io_err)?;
+
+        let mut size = None;
+        loop {
+            if get_u64(&mut stream).map_err(io_err)? != REPLY_MAGIC_OPT {
+                return Err("Bad option reply magic".to_string());
+            }
+            let _option = get_u32(&mut stream).map_err(io_err)?;
+            let reply = get_u32(&mut stream).map_err(io_err)?;
+            let len = get_u32(&mut stream).map_err(io_err)?;
+            let mut payload = vec![0u8; len as usize];
+            stream.read_exact(&mut payload).map_err(io_err)?;
+
+            match reply {
+                REP_ACK => break,
+                REP_INFO if payload.len() >= 10 && payload[0..2] == INFO_EXPORT.to_be_bytes() => {
+                    size = Some(u64::from_be_bytes(payload[2..10].try_into().unwrap()));
+                }
+                REP_INFO => {}
+                _ => return Err(format!("Server rejected OPT_GO (reply {:#x})", reply)),
+            }
+        }
+
+        Ok(NbdClient {
+            stream,
+            size: size.ok_or("Server did not report the export size")?,
+            next_handle: 1,
+        })
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn read(&mut self, offset: u64, len: u32) -> Result<Vec<u8>, String> {
+        self.request(CMD_READ, offset, len, &[])?;
+        let mut data = vec![0u8; len as usize];
+        self.stream.read_exact(&mut data).map_err(|e| e.to_string())?;
+        Ok(data)
+    }
+
+    pub fn write(&mut self, offset: u64, data: &[u8]) -> Result<(), String> {
+        self.request(CMD_WRITE, offset, data.len() as u32, data)
+    }
+
+    pub fn trim(&mut self, offset: u64, len: u32) -> Result<(), String> {
+        self.request(CMD_TRIM, offset, len, &[])
+    }
+
+    pub fn flush(&mut self) -> Result<(), String> {
+        self.request(CMD_FLUSH, 0, 0, &[])
+    }
+
+    // DISC에는 응답이 없음
+    pub fn disconnect(mut self) -> Result<(), String> {
+        self.send_request(CMD_DISC, 0, 0, &[]).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    fn send_request(&mut self, command: u16, offset: u64, len: u32, payload: &[u8]) -> io::Result<u64> {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+
+        let mut header = Vec::with_capacity(28 + payload.len());
+        header.extend_from_slice(&REQUEST_MAGIC.to_be_bytes());
+        header.extend_from_slice(&0u16.to_be_bytes());
+        header.extend_from_slice(&command.to_be_bytes());
+        header.extend_from_slice(&handle.to_be_bytes());
+        header.extend_from_slice(&offset.to_be_bytes());
+        header.extend_from_slice(&len.to_be_bytes());
+        header.extend_from_slice(payload);
+        self.stream.write_all(&header)?;
+        self.stream.flush()?;
+        Ok(handle)
+    }
+
+    fn request(&mut self, command: u16, offset: u64, len: u32, payload: &[u8]) -> Result<(), String> {
+        let handle = self.send_request(command, offset, len, payload).map_err(|e| e.to_string())?;
+
+        let magic = get_u32(&mut self.stream).map_err(|e| e.to_string())?;
+        let error = get_u32(&mut self.stream).map_err(|e| e.to_string())?;
+        let reply_handle = get_u64(&mut self.stream).map_err(|e| e.to_string())?;
+        if magic != SIMPLE_REPLY_MAGIC || reply_handle != handle {
+            return Err("Bad NBD reply".to_string());
+        }
+        if error != 0 {
+            return Err(format!("NBD request failed with error {}", error));
+        }
+        Ok(())
+    }
+}
+
+fn option_reply<W: Write>(stream: &mut W, option: u32, reply: u32, data: &[u8]) -> io::Result<()> {
+    put_u64(stream, REPLY_MAGIC_OPT)?;
+    put_u32(stream, option)?;
+    put_u32(stream, reply)?;
+    put_u32(stream, data.len() as u32)?;
+    stream.write_all(data)?;
+    stream.flush()
+}
+
+fn simple_reply<W: Write>(stream: &mut W, error: u32, handle: u64) -> io::Result<()> {
+    put_u32(stream, SIMPLE_REPLY_MAGIC)?;
+    put_u32(stream, error)?;
+    put_u64(stream, handle)
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+// NBD는 모든 정수가 빅엔디언
+fn put_u16<W: Write>(w: &mut W, v: u16) -> io::Result<()> {
+    w.write_all(&v.to_be_bytes())
+}
+
+fn put_u32<W: Write>(w: &mut W, v: u32) -> io::Result<()> {
+    w.write_all(&v.to_be_bytes())
+}
+
+fn put_u64<W: Write>(w: &mut W, v: u64) -> io::Result<()> {
+    w.write_all(&v.to_be_bytes())
+}
+
+fn get_u16<R: Read>(r: &mut R) -> io::Result<u16> {
+    let mut buf = [0u8; 2];
+    r.read_exact(&mut buf)?;
+    Ok(u16::from_be_bytes(buf))
+}
+
+fn get_u32<R: Read>(r: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_be_bytes(buf))
+}
+
+fn get_u64<R: Read>(r: &mut R) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_be_bytes(buf))
+}
@@ -1,14 +1,15 @@
 use std::fmt;
+use serde::{Deserialize, Serialize};
 
 // 1. PageState에도 Debug가 있어야 출력 가능하므로 추가합니다.
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum PageState {
     Free,
     Valid,
     Invalid,
 }
 
-#[derive(Clone)] // 2. 여기서 Debug를 제거하고 직접 구현(impl)합니다.
+#[derive(Clone, Serialize, Deserialize)] // 2. 여기서 Debug를 제거하고 직접 구현(impl)합니다.
 pub struct Page {
     pub content: u32,
     pub state: PageState,
@@ -0,0 +1,267 @@
+// 호스트 논리 블록(섹터) 크기와 NAND 페이지 크기가 다를 때의 변환 계층
+// 호스트는 512 B / 4 KiB 섹터 단위로 주소를 쓰고, NAND 페이지는 8 ~ 16 KiB입니다.
+// FTL은 페이지 단위로 매핑하므로 페이지 일부만 덮는 쓰기는 기존 페이지를 읽어 합친 뒤 페이지 전체를 다시 쓰고(read-modify-write),
+// 이때 생기는 증폭을 FTL의 GC 증폭(WAF)과 따로 집계합니다.
+// 섹터를 페이지 안의 슬롯에 따로 매핑하는 방식은 모델링하지 않습니다. (슬롯별 유효 상태와 덜 찬 페이지의 GC 비용이 필요)
+use serde::{Deserialize, Serialize};
+
+use super::block::Block;
+use super::device::BlockDevice;
+use super::stats::Stats;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectorConfig {
+    // 호스트 논리 블록 크기 (bytes)
+    pub sector_size: usize,
+    // NAND 페이지 크기 (bytes) = 내부 장치의 LBA 하나
+    pub page_size: usize,
+}
+
+impl SectorConfig {
+    pub fn sectors_per_page(&self) -> usize {
+        self.page_size / self.sector_size
+    }
+}
+
+// 섹터 단위 호스트 통계 (페이지 단위 통계는 내부 장치의 stats())
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SectorStats {
+    pub sectors_read: u64,
+    pub sectors_written: u64,
+    pub sectors_trimmed: u64,
+    // 페이지 일부만 덮은 쓰기 (페이지 단위)
+    pub partial_page_writes: u64,
+    // 합치려고 기존 페이지를 NAND에서 읽은 횟수
+    pub rmw_reads: u64,
+}
+
+pub struct SectorDevice<D: BlockDevice> {
+    device: D,
+    config: SectorConfig,
+    // 섹터별 내용 (FTL 페이지에는 섹터들의 체크섬을 씀)
+    // 내부 장치 용량이 바뀔 수 있으므로(resize) 접근할 때마다 num_lbas에 맞춤
+    sectors: Vec<Option<u32>>,
+    stats: SectorStats,
+}
+
+impl<D: BlockDevice> SectorDevice<D> {
+    // 빈 장치로 시작해야 함 (이미 쓰인 페이지의 섹터 내용은 알 수 없음)
+    pub fn new(device: D, config: SectorConfig) -> Result<Self, String> {
+        if config.sector_size == 0 || config.page_size < config.sector_size || !config.page_size.is_multiple_of(config.sector_size) {
+            return Err(format!("Page size {} is not a multiple of sector size {}", config.page_size, config.sector_size));
+        }
+        let sectors = vec![None; device.num_lbas() * config.sectors_per_page()];
+        Ok(SectorDevice { device, config, sectors, stats: SectorStats::default() })
+    }
+
+    pub fn config(&self) -> SectorConfig {
+        self.config
+    }
+
+    pub fn device(&self) -> &D {
+        &self.device
+    }
+
+    pub fn device_mut(&mut self) -> &mut D {
+        &mut self.device
+    }
+
+    pub fn into_inner(self) -> D {
+        self.device
+    }
+
+    pub fn sector_stats(&self) -> &SectorStats {
+        &self.stats
+    }
+
+    // 변환 계층 증폭: FTL에 넘긴 바이트 / 호스트가 쓴 바이트 (GC 이전)
+    pub fn translation_amplification(&self) -> f64 {
+        self.bytes_ratio(self.device.stats().host_writes)
+    }
+
+    // 전체 증폭: NAND에 프로그램한 바이트 / 호스트가 쓴 바이트 (= 변환 계층 증폭 x FTL WAF)
+    pub fn total_amplification(&self) -> f64 {
+        self.bytes_ratio(self.device.stats().nand_writes)
+    }
+
+    fn bytes_ratio(&self, units: u64) -> f64 {
+        let host_bytes = self.stats.sectors_written * self.config.sector_size as u64;
+        if host_bytes == 0 { return 0.0 }
+        (units * self.config.page_size as u64) as f64 / host_bytes as f64
+    }
+
+    // 범위 쓰기: start부터 data.len()개의 섹터
+    pub fn write_range(&mut self, start: usize, data: &[u32]) -> Result<(), String> {
+        self.sync_capacity();
+        self.check_range(start, data.len())?;
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.stats.sectors_written += data.len() as u64;
+
+        // 일부만 덮이는 페이지는 요청의 첫 페이지와 마지막 페이지뿐
+        let n = self.config.sectors_per_page();
+        let end = start + data.len();
+        let (first, last) = (start / n, (end - 1) / n);
+        let mut edges = vec![first];
+        if last != first {
+            edges.push(last);
+        }
+        for page in edges {
+            let covered = end.min((page + 1) * n) - start.max(page * n);
+            if covered < n {
+                self.stats.partial_page_writes += 1;
+                if self.device.read_for_update(page)?.is_some() {
+                    self.stats.rmw_reads += 1;
+                } else {
+                    // FTL에 없는 페이지 (trim, 용량 축소 등)의 나머지 섹터는 빈 섹터로 합침
+                    self.sectors[page * n..(page + 1) * n].fill(None);
+                }
+            }
+        }
+
+        for (slot, &value) in self.sectors[start..end].iter_mut().zip(data) {
+            *slot = Some(value);
+        }
+        let checksums: Vec<u32> = (first..=last).map(|page| self.page_checksum(page)).collect();
+        self.device.write_range(first, &checksums)
+    }
+
+    pub fn read_range(&mut self, start: usize, len: usize) -> Result<Vec<Option<u32>>, String> {
+        self.sync_capacity();
+        self.check_range(start, len)?;
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+        self.stats.sectors_read += len as u64;
+
+        let n = self.config.sectors_per_page();
+        let first = start / n;
+        let pages = self.device.read_range(first, (start + len - 1) / n - first + 1)?;
+        Ok((start..start + len).map(|s| pages[s / n - first].and(self.sectors[s])).collect())
+    }
+
+    // 페이지 안의 섹터가 모두 trim되면 그 페이지를 FTL에서도 trim
+    pub fn trim_range(&mut self, start: usize, len: usize) -> Result<(), String> {
+        self.sync_capacity();
+        self.check_range(start, len)?;
+        if len == 0 {
+            return Ok(());
+        }
+        self.stats.sectors_trimmed += len as u64;
+
+        let n = self.config.sectors_per_page();
+        self.sectors[start..start + len].fill(None);
+        let mut run: Option<(usize, usize)> = None;
+        for page in start / n..=(start + len - 1) / n {
+            let empty = self.sectors[page * n..(page + 1) * n].iter().all(Option::is_none);
+            run = match run {
+                Some((first, count)) if empty && first + count == page => Some((first, count + 1)),
+                Some((first, count)) => {
+                    self.device.trim_range(first, count)?;
+                    empty.then_some((page, 1))
+                }
+                None => empty.then_some((page, 1)),
+            };
+        }
+        if let Some((first, count)) = run {
+            self.device.trim_range(first, count)?;
+        }
+        Ok(())
+    }
+
+    // 페이지에 든 섹터 내용의 체크섬 (FTL 페이지에 저장하는 값)
+    fn page_checksum(&self, page: usize) -> u32 {
+        let n = self.config.sectors_per_page();
+        self.sectors[page * n..(page + 1) * n].iter().fold(0x811c_9dc5u32, |hash, sector| {
+            let value = sector.map_or(0, |v| v ^ 0x9e37_79b9);
+            (hash ^ value).wrapping_mul(0x0100_0193)
+        })
+    }
+
+    // 내부 장치를 device_mut()으로 resize했으면 섹터 보관소도 같은 크기로 (줄어든 섹터의 내용은 버림)
+    fn sync_capacity(&mut self) {
+        let num_sectors = self.num_sectors();
+        if self.sectors.len() != num_sectors {
+            self.sectors.resize(num_sectors, None);
+        }
+    }
+
+    fn num_sectors(&self) -> usize {
+        self.device.num_lbas() * self.config.sectors_per_page()
+    }
+
+    fn check_range(&self, start: usize, len: usize) -> Result<(), String> {
+        let num_sectors = self.num_sectors();
+        if start >= num_sectors || len > num_sectors - start {
+            return Err(format!("Sector {} is out of range!", start.max(num_sectors)));
+        }
+        Ok(())
+    }
+}
+
+// LBA = 섹터 번호로 보이는 장치 (워크로드/트레이스를 섹터 단위로 그대로 실행)
+impl<D: BlockDevice> BlockDevice for SectorDevice<D> {
+    fn read(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        Ok(self.read_range(lba, 1)?[0])
+    }
+
+    fn write(&mut self, lba: usize, data: u32) -> Result<(), String> {
+        self.write_range(lba, std::slice::from_ref(&data))
+    }
+
+    fn trim(&mut self, lba: usize) -> Result<(), String> {
+        self.trim_range(lba, 1)
+    }
+
+    fn write_range(&mut self, start_lba: usize, data: &[u32]) -> Result<(), String> {
+        SectorDevice::write_range(self, start_lba, data)
+    }
+
+    fn read_range(&mut self, start_lba: usize, len: usize) -> Result<Vec<Option<u32>>, String> {
+        SectorDevice::read_range(self, start_lba, len)
+    }
+
+    fn trim_range(&mut self, start_lba: usize, len: usize) -> Result<(), String> {
+        SectorDevice::trim_range(self, start_lba, len)
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        self.device.flush()
+    }
+
+    fn num_lbas(&self) -> usize {
+        self.num_sectors()
+    }
+
+    // 내부 장치(페이지 단위) 통계, 섹터 단위는 sector_stats()
+    fn stats(&self) -> &Stats {
+        self.device.stats()
+    }
+
+    fn reset_stats(&mut self) {
+        self.device.reset_stats();
+        self.stats = SectorStats::default();
+    }
+
+    fn blocks(&self) -> &[Block] {
+        self.device.blocks()
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        self.device.check_invariants()
+    }
+
+    // 내부 장치 상태만 저장 (섹터 내용은 포함하지 않음)
+    fn save_snapshot(&self, path: &str) -> Result<(), String> {
+        self.device.save_snapshot(path)
+    }
+
+    fn set_arrival(&mut self, arrival_us: u64) {
+        self.device.set_arrival(arrival_us);
+    }
+
+    fn clock_us(&self) -> Option<u64> {
+        self.device.clock_us()
+    }
+}
@@ -0,0 +1,43 @@
+// 마스터 seed 하나에서 구성 요소별 독립 seed를 뽑아내는 함수
+// 같은 마스터 seed + 같은 설정이면 모든 난수열(워크로드, 도착 시각, GC, 배드 블록 배치)이 똑같이 재현됩니다.
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+// 난수를 쓰는 구성 요소마다 고유한 스트림 번호
+pub const STREAM_FTL: u64 = 1;
+pub const STREAM_WARMUP: u64 = 2;
+pub const STREAM_MEASURE: u64 = 3;
+pub const STREAM_ARRIVAL: u64 = 4;
+
+// SplitMix64: 비슷한 입력(seed 1, 2, 3...)도 서로 무관한 출력으로 흩어줌
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+pub fn derive_seed(master: u64, stream: u64) -> u64 {
+    splitmix64(master ^ splitmix64(stream))
+}
+
+// 난수열의 정확한 위치 (ChaCha seed + 지금까지 소비한 32비트 word 수)
+// 스냅샷이 라이브 난수를 건드리지 않고 저장하고, 복원하면 같은 위치부터 이어서 뽑음
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RngState {
+    seed: [u8; 32],
+    word_pos: u128,
+}
+
+impl RngState {
+    pub fn of(rng: &ChaCha12Rng) -> Self {
+        RngState { seed: rng.get_seed(), word_pos: rng.get_word_pos() }
+    }
+
+    pub fn restore(&self) -> ChaCha12Rng {
+        let mut rng = ChaCha12Rng::from_seed(self.seed);
+        rng.set_word_pos(self.word_pos);
+        rng
+    }
+}
@@ -0,0 +1,278 @@
+// FTL을 손으로 한 단계씩 조작하는 대화형 셸 (GC 동작 디버깅용)
+// 명령 한 줄 = execute() 한 번이므로, 같은 명령을 파일에 적어두면 스크립트로 그대로 재현할 수 있습니다.
+// 장치를 바꾸는 명령(write/fill/trim/gc/flush 등) 직전마다 스냅샷을 쌓아두고 undo로 한 단계씩 되돌립니다.
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+use super::alloc::AllocPolicy;
+use super::capacity::OpMode;
+use super::ssd_basic::{SsdSnapshot, SSD};
+use super::write_buffer::{EvictionPolicy, WriteBufferConfig};
+
+// undo로 되돌릴 수 있는 최대 단계 수
+const UNDO_DEPTH: usize = 64;
+
+pub const HELP: &str = "\
+Commands:
+  write <lba> <data>       write data (decimal or 0x hex) to an LBA
+  fill <start> <end>       write every LBA in [start, end) with its own LBA number
+  read <lba>               read an LBA
+  trim <lba>               unmap an LBA
+  fua <lba> <data>         write straight to NAND, bypassing the write buffer
+  flush                    write the buffered data to NAND
+  powerloss                drop everything still in the write buffer
+  buffer <pages> [policy]  enable a write buffer (policy: lru, fifo, padding)
+  buffer off               flush and remove the write buffer
+  capacity                 raw / user / spare capacity and effective OP
+  resize <lbas>            shrink or grow the exported capacity
+  opmode <dynamic|static>  whether trimmed space is reclaimed as spare by GC
+  alloc <policy>           free block allocation (first_free, lowest_erase, round_robin, random, hot_cold)
+  gc                       run GC until one victim block is erased
+  gc step                  run a single incremental GC step
+  show block <id>          dump one physical block
+  show map [lba]           dump the whole mapping table, or one LBA
+  show heatmap             block valid-ratio / erase-count heatmap
+  stats                    FTL statistics and erase distribution
+  check                    verify mapping / page / block / counter consistency
+  snapshot <file>          save the device to a snapshot file
+  load <file>              replace the device with a snapshot file
+  undo                     revert the last command that changed the device
+  source <file>            run commands from a script file
+  help                     show this help
+  quit                     leave the shell
+Lines starting with '#' are comments.";
+
+#[derive(Debug)]
+pub enum Outcome {
+    // 출력할 내용 (비어 있을 수 있음)
+    Output(String),
+    Quit,
+}
+
+pub struct Shell {
+    ssd: SSD,
+    history: Vec<SsdSnapshot>,
+}
+
+impl Shell {
+    pub fn new(ssd: SSD) -> Self {
+        Shell { ssd, history: Vec::new() }
+    }
+
+    pub fn ssd(&self) -> &SSD {
+        &self.ssd
+    }
+
+    pub fn into_inner(self) -> SSD {
+        self.ssd
+    }
+
+    // 명령 한 줄 실행
+    pub fn execute(&mut self, line: &str) -> Result<Outcome, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let output = match words.as_slice() {
+            [] => String::new(),
+            [first, ..] if first.starts_with('#') => String::new(),
+            ["quit" | "exit"] => return Ok(Outcome::Quit),
+            ["help"] => HELP.to_string(),
+
+            ["write", lba, data] => {
+                let (lba, data) = (parse_num(lba)?, parse_u32(data)?);
+                self.mutate(|ssd| ssd.write(lba, data))?;
+                format!("LBA {} <- {:#010X}", lba, data)
+            }
+            ["fill", start, end] => {
+                let (start, end) = (parse_num(start)?, parse_num(end)?);
+                self.mutate(|ssd| (start..end).try_for_each(|lba| ssd.write(lba, lba as u32)))?;
+                format!("LBA {}..{} written", start, end)
+            }
+            ["read", lba] => {
+                let lba = parse_num(lba)?;
+                match self.ssd.read(lba)? {
+                    Some(data) => format!("LBA {} = {:#010X}", lba, data),
+                    None => format!("LBA {} is unmapped", lba),
+                }
+            }
+            ["trim", lba] => {
+                let lba = parse_num(lba)?;
+                self.mutate(|ssd| ssd.trim(lba))?;
+                format!("LBA {} trimmed", lba)
+            }
+            ["fua", lba, data] => {
+                let (lba, data) = (parse_num(lba)?, parse_u32(data)?);
+                self.mutate(|ssd| ssd.write_fua(lba, data))?;
+                format!("LBA {} <- {:#010X} (FUA)", lba, data)
+            }
+            ["flush"] => {
+                self.mutate(|ssd| ssd.flush())?;
+                "Write buffer flushed".to_string()
+            }
+            ["powerloss"] => {
+                let lost = self.mutate(|ssd| Ok(ssd.power_loss()))?;
+                format!("Power lost: {} buffered pages dropped", lost)
+            }
+            ["buffer", "off"] => {
+                self.mutate(|ssd| ssd.set_write_buffer(None))?;
+                "Write buffer removed".to_string()
+            }
+            ["buffer", capacity, rest @ ..] if rest.len() <= 1 => {
+                let capacity = parse_num(capacity)?;
+                let policy = rest.first().map_or(Ok(EvictionPolicy::default()), |name| EvictionPolicy::parse(name))?;
+                self.mutate(|ssd| ssd.set_write_buffer(Some(WriteBufferConfig { capacity, policy })))?;
+                format!("Write buffer: {} pages, {:?}", capacity, policy)
+            }
+            ["capacity"] => self.ssd.capacity().to_string(),
+            ["resize", num_lbas] => {
+                let num_lbas = parse_num(num_lbas)?;
+                self.mutate(|ssd| ssd.resize(num_lbas))?;
+                format!("Exporting {} LBAs\n{}", num_lbas, self.ssd.capacity())
+            }
+            ["opmode", mode] => {
+                let mode = OpMode::parse(mode)?;
+                self.mutate(|ssd| {
+                    ssd.set_op_mode(mode);
+                    Ok(())
+                })?;
+                format!("OP mode: {:?}", mode)
+            }
+            ["alloc", policy] => {
+                let policy = AllocPolicy::parse(policy)?;
+                self.mutate(|ssd| {
+                    ssd.set_alloc_policy(policy);
+                    Ok(())
+                })?;
+                format!("Allocation policy: {:?}", policy)
+            }
+            ["gc"] => {
+                self.mutate(|ssd| ssd.gc())?;
+                format!("GC done (free blocks: {})", self.ssd.count_free_blocks())
+            }
+            ["gc", "step"] => {
+                let step = self.mutate(|ssd| ssd.gc_step())?;
+                format!("{:?} (free blocks: {})", step, self.ssd.count_free_blocks())
+            }
+
+            ["show", "block", id] => {
+                let id = parse_num(id)?;
+                let block = self.ssd.blocks().get(id).ok_or_else(|| format!("Block {} does not exist", id))?;
+                format!("{:?}", block)
+            }
+            ["show", "map"] => format!("{:?}", self.ssd.mapping_table()).trim_end().to_string(),
+            ["show", "map", lba] => {
+                let lba = parse_num(lba)?;
+                if lba >= self.ssd.num_lbas() {
+                    return Err(format!("LBA {} is out of range!", lba));
+                }
+                match self.ssd.mapping_table().get(lba) {
+                    Some(pba) => format!("LBA [{}] -> Block {} | Page {}", lba, pba.block_id, pba.page_offset),
+                    None => format!("LBA [{}] -> (unmapped)", lba),
+                }
+            }
+            ["show", "heatmap"] => super::visualize::terminal_heatmap(self.ssd.blocks(), 64).trim_end().to_string(),
+            ["stats"] => {
+                let erase = self.ssd.erase_distribution();
+                format!(
+                    "{}\n  Erase: min {} | p50 {} | p99 {} | max {} | std-dev {:.2}\n  Free blocks: {}",
+                    self.ssd.stats(), erase.min, erase.p50, erase.p99, erase.max, erase.std_dev,
+                    self.ssd.count_free_blocks()
+                )
+            }
+            ["check"] => {
+                self.ssd.check_invariants()?;
+                "Invariants OK".to_string()
+            }
+
+            ["snapshot", path] => {
+                self.ssd.save_snapshot(path)?;
+                format!("Snapshot saved to {}", path)
+            }
+            ["load", path] => {
+                let ssd = SSD::load_snapshot(path)?;
+                self.checkpoint();
+                self.ssd = ssd;
+                format!("Loaded {}", path)
+            }
+            ["undo"] => match self.history.pop() {
+                Some(snapshot) => {
+                    self.ssd = SSD::from_snapshot(snapshot);
+                    format!("Undone ({} more steps available)", self.history.len())
+                }
+                None => "Nothing to undo".to_string(),
+            },
+            ["source", path] => return self.run_script(path),
+
+            _ => return Err(format!("Unknown command '{}' (type 'help')", line.trim())),
+        };
+        Ok(Outcome::Output(output))
+    }
+
+    // 스크립트 파일 재생: 명령마다 "> 명령"과 결과를 출력하고, 첫 에러에서 줄 번호와 함께 중단
+    pub fn run_script(&mut self, path: &str) -> Result<Outcome, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let mut transcript = Vec::new();
+
+        for (line_no, line) in content.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            transcript.push(format!("> {}", line.trim()));
+            match self.execute(line).map_err(|e| format!("{}:{}: {}", path, line_no + 1, e))? {
+                Outcome::Output(out) if !out.is_empty() => transcript.push(out),
+                Outcome::Output(_) => {}
+                Outcome::Quit => return Ok(Outcome::Quit),
+            }
+        }
+        Ok(Outcome::Output(transcript.join("\n")))
+    }
+
+    // 표준 입력으로 대화형 실행 (에러는 출력만 하고 계속)
+    pub fn run_interactive(&mut self) -> Result<(), String> {
+        let stdin = io::stdin();
+        let mut lines = stdin.lock().lines();
+        println!("ssd-sim shell: {} blocks, {} LBAs. Type 'help' for commands.",
+                 self.ssd.blocks().len(), self.ssd.num_lbas());
+
+        loop {
+            print!("ssd> ");
+            io::stdout().flush().map_err(|e| e.to_string())?;
+
+            let Some(line) = lines.next() else { break };
+            let line = line.map_err(|e| e.to_string())?;
+            match self.execute(&line) {
+                Ok(Outcome::Output(out)) if !out.is_empty() => println!("{}", out),
+                Ok(Outcome::Output(_)) => {}
+                Ok(Outcome::Quit) => break,
+                Err(e) => println!("error: {}", e),
+            }
+        }
+        Ok(())
+    }
+
+    // 장치를 바꾸는 동작: 실행 전 상태를 undo 기록에 쌓음
+    // 실패해도 기록은 남김 (fill처럼 도중에 실패한 명령은 일부만 적용되어 있으므로 undo로 실행 전 상태로 되돌림)
+    fn mutate<T>(&mut self, op: impl FnOnce(&mut SSD) -> Result<T, String>) -> Result<T, String> {
+        self.checkpoint();
+        op(&mut self.ssd)
+    }
+
+    fn checkpoint(&mut self) {
+        if self.history.len() == UNDO_DEPTH {
+            self.history.remove(0);
+        }
+        let snapshot = self.ssd.snapshot();
+        self.history.push(snapshot);
+    }
+}
+
+fn parse_num(s: &str) -> Result<usize, String> {
+    let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => s.parse(),
+    };
+    parsed.map_err(|_| format!("Invalid number '{}'", s))
+}
+
+// 페이지 데이터는 32비트 (잘라내지 않고 에러)
+fn parse_u32(s: &str) -> Result<u32, String> {
+    u32::try_from(parse_num(s)?).map_err(|_| format!("Data '{}' does not fit in 32 bits", s))
+}
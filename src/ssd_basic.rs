@@ -1,203 +1,870 @@
 use super::block::*;
+use super::cell::CellMode;
 use super::mapping::*;
 use super::page::*;
-use log::{info, debug, warn, trace};
+use super::stats::*;
+use super::metrics::MetricsRecorder;
+use super::visualize::terminal_heatmap;
+use super::gc::*;
+use super::device::BlockDevice;
+use super::invariants;
+use super::alloc::{AllocPolicy, FreeBlockPool, WriteStream};
+use super::capacity::{check_user_pages, Capacity, OpMode, OverProvisioning};
+use super::write_buffer::{EvictionPolicy, WriteBuffer, WriteBufferConfig};
+use super::seed::{derive_seed, RngState, STREAM_FTL};
+use log::{info, debug, warn};
+use rand::seq::index::sample;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
 pub struct SSD {
     blocks: Vec<Block>,
     mapping_table: MappingTable,
     active_block_idx: usize,
-    user_write_cnt: u64,
-    nand_write_cnt: u64,
-    gap_threshold: u32
+    stats: Stats,
+    // 시계열 메트릭 샘플러 (붙이지 않으면 None)
+    metrics: Option<MetricsRecorder>,
+    // 진행 중인 점진적 GC 작업 (없으면 None)
+    gc_job: Option<GcJob>,
+    // 지금까지 수행한 GC step 수 (스냅샷에 저장하지 않음, 호출 앞뒤의 차이로만 씀)
+    gc_steps: u64,
+    victim_policy: VictimPolicy,
+    // FTL 내부 난수 (d-choices GC, 배드 블록 배치) - 마스터 seed에서 파생
+    rng: ChaCha12Rng,
+    // 디버그용: 매 연산 뒤 check_invariants() 실행
+    debug_checks: bool,
+    // DRAM 쓰기 버퍼 (없으면 모든 쓰기가 바로 NAND로)
+    write_buffer: Option<WriteBuffer>,
+    op_mode: OpMode,
+    // 고정 OP 모드에서 trim되었지만 페이지는 아직 유효한 LBA (읽으면 None)
+    pending_trims: BTreeSet<usize>,
+    alloc_policy: AllocPolicy,
+    // 할당할 수 있는 빈 블록 (Active Block 제외)
+    free_pool: FreeBlockPool,
+    // GC 후보 블록의 무효 페이지 수별 색인 (페이지 상태가 바뀔 때마다 갱신)
+    victims: VictimIndex,
+}
+
+// 장치 상태 전체를 저장/복원하기 위한 스냅샷 (메트릭 샘플러는 포함하지 않음)
+// FTL 난수는 난수열 위치까지 저장 -> 복원된 장치가 원본과 같은 위치부터 같은 난수열을 사용
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SsdSnapshot {
+    blocks: Vec<Block>,
+    mapping_table: MappingTable,
+    active_block_idx: usize,
+    #[serde(with = "super::stats::with_lba_writes")]
+    stats: Stats,
+    gc_job: Option<GcJob>,
+    victim_policy: VictimPolicy,
+    rng: RngState,
+    #[serde(default)]
+    write_buffer: Option<WriteBuffer>,
+    #[serde(default)]
+    op_mode: OpMode,
+    #[serde(default)]
+    pending_trims: BTreeSet<usize>,
+    #[serde(default)]
+    alloc_policy: AllocPolicy,
+    // RoundRobin 할당 위치 (빈 블록 풀 자체는 블록 상태에서 다시 만듦)
+    #[serde(default)]
+    alloc_cursor: usize,
 }
 
 impl SSD {
     pub fn new(num_blocks: usize, num_lbas: usize) -> Self {
+        Self::with_seed(num_blocks, num_lbas, 0)
+    }
+
+    // seed: 실험 전체의 마스터 seed (같은 seed면 FTL의 무작위 결정도 똑같이 재현됨)
+    pub fn with_seed(num_blocks: usize, num_lbas: usize, seed: u64) -> Self {
         let mut blocks = Vec::new();
         for i in 0..num_blocks {
             blocks.push(Block::new(i as u32));
         }
+        let free_pool = FreeBlockPool::from_blocks(&blocks, 0, 0);
 
         SSD {
             blocks,
             mapping_table: MappingTable::new(num_lbas),
             active_block_idx: 0,
-            user_write_cnt: 0,
-            nand_write_cnt: 0,
-            gap_threshold: 5
+            stats: Stats::new(num_lbas),
+            metrics: None,
+            gc_job: None,
+            gc_steps: 0,
+            victim_policy: VictimPolicy::Greedy,
+            rng: ChaCha12Rng::seed_from_u64(derive_seed(seed, STREAM_FTL)),
+            debug_checks: false,
+            write_buffer: None,
+            op_mode: OpMode::Dynamic,
+            pending_trims: BTreeSet::new(),
+            alloc_policy: AllocPolicy::FirstFree,
+            free_pool,
+            victims: VictimIndex::new(num_blocks),
+        }
+    }
+
+    // OP로 호스트 용량을 정해서 생성 (블록은 모두 기본 셀 모드)
+    pub fn with_over_provisioning(num_blocks: usize, op: OverProvisioning, seed: u64) -> Result<Self, String> {
+        let num_lbas = op.user_pages(num_blocks, CellMode::default().pages_per_block())?;
+        Ok(Self::with_seed(num_blocks, num_lbas, seed))
+    }
+
+    pub fn snapshot(&self) -> SsdSnapshot {
+        SsdSnapshot {
+            blocks: self.blocks.clone(),
+            mapping_table: self.mapping_table.clone(),
+            active_block_idx: self.active_block_idx,
+            stats: self.stats.clone(),
+            gc_job: self.gc_job,
+            victim_policy: self.victim_policy,
+            rng: RngState::of(&self.rng),
+            write_buffer: self.write_buffer.clone(),
+            op_mode: self.op_mode,
+            pending_trims: self.pending_trims.clone(),
+            alloc_policy: self.alloc_policy,
+            alloc_cursor: self.free_pool.cursor(),
+        }
+    }
+
+    pub fn from_snapshot(snapshot: SsdSnapshot) -> Self {
+        let free_pool = FreeBlockPool::from_blocks(&snapshot.blocks, snapshot.active_block_idx, snapshot.alloc_cursor);
+        let victims = VictimIndex::from_blocks(&snapshot.blocks, snapshot.active_block_idx);
+        SSD {
+            blocks: snapshot.blocks,
+            mapping_table: snapshot.mapping_table,
+            active_block_idx: snapshot.active_block_idx,
+            stats: snapshot.stats,
+            metrics: None,
+            gc_job: snapshot.gc_job,
+            gc_steps: 0,
+            victim_policy: snapshot.victim_policy,
+            rng: snapshot.rng.restore(),
+            debug_checks: false,
+            write_buffer: snapshot.write_buffer,
+            op_mode: snapshot.op_mode,
+            pending_trims: snapshot.pending_trims,
+            alloc_policy: snapshot.alloc_policy,
+            free_pool,
+            victims,
+        }
+    }
+
+    pub fn save_snapshot(&self, path: &str) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+        serde_json::to_writer(BufWriter::new(file), &self.snapshot())
+            .map_err(|e| format!("Failed to write snapshot {}: {}", path, e))
+    }
+
+    pub fn load_snapshot(path: &str) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+        let snapshot: SsdSnapshot = serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| format!("Failed to parse snapshot {}: {}", path, e))?;
+        Ok(Self::from_snapshot(snapshot))
+    }
+
+    // 켜면 호스트 연산과 GC step이 끝날 때마다 check_invariants()를 돌려 위반 시 에러 반환 (느림)
+    pub fn set_debug_checks(&mut self, enabled: bool) {
+        self.debug_checks = enabled;
+    }
+
+    // 매핑 테이블 / 페이지 / 블록 상태 / 통계 카운터의 일관성 검사 (첫 번째 위반을 에러로)
+    pub fn check_invariants(&self) -> Result<(), String> {
+        invariants::check(&self.blocks, &self.mapping_table, self.active_block_idx, &self.stats)?;
+
+        // 진행 중인 GC: victim은 쓸 수 있는 블록이고, 이미 지나간 페이지에는 유효 데이터가 남아있지 않아야 함
+        if let Some(job) = self.gc_job {
+            let victim = self.blocks.get(job.victim_idx)
+                .ok_or_else(|| format!("GC victim {} does not exist", job.victim_idx))?;
+            if victim.is_bad || job.victim_idx == self.active_block_idx || job.next_page > victim.pages.len() {
+                return Err(format!("GC job {:?} is not a valid victim", job));
+            }
+            if let Some(page) = (0..job.next_page).find(|&p| victim.pages[p].state == PageState::Valid) {
+                return Err(format!("GC victim {} Page {} is still Valid after migration", job.victim_idx, page));
+            }
+        }
+        self.free_pool.check(&self.blocks, self.active_block_idx)?;
+        self.victims.check(&self.blocks, self.active_block_idx)?;
+        if let Some(buffer) = &self.write_buffer {
+            buffer.check(self.num_lbas())?;
+        }
+        // 회수 대기 중인 trim은 고정 OP 모드에서만, 아직 유효 페이지를 가진 LBA만
+        if let Some(&lba) = self.pending_trims.iter().next()
+            && self.op_mode == OpMode::Dynamic {
+            return Err(format!("LBA {} has a pending trim in dynamic OP mode", lba));
+        }
+        if let Some(&lba) = self.pending_trims.iter().find(|&&lba| lba >= self.num_lbas() || self.mapping_table.get(lba).is_none()) {
+            return Err(format!("LBA {} has a pending trim but is not mapped", lba));
+        }
+        Ok(())
+    }
+
+    fn debug_check(&self, op: &str) -> Result<(), String> {
+        if !self.debug_checks {
+            return Ok(());
+        }
+        self.check_invariants().map_err(|e| format!("Invariant violated after {}: {}", op, e))
+    }
+
+    // 블록의 셀 모드 변경 (지워진 블록만 가능, 혼합 모드 장치 구성용)
+    pub fn set_cell_mode(&mut self, block_idx: usize, cell_mode: CellMode) -> Result<(), String> {
+        let block = self.blocks.get_mut(block_idx).ok_or_else(|| format!("Block {} does not exist", block_idx))?;
+        block.set_cell_mode(cell_mode)?;
+        // 빈 블록 풀이 세는 페이지 수도 바뀜
+        self.free_pool.update_pages(&self.blocks[block_idx]);
+        Ok(())
+    }
+
+    pub fn set_victim_policy(&mut self, policy: VictimPolicy) {
+        self.victim_policy = policy;
+    }
+
+    pub fn set_alloc_policy(&mut self, policy: AllocPolicy) {
+        self.alloc_policy = policy;
+    }
+
+    pub fn alloc_policy(&self) -> AllocPolicy {
+        self.alloc_policy
+    }
+
+    // 쓰기 버퍼 설정/제거 (기존 버퍼에 남은 데이터는 먼저 NAND에 내려씀)
+    pub fn set_write_buffer(&mut self, config: Option<WriteBufferConfig>) -> Result<(), String> {
+        let new_buffer = config.map(WriteBuffer::new).transpose()?;
+        self.flush()?;
+        self.write_buffer = new_buffer;
+        Ok(())
+    }
+
+    pub fn write_buffer(&self) -> Option<&WriteBuffer> {
+        self.write_buffer.as_ref()
+    }
+
+    // 고정 -> 동적으로 바꾸면 회수 대기 중이던 trim을 바로 반영
+    pub fn set_op_mode(&mut self, mode: OpMode) {
+        self.op_mode = mode;
+        if mode == OpMode::Dynamic {
+            for lba in std::mem::take(&mut self.pending_trims) {
+                self.unmap(lba);
+            }
+        }
+    }
+
+    pub fn op_mode(&self) -> OpMode {
+        self.op_mode
+    }
+
+    pub fn capacity(&self) -> Capacity {
+        Capacity::of(&self.blocks, self.num_lbas())
+    }
+
+    // 호스트에 내보내는 용량을 num_lbas로 바꿈 (줄이면 잘려나간 LBA는 trim되어 그 공간이 여분 OP가 됨)
+    // LBA별 쓰기 기록은 줄여도 그대로 둠 (host_writes 합계와 맞아야 하므로)
+    pub fn resize(&mut self, num_lbas: usize) -> Result<(), String> {
+        // 늘릴 때도 GC가 쓸 여유 블록 하나(가장 큰 블록 기준)는 남아야 함
+        let largest_block = self.blocks.iter().map(|b| b.pages.len()).max().unwrap_or(0);
+        check_user_pages(num_lbas, self.capacity().raw_pages, largest_block)?;
+
+        for lba in num_lbas..self.num_lbas() {
+            if let Some(buffer) = self.write_buffer.as_mut() && buffer.remove(lba).is_some() {
+                self.stats.buffer_absorbed += 1;
+            }
+            self.pending_trims.remove(&lba);
+            self.unmap(lba);
+        }
+        self.mapping_table.resize(num_lbas);
+        if self.stats.lba_writes.len() < num_lbas {
+            self.stats.lba_writes.resize(num_lbas, 0);
+        }
+        info!("[Resize] Exporting {} LBAs (OP {:.2}%)", num_lbas, self.capacity().op_pct());
+        self.debug_check("resize")
+    }
+
+    // LBA의 매핑을 지우고 페이지를 무효화
+    fn unmap(&mut self, lba: usize) {
+        if let Some(old_pba) = self.mapping_table.unmap(lba) {
+            self.blocks[old_pba.block_id as usize].pages[old_pba.page_offset].state = PageState::Invalid;
+            self.victims.invalidate(old_pba.block_id as usize);
+            debug!("  -> Unmapped LBA {}: Block {} Page {}", lba, old_pba.block_id, old_pba.page_offset);
         }
     }
 
+    // 공장 출하 시 배드 블록: count개의 블록을 무작위(seed 기반) 위치에 배드로 지정
+    // 아무것도 쓰기 전에만 호출 가능
+    pub fn place_bad_blocks(&mut self, count: usize) -> Result<(), String> {
+        if self.blocks.iter().any(|b| b.state != BlockState::Free) {
+            return Err("Bad blocks must be placed before the first write".to_string());
+        }
+        if count + 2 > self.blocks.len() {
+            return Err(format!("Too many bad blocks: {} of {}", count, self.blocks.len()));
+        }
+
+        for idx in sample(&mut self.rng, self.blocks.len(), count) {
+            self.blocks[idx].is_bad = true;
+            self.free_pool.remove(idx);
+            debug!("[BadBlock] Block {} marked as factory bad block", idx);
+        }
+
+        if self.blocks[self.active_block_idx].is_bad {
+            let next_idx = self.find_next_free_block(WriteStream::Host).expect("good block must exist");
+            self.switch_active_block(next_idx);
+        }
+        Ok(())
+    }
+
     // [수정 1] 공용 Write 함수: 정책 담당 (사용자 카운트 증가 + GC 트리거 + 위임)
     pub fn write(&mut self, lba: usize, data: u32) -> Result<(), String> {
-        self.user_write_cnt += 1; // 사용자가 요청했으므로 증가
+        self.write_range(lba, std::slice::from_ref(&data))
+    }
 
-        // [방어 로직] 빈 블록이 1개 이하로 남으면 미리 GC를 돌려서 여유 공간 확보 (Reserved Block)
-        while self.count_free_blocks() == 0 {
-            self.gc()?;
+    // 범위 쓰기: start_lba부터 data.len()개의 연속된 LBA를 요청 하나로
+    // 페이지는 Active Block에 연속으로 배치하고, GC 확인은 블록을 바꿀 때만 함
+    pub fn write_range(&mut self, start_lba: usize, data: &[u32]) -> Result<(), String> {
+        self.mapping_table.check_range(start_lba, data.len())?;
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.stats.record_write_request(data.len());
+        for lba in start_lba..start_lba + data.len() {
+            self.stats.record_host_write(lba); // 사용자가 요청했으므로 증가
+        }
 
+        let pages = (start_lba..).zip(data.iter().copied());
+        // 쓰기 버퍼가 있으면 버퍼에만 받고, 넘친 만큼만 NAND로 내보냄
+        if let Some(buffer) = self.write_buffer.as_mut() {
+            for (lba, data) in pages {
+                if buffer.insert(lba, data) {
+                    self.stats.buffer_absorbed += 1;
+                }
+            }
+            self.evict_overflow()?;
+        } else {
+            self.write_pages_to_nand(pages)?;
         }
 
-        // 실제 쓰기는 internal에게 위임!
-        // 만약 internal이 실패하면(Active Block Full), 블록을 바꾸고 다시 시도
-        if let Err(_) = self.write_internal(lba, data) {
-            // Active Block이 꽉 찼으니 다음 빈 블록 찾기
-            if let Some(next_idx) = self.find_next_free_block() {
-                debug!("Switching Active Block: {} -> {}", self.active_block_idx, next_idx);
-                self.active_block_idx = next_idx;
-                
-                // 블록 교체 후 재시도 (여기선 무조건 성공해야 함)
-                self.write_internal(lba, data)?;
-            } else {
-                // GC를 했는데도 빈 블록이 없다?
-                return Err("Fatal Error: SSD is Full!".to_string());
+        self.sample_metrics();
+        self.debug_check("write")
+    }
+
+    // FUA (Force Unit Access): 이 쓰기는 완료 시점에 NAND에 있어야 함 -> 버퍼를 거치지 않고 바로 프로그램
+    // 버퍼에 같은 LBA의 이전 데이터가 있으면 버림 (NAND에 갈 필요가 없어짐)
+    pub fn write_fua(&mut self, lba: usize, data: u32) -> Result<(), String> {
+        self.mapping_table.check_range(lba, 1)?;
+        self.stats.record_write_request(1);
+        self.stats.record_host_write(lba);
+
+        if let Some(buffer) = self.write_buffer.as_mut() && buffer.remove(lba).is_some() {
+            self.stats.buffer_absorbed += 1;
+        }
+        self.write_to_nand(lba, data)?;
+        if self.write_buffer.is_some() {
+            self.pad_to_program_unit()?;
+        }
+
+        self.sample_metrics();
+        self.debug_check("write_fua")
+    }
+
+    // 버퍼의 모든 데이터를 NAND에 내려쓰고, 열린 프로그램 단위를 채워 마무리
+    // (버퍼가 없으면 모든 쓰기가 이미 NAND에 있으므로 할 일 없음)
+    pub fn flush(&mut self) -> Result<(), String> {
+        let Some(buffer) = self.write_buffer.as_mut() else { return Ok(()) };
+        let drained = buffer.drain();
+        self.write_pages_to_nand(drained)?;
+        self.pad_to_program_unit()?;
+        self.debug_check("flush")
+    }
+
+    // 전원 차단: 쓰기 버퍼에 있던 데이터는 사라지고 NAND에 있던 (이전) 데이터만 남음
+    // 잃어버린 페이지 수 반환
+    pub fn power_loss(&mut self) -> usize {
+        let lost = self.write_buffer.as_mut().map_or(0, |b| b.clear());
+        self.stats.buffer_lost += lost as u64;
+        info!("[PowerLoss] {} buffered pages lost", lost);
+        lost
+    }
+
+    // 버퍼가 용량을 넘으면 정책 순서대로 내보냄
+    // PaddingAware는 Active Block의 현재 프로그램 단위가 다 찰 만큼 한 번에 내보냄
+    fn evict_overflow(&mut self) -> Result<(), String> {
+        while let Some(buffer) = self.write_buffer.as_ref() && buffer.is_overflowing() {
+            let count = match buffer.policy() {
+                EvictionPolicy::PaddingAware => match self.pages_to_unit_boundary() {
+                    0 => self.blocks[self.active_block_idx].cell_mode.bits_per_cell(),
+                    n => n,
+                },
+                EvictionPolicy::Lru | EvictionPolicy::Fifo => 1,
+            };
+
+            for _ in 0..count {
+                let Some((lba, data)) = self.write_buffer.as_mut().and_then(|b| b.pop_oldest()) else { break };
+                self.write_to_nand(lba, data)?;
             }
         }
         Ok(())
     }
 
-    // [수정 2] 내부 Write 함수: 실제 동작 담당 (NAND 카운트 증가 + 쓰기 + 매핑)
-    // GC는 이 함수를 호출하므로 user_write_cnt가 오르지 않음 (WAF 정확도 상승)
-    fn write_internal(&mut self, lba: usize, data: u32) -> Result<(), String> {
+    // 프로그램 단위 = 워드라인 하나의 페이지들 (셀당 비트 수만큼, one-shot program)
+    // 단위가 덜 찬 채로는 데이터가 안전하지 않으므로 flush/FUA 때 나머지를 채움
+    // PaddingAware면 버퍼에 남은 데이터로, 아니면(또는 버퍼가 비면) 더미 페이지로
+    fn pad_to_program_unit(&mut self) -> Result<(), String> {
+        while self.pages_to_unit_boundary() > 0 {
+            let filler = match self.write_buffer.as_mut() {
+                Some(buffer) if buffer.policy() == EvictionPolicy::PaddingAware => buffer.pop_oldest(),
+                _ => None,
+            };
+            match filler {
+                Some((lba, data)) => self.write_to_nand(lba, data)?,
+                None => self.program_padding(),
+            }
+        }
+        Ok(())
+    }
+
+    // Active Block에서 현재 프로그램 단위를 채우는 데 남은 페이지 수
+    fn pages_to_unit_boundary(&self) -> usize {
+        let block = &self.blocks[self.active_block_idx];
+        let unit = block.cell_mode.bits_per_cell();
+        let programmed = block.pages.iter().filter(|p| p.state != PageState::Free).count();
+        (unit - programmed % unit) % unit
+    }
+
+    // 더미 데이터로 한 페이지 프로그램 (바로 Invalid, GC 때 회수됨)
+    fn program_padding(&mut self) {
+        let offset = self.first_free_page(self.active_block_idx)
+            .expect("active block must have a free page below the unit boundary");
         let block = &mut self.blocks[self.active_block_idx];
+        block.program(offset, 0);
+        block.pages[offset].state = PageState::Invalid;
+        self.stats.record_nand_write(block.cell_mode);
+        self.stats.padding_pages += 1;
+        debug!("  -> Padding: Block {} Page {}", self.active_block_idx, offset);
+    }
 
-        // 빈 페이지 찾기
-        let mut target_page = None;
-        for page_offset in 0..PAGES_PER_BLOCK {
-            if block.read(page_offset).state == PageState::Free {
-                // [수정] 여기서만 NAND 카운트를 올리면 됨 (GC 상황도 포함되므로)
-                self.nand_write_cnt += 1; 
-                block.program(page_offset, data);
-                target_page = Some(page_offset);
-                break;
+    // 호스트 데이터 한 페이지를 NAND에 프로그램 (필요하면 GC, Active Block 교체)
+    fn write_to_nand(&mut self, lba: usize, data: u32) -> Result<(), String> {
+        self.write_pages_to_nand([(lba, data)])
+    }
+
+    // 호스트 데이터 여러 페이지를 차례로 NAND에 프로그램
+    // 빈 블록은 Active Block을 바꿀 때만 줄어드므로 GC 확인은 시작할 때와 블록 교체 다음 페이지에서만 함
+    // (한 페이지씩 write()를 반복한 것과 같은 시점에 GC가 일어남)
+    fn write_pages_to_nand(&mut self, pages: impl IntoIterator<Item = (usize, u32)>) -> Result<(), String> {
+        let mut check_free = true;
+        // Active Block에서 다음에 프로그램할 페이지 (모르면 None -> 다시 찾음)
+        let mut next_page = None;
+
+        for (lba, data) in pages {
+            // [방어 로직] 빈 블록이 1개 이하로 남으면 미리 GC를 돌려서 여유 공간 확보 (Reserved Block)
+            if check_free {
+                while self.count_free_blocks() == 0 {
+                    self.gc()?;
+                }
+                check_free = false;
+                // GC가 Active Block에 페이지를 옮겼거나 Active Block을 바꿨을 수 있음
+                next_page = None;
             }
-        }
 
-        if let Some(page_offset) = target_page {
-            let new_pba = PhysicalAddress {
-                block_id: self.blocks[self.active_block_idx].id,
-                page_offset,
+            let page_offset = match next_page.or_else(|| self.first_free_page(self.active_block_idx)) {
+                Some(offset) => offset,
+                None => {
+                    // Active Block이 꽉 찼으니 다음 빈 블록으로
+                    let next_idx = self.find_next_free_block(WriteStream::Host).ok_or("Fatal Error: SSD is Full!")?;
+                    debug!("Switching Active Block: {} -> {}", self.active_block_idx, next_idx);
+                    self.switch_active_block(next_idx);
+                    check_free = true;
+                    0
+                }
             };
+            // 다시 쓰면 trim 전의 페이지는 덮어쓰기로 무효화됨
+            self.pending_trims.remove(&lba);
+            self.program_page(page_offset, lba, data);
+
+            let pages_in_block = self.blocks[self.active_block_idx].pages.len();
+            next_page = (page_offset + 1 < pages_in_block).then_some(page_offset + 1);
+        }
+        Ok(())
+    }
+
+    // sink 에러는 호스트 쓰기 결과와 무관 (recorder가 보관, metrics_error / detach_metrics로 확인)
+    fn sample_metrics(&mut self) {
+        if self.metrics.as_ref().is_some_and(|m| m.is_due(self.stats.host_writes)) {
+            let free_blocks = self.count_free_blocks();
+            if let Some(metrics) = self.metrics.as_mut() {
+                metrics.sample(&self.stats, &self.blocks, free_blocks);
+            }
+        }
+    }
+
+    // 읽기: LBA가 가리키는 페이지의 데이터를 반환 (한 번도 안 쓴 LBA면 None)
+    pub fn read(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        Ok(self.read_range(lba, 1)?[0])
+    }
+
+    // 범위 읽기: start_lba부터 len개의 LBA를 요청 하나로
+    pub fn read_range(&mut self, start_lba: usize, len: usize) -> Result<Vec<Option<u32>>, String> {
+        self.mapping_table.check_range(start_lba, len)?;
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+        self.stats.record_read_request(len);
+
+        let mut result = Vec::with_capacity(len);
+        for lba in start_lba..start_lba + len {
+            self.stats.host_reads += 1;
+            if let Some(data) = self.write_buffer.as_mut().and_then(|b| b.lookup(lba)) {
+                self.stats.buffer_read_hits += 1;
+                result.push(Some(data));
+                continue;
+            }
+            if self.pending_trims.contains(&lba) {
+                result.push(None);
+                continue;
+            }
+            let data = self.mapping_table.get(lba).map(|pba| {
+                let block = &self.blocks[pba.block_id as usize];
+                (block.read(pba.page_offset).content, block.cell_mode)
+            });
+            if let Some((_, mode)) = data {
+                self.stats.record_nand_read(mode);
+            }
+            result.push(data.map(|(content, _)| content));
+        }
+        self.debug_check("read")?;
+        Ok(result)
+    }
+
+    // 부분 쓰기의 read-modify-write용 읽기: 호스트 읽기가 아니라 쓰기 때문에 생긴 추가 NAND 읽기로 기록
+    pub fn read_for_update(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        if lba >= self.mapping_table.entries().len() {
+            return Err(format!("LBA {} is out of range!", lba));
+        }
 
-            // 매핑 테이블 갱신 및 Old Data 무효화
-            if let Some(old_pba) = self.mapping_table.update(lba, new_pba) {
-                let old_blk_idx = old_pba.block_id as usize;
-                
-                // [안전 장치] 혹시 모를 인덱스 에러 방지
-                if old_blk_idx < self.blocks.len() {
-                    // Block에 invalidate 메서드가 있다고 가정 (직접 접근도 가능)
-                    self.blocks[old_blk_idx].pages[old_pba.page_offset].state = PageState::Invalid;
-                    debug!("  -> Invalidated Old Data: Block {} Page {}", old_blk_idx, old_pba.page_offset);
+        // 버퍼에 있는 최신 데이터는 NAND 읽기 없이
+        if let Some(data) = self.write_buffer.as_mut().and_then(|b| b.lookup(lba)) {
+            return Ok(Some(data));
+        }
+        if self.pending_trims.contains(&lba) {
+            return Ok(None);
+        }
+        let data = self.mapping_table.get(lba).map(|pba| {
+            let block = &self.blocks[pba.block_id as usize];
+            (block.read(pba.page_offset).content, block.cell_mode)
+        });
+        if let Some((_, mode)) = data {
+            self.stats.record_nand_read(mode);
+            self.stats.rmw_reads += 1;
+        }
+        Ok(data.map(|(content, _)| content))
+    }
+
+    // Trim (Unmap): 파일 삭제 등으로 더 이상 필요 없는 LBA를 알려줌 -> 해당 페이지는 바로 Invalid
+    pub fn trim(&mut self, lba: usize) -> Result<(), String> {
+        self.trim_range(lba, 1)
+    }
+
+    // 범위 Trim: start_lba부터 len개의 LBA를 요청 하나로
+    pub fn trim_range(&mut self, start_lba: usize, len: usize) -> Result<(), String> {
+        self.mapping_table.check_range(start_lba, len)?;
+        if len == 0 {
+            return Ok(());
+        }
+        self.stats.record_trim_request(len);
+
+        for lba in start_lba..start_lba + len {
+            self.stats.host_trims += 1;
+            if let Some(buffer) = self.write_buffer.as_mut() && buffer.remove(lba).is_some() {
+                self.stats.buffer_absorbed += 1;
+            }
+            match self.op_mode {
+                OpMode::Dynamic => self.unmap(lba),
+                OpMode::Static => {
+                    if self.mapping_table.get(lba).is_some() {
+                        self.pending_trims.insert(lba);
+                    }
                 }
             }
-            Ok(())
-        } else {
+        }
+        self.debug_check("trim")
+    }
+
+    pub fn num_lbas(&self) -> usize {
+        self.mapping_table.entries().len()
+    }
+
+    // [수정 2] 내부 Write 함수: 실제 동작 담당 (NAND 카운트 증가 + 쓰기 + 매핑)
+    // GC는 이 함수를 호출하므로 host_writes가 오르지 않음 (WAF 정확도 상승)
+    fn write_internal(&mut self, lba: usize, data: u32) -> Result<(), String> {
+        match self.first_free_page(self.active_block_idx) {
+            Some(page_offset) => {
+                self.program_page(page_offset, lba, data);
+                Ok(())
+            }
             // 현재 Active Block이 꽉 참 -> 상위 함수(write)나 GC가 처리하도록 에러 반환
-            Err("Active block is full".to_string())
+            None => Err("Active block is full".to_string()),
         }
     }
-    
+
+    // 페이지는 앞에서부터 순서대로 프로그램되므로 첫 빈 페이지가 다음 프로그램 위치
+    fn first_free_page(&self, block_idx: usize) -> Option<usize> {
+        self.blocks[block_idx].pages.iter().position(|p| p.state == PageState::Free)
+    }
+
+    // Active Block의 page_offset에 프로그램하고 매핑 갱신
+    fn program_page(&mut self, page_offset: usize, lba: usize, data: u32) {
+        // [수정] 여기서만 NAND 카운트를 올리면 됨 (GC 상황도 포함되므로)
+        let block = &mut self.blocks[self.active_block_idx];
+        self.stats.record_nand_write(block.cell_mode);
+        block.program(page_offset, data);
+        let new_pba = PhysicalAddress { block_id: block.id, page_offset };
+
+        // 매핑 테이블 갱신 및 Old Data 무효화
+        if let Some(old_pba) = self.mapping_table.update(lba, new_pba) {
+            let old_blk_idx = old_pba.block_id as usize;
+            self.blocks[old_blk_idx].pages[old_pba.page_offset].state = PageState::Invalid;
+            self.victims.invalidate(old_blk_idx);
+            debug!("  -> Invalidated Old Data: Block {} Page {}", old_blk_idx, old_pba.page_offset);
+        }
+    }
+
+    // GC 한 번 = victim 하나를 완전히 정리할 때까지 gc_step()을 반복 (Stop-the-world)
     pub fn gc(&mut self) -> Result<(), String> {
         info!("\n[GC] Started! (Free blocks: {})", self.count_free_blocks());
 
-
-        let mut victim_idx = None;
-        let mut min_valid_count = usize::MAX;
-    
-        for (i, block) in self.blocks.iter().enumerate() {
-            if i == self.active_block_idx || block.state == BlockState::Free {
-                continue;
-            }
-    
-            let valid_cnt = block.count_valid_pages();
-            if valid_cnt < min_valid_count {
-                min_valid_count = valid_cnt;
-                victim_idx = Some(i);
+        loop {
+            if let GcStep::Erased { victim_idx } = self.gc_step()? {
+                info!("[GC] Erased Block {}. GC Finished.\n", victim_idx);
+                return Ok(());
             }
         }
-    
-        let victim_idx = match victim_idx {
-            Some(idx) => idx,
+    }
+
+    // 점진적 GC: 유효 페이지 1개 복사 또는 victim erase 1회만 수행하고 돌아옴
+    // 진행 중인 작업이 없으면 새 victim(Greedy)을 고르고 시작
+    pub fn gc_step(&mut self) -> Result<GcStep, String> {
+        let step = self.run_gc_step()?;
+        self.gc_steps += 1;
+        self.debug_check("gc step")?;
+        Ok(step)
+    }
+
+    fn run_gc_step(&mut self) -> Result<GcStep, String> {
+        let mut job = match self.gc_job {
+            Some(job) => job,
             None => {
-                return Err("Failed to find victim block!".to_string());
+                let victim_idx = self.select_victim()?;
+                let free_blocks = self.count_free_blocks();
+                self.stats.record_gc_start(self.blocks[victim_idx].count_valid_pages(), free_blocks);
+                GcJob { victim_idx, next_page: 0 }
             }
         };
-    
-        debug!("[GC] Selected Victim: Block {} (Valid Pages: {})", victim_idx, min_valid_count);
-    
-        // 2. 유효 페이지 대피 (Migration)
-        for page_idx in 0..PAGES_PER_BLOCK {
-            let is_valid = self.blocks[victim_idx].pages[page_idx].state == PageState::Valid;
-    
-            if is_valid {
-                let data = self.blocks[victim_idx].pages[page_idx].content;
-                let lba_opt = self.find_lba_by_pba(victim_idx as u32, page_idx);
-    
-                if let Some(target_lba) = lba_opt {
-                    // ✅ 루프: Active Block이 또 꽉 찰 때까지 계속 전환
-                    loop {
-                        match self.write_internal(target_lba, data) {
-                            Ok(()) => break,  // 성공! 루프 탈출
-                            Err(_) => {
-                                // Active Block 꽉 찼음
-                                match self.find_next_free_block() {
-                                    Some(next_idx) => {
-                                        debug!("[GC] Switching block during migration: {} → {}", 
-                                                 self.active_block_idx, next_idx);
-                                        self.active_block_idx = next_idx;
-                                        // 루프 재시도
-                                    }
-                                    None => {
-                                        // 정말 더 이상 공간 없음
-                                        return Err(
-                                            format!("Fatal: No space left during GC migration at page {}", page_idx)
-                                        );
-                                    }
-                                }
+        let victim_idx = job.victim_idx;
+
+        // 1. 다음 유효 페이지 찾기 (그 사이 호스트 쓰기로 무효화된 페이지는 건너뜀)
+        let victim_pages = self.blocks[victim_idx].pages.len();
+        while job.next_page < victim_pages
+            && self.blocks[victim_idx].pages[job.next_page].state != PageState::Valid
+        {
+            job.next_page += 1;
+        }
+
+        // 2. 더 옮길 페이지가 없으면 erase로 마무리
+        if job.next_page == victim_pages {
+            let victim = &mut self.blocks[victim_idx];
+            victim.erase();
+            self.stats.record_nand_erase(victim.cell_mode);
+            self.victims.remove(victim_idx);
+            // 보장 P/E 사이클을 다 쓴 블록은 배드 블록으로 퇴역 (빈 블록 풀에 돌려놓지 않음)
+            if victim.is_worn_out() {
+                victim.is_bad = true;
+                warn!("[BadBlock] Block {} retired after {} erases ({:?} endurance)", victim_idx, victim.erase_count, victim.cell_mode);
+            } else {
+                self.free_pool.insert(&self.blocks[victim_idx]);
+            }
+            self.gc_job = None;
+            return Ok(GcStep::Erased { victim_idx });
+        }
+
+        // 3. 유효 페이지 대피 (Migration)
+        let page_idx = job.next_page;
+        let data = self.blocks[victim_idx].pages[page_idx].content;
+        if let Some(target_lba) = self.find_lba_by_pba(victim_idx as u32, page_idx) {
+            // ✅ 루프: Active Block이 또 꽉 찰 때까지 계속 전환
+            self.stats.record_nand_read(self.blocks[victim_idx].cell_mode);
+            loop {
+                match self.write_internal(target_lba, data) {
+                    Ok(()) => {
+                        // 성공! 루프 탈출
+                        self.stats.gc_migrated_pages += 1;
+                        break;
+                    }
+                    Err(_) => {
+                        // Active Block 꽉 찼음
+                        match self.find_next_free_block(WriteStream::Gc) {
+                            Some(next_idx) => {
+                                debug!("[GC] Switching block during migration: {} → {}", 
+                                         self.active_block_idx, next_idx);
+                                self.switch_active_block(next_idx);
+                                // 루프 재시도
+                            }
+                            None => {
+                                // 정말 더 이상 공간 없음 (작업은 남겨두어 다음에 이어서 진행)
+                                self.gc_job = Some(job);
+                                return Err(
+                                    format!("Fatal: No space left during GC migration at page {}", page_idx)
+                                );
                             }
                         }
                     }
                 }
             }
         }
-    
-        self.blocks[victim_idx].erase();
-        info!("[GC] Erased Block {}. GC Finished.\n", victim_idx);
-        Ok(())
+
+        job.next_page += 1;
+        self.gc_job = Some(job);
+        Ok(GcStep::Copied { victim_idx, page_offset: page_idx })
+    }
+
+    // victim_policy에 따라 victim 선정 (Greedy: 유효 페이지가 가장 적은 블록)
+    fn select_victim(&mut self) -> Result<usize, String> {
+        // 무효 페이지가 가장 많은 블록 (셀 모드가 같으면 유효 페이지가 가장 적은 블록과 같음), 같으면 번호가 작은 블록
+        // 유효 페이지를 옮길 빈 페이지가 모자란 블록은 제외 (크기가 다른 블록이 섞인 장치)
+        let free_pages = self.count_free_pages();
+        let fits = |ssd: &Self, i: usize| ssd.blocks[i].count_valid_pages() <= free_pages;
+
+        let victim_idx = match self.victim_policy {
+            // 색인을 무효 페이지가 많은 순으로 훑으므로 처음 들어맞는 블록이 victim
+            VictimPolicy::Greedy => self.victims.by_invalid_desc().find(|&i| fits(self, i)),
+            VictimPolicy::DChoices { d } => {
+                let all: Vec<usize> = self.victims.candidates().collect();
+                let candidates: Vec<usize> = if all.len() <= d {
+                    all
+                } else {
+                    // 중복 없이 d개 (같은 블록을 두 번 뽑으면 실제로 비교하는 후보가 d개보다 적어짐)
+                    sample(&mut self.rng, all.len(), d.max(1)).into_iter().map(|i| all[i]).collect()
+                };
+
+                let mut victim_idx = None;
+                let mut max_invalid_count = 0;
+                for i in candidates {
+                    let invalid_cnt = self.victims.invalid_pages(i).unwrap_or(0);
+                    if (victim_idx.is_none() || invalid_cnt > max_invalid_count) && fits(self, i) {
+                        max_invalid_count = invalid_cnt;
+                        victim_idx = Some(i);
+                    }
+                }
+                // 뽑은 후보가 모두 옮길 자리가 모자라면 Greedy 순서로
+                victim_idx.or_else(|| self.victims.by_invalid_desc().find(|&i| fits(self, i)))
+            }
+        };
+
+        match victim_idx {
+            Some(idx) => {
+                debug!("[GC] Selected Victim: Block {} (Valid Pages: {})", idx, self.blocks[idx].count_valid_pages());
+                Ok(idx)
+            }
+            None => Err("Failed to find victim block!".to_string()),
+        }
+    }
+
+    // 무효 페이지를 가진 (= GC로 공간을 회수할 수 있는) 블록이 있는지
+    pub fn has_reclaimable_block(&self) -> bool {
+        self.victims.has_reclaimable()
+    }
+
+    // 지금까지 수행한 GC step 수 (쓰기 도중 FTL이 스스로 돌린 GC 포함)
+    pub fn gc_steps(&self) -> u64 {
+        self.gc_steps
+    }
+
+    pub fn gc_in_progress(&self) -> bool {
+        self.gc_job.is_some()
     }
     
+    // 지금 바로 프로그램할 수 있는 페이지 수 (Active Block의 남은 페이지 + 빈 블록)
+    fn count_free_pages(&self) -> usize {
+        let active = &self.blocks[self.active_block_idx];
+        active.pages.iter().filter(|p| p.state == PageState::Free).count() + self.free_pool.free_pages()
+    }
+
     // [보조 함수] 빈 블록 개수 세기 (GC 트리거 조건용)
-    fn count_free_blocks(&self) -> usize {
-        self.blocks.iter().filter(|b| b.state == BlockState::Free).count()
+    // 풀의 블록 + 아직 한 페이지도 안 쓴 Active Block
+    pub fn count_free_blocks(&self) -> usize {
+        let active = &self.blocks[self.active_block_idx];
+        self.free_pool.len() + usize::from(active.state == BlockState::Free && !active.is_bad)
     }
 
-    fn find_next_free_block(&self) -> Option<usize> {
-        for (i, block) in self.blocks.iter().enumerate() {
-            // 현재 쓰고 있는 블록은 제외하고 찾기
-            if block.state == BlockState::Free && i != self.active_block_idx {
-                return Some(i);
-            }
+
+    // 다 쓴 Active Block은 GC 후보가 됨 (배드 블록으로 바뀐 경우는 제외)
+    fn switch_active_block(&mut self, next_idx: usize) {
+        let old_idx = std::mem::replace(&mut self.active_block_idx, next_idx);
+        let old = &self.blocks[old_idx];
+        if old.state != BlockState::Free && !old.is_bad {
+            let invalid_pages = old.pages.iter().filter(|p| p.state != PageState::Valid).count();
+            self.victims.insert(old_idx, invalid_pages);
         }
-        None
+    }
+
+    // 다음 Active Block을 할당 정책에 따라 빈 블록 풀에서 꺼냄 (현재 Active Block은 풀에 없음)
+    fn find_next_free_block(&mut self, stream: WriteStream) -> Option<usize> {
+        self.free_pool.allocate(self.alloc_policy, stream, &mut self.rng)
     }
     
-    // MappingTable 구현에 따라 entries 접근 방식이 다를 수 있음 (여기선 Vec 직접 접근 가정)
+    // 물리 페이지의 LBA (매핑 테이블의 역방향 색인으로 O(1))
     fn find_lba_by_pba(&self, block_id: u32, page_offset: usize) -> Option<usize> {
-        // MappingTable의 entries 필드가 pub이어야 합니다.
-        // 만약 entries() 메서드를 쓰신다면 그대로 두셔도 됩니다.
-        for (lba, entry) in self.mapping_table.entries().iter().enumerate() {
-            if let Some(pba) = entry {
-                if pba.block_id == block_id && pba.page_offset == page_offset {
-                    return Some(lba);
-                }
-            }
+        self.mapping_table.lba_at(PhysicalAddress { block_id, page_offset })
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    // warm-up이 끝난 뒤 측정 구간만 보고 싶을 때 (erase 횟수 등 블록 상태는 그대로)
+    pub fn reset_stats(&mut self) {
+        self.stats.reset();
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.reset_baseline();
+        }
+    }
+
+    pub fn attach_metrics(&mut self, mut recorder: MetricsRecorder) {
+        recorder.start_at(self.stats.host_writes);
+        self.metrics = Some(recorder);
+    }
+
+    // 메트릭 sink에 기록하다 처음 난 에러
+    pub fn metrics_error(&self) -> Option<&str> {
+        self.metrics.as_ref().and_then(|m| m.error())
+    }
+
+    // 샘플러를 떼어내면서 sink에 남은 내용을 flush (기록 중 난 에러도 여기서 보고)
+    pub fn detach_metrics(&mut self) -> Result<(), String> {
+        match self.metrics.take() {
+            Some(mut recorder) => recorder.flush(),
+            None => Ok(()),
         }
-        None
+    }
+
+    pub fn erase_distribution(&self) -> EraseDistribution {
+        EraseDistribution::from_blocks(&self.blocks)
     }
 
     pub fn get_waf(&self) -> f64 {
-        if self.user_write_cnt == 0 { return 0.0 }
-        self.nand_write_cnt as f64 / self.user_write_cnt as f64 
+        self.stats.waf()
+    }
+
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    pub fn mapping_table(&self) -> &MappingTable {
+        &self.mapping_table
+    }
+
+    // 전체 블록의 유효 페이지 비율 / erase 횟수를 한 화면에 요약
+    pub fn print_heatmap(&self) {
+        print!("{}", terminal_heatmap(&self.blocks, 64));
     }
 
     pub fn print_blocks(&self) {
@@ -207,16 +874,76 @@ impl SSD {
         println!("===============================")
     }
     // erase의 평균과 
+    // 배드 블록은 쓰지 않으므로 통계에서 제외
     pub fn compute_wear_metrics(&self) -> WearStats {
         let mut min = u32::MAX;
         let mut max = 0;
-        let sum = self.blocks.iter().fold(0, |acc, x| {
+        let good_blocks = self.blocks.iter().filter(|b| !b.is_bad);
+        let sum = good_blocks.clone().fold(0, |acc, x| {
             let cnt = x.erase_count;
             if cnt < min {min = cnt;}
             if cnt > max {max = cnt;}
             acc + x.erase_count}
         );
-        WearStats { min: min, max: max, avg: sum as f64/ self.blocks.len() as f64, gap: max - min }
+        WearStats { min, max, avg: sum as f64/ good_blocks.count() as f64, gap: max - min }
+    }
+
+}
+
+impl BlockDevice for SSD {
+    fn read(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        SSD::read(self, lba)
+    }
+
+    fn write(&mut self, lba: usize, data: u32) -> Result<(), String> {
+        SSD::write(self, lba, data)
+    }
+
+    fn read_for_update(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        SSD::read_for_update(self, lba)
+    }
+
+    fn trim(&mut self, lba: usize) -> Result<(), String> {
+        SSD::trim(self, lba)
+    }
+
+    fn write_range(&mut self, start_lba: usize, data: &[u32]) -> Result<(), String> {
+        SSD::write_range(self, start_lba, data)
+    }
+
+    fn read_range(&mut self, start_lba: usize, len: usize) -> Result<Vec<Option<u32>>, String> {
+        SSD::read_range(self, start_lba, len)
     }
 
-}
\ No newline at end of file
+    fn trim_range(&mut self, start_lba: usize, len: usize) -> Result<(), String> {
+        SSD::trim_range(self, start_lba, len)
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        SSD::flush(self)
+    }
+
+    fn num_lbas(&self) -> usize {
+        SSD::num_lbas(self)
+    }
+
+    fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    fn reset_stats(&mut self) {
+        SSD::reset_stats(self)
+    }
+
+    fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        SSD::check_invariants(self)
+    }
+
+    fn save_snapshot(&self, path: &str) -> Result<(), String> {
+        SSD::save_snapshot(self, path)
+    }
+}
@@ -0,0 +1,703 @@
+// SLC 쓰기 캐시를 가진 FTL (소비자용 TLC/QLC SSD 구조)
+// 호스트 쓰기는 먼저 빠른 SLC 블록에 받고, 다 찬 SLC 블록은 유휴 시간에 고밀도(TLC/QLC) 블록으로 접어(folding) 넣습니다.
+// 캐시가 가득 찬 상태로 쓰기가 계속 들어오면 고밀도 블록에 바로 써야 하므로 지연 시간이 급격히 늘어납니다 (write cliff).
+// 셀 모드별 지연 시간으로 자체 시계를 돌리며, set_arrival로 받은 도착 시각 사이의 유휴 시간에 접기를 진행합니다.
+use super::block::*;
+use super::cell::CellMode;
+use super::mapping::*;
+use super::page::*;
+use super::stats::*;
+use super::visualize::terminal_heatmap;
+use super::device::BlockDevice;
+use super::invariants;
+use super::alloc::FreeBlockPool;
+use super::gc::VictimIndex;
+use super::metrics::MetricsRecorder;
+use log::{info, debug, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+
+// SLC 캐시 크기를 정하는 방식
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlcCachePolicy {
+    // 앞쪽 blocks개 블록을 항상 SLC로 사용 (사용자 용량과 별도)
+    Static { blocks: usize },
+    // 비어 있는 사용자 용량의 free_space_pct%만큼을 SLC로 사용 (데이터가 찰수록 캐시가 줄어듦)
+    Dynamic { free_space_pct: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SlcCacheConfig {
+    pub policy: SlcCachePolicy,
+    // 캐시가 아닌 블록의 셀 모드
+    pub dense_mode: CellMode,
+}
+
+impl Default for SlcCacheConfig {
+    fn default() -> Self {
+        SlcCacheConfig {
+            policy: SlcCachePolicy::Dynamic { free_space_pct: 25.0 },
+            dense_mode: CellMode::Tlc,
+        }
+    }
+}
+
+pub struct SSD {
+    blocks: Vec<Block>,
+    mapping_table: MappingTable,
+    stats: Stats,
+    config: SlcCacheConfig,
+    // 호스트 쓰기를 받는 SLC 블록 (캐시 블록을 더 열 수 없으면 None)
+    slc_active: Option<usize>,
+    // 캐시 초과 쓰기 / 접기 / GC 이동을 받는 고밀도 블록
+    dense_active: usize,
+    // 다 찬 SLC 블록 (먼저 찬 블록부터 접음)
+    fold_queue: VecDeque<usize>,
+    // fold_queue 맨 앞 블록에서 다음에 확인할 페이지
+    fold_next_page: usize,
+    // NAND가 마지막 작업을 끝내는 시각 (us)
+    now_us: u64,
+    next_arrival_us: u64,
+    // 디버그용: 매 연산 뒤 check_invariants() 실행
+    debug_checks: bool,
+    // 고밀도 데이터나 동적 캐시로 열 수 있는 빈 블록 (정적 캐시 블록, Active Block 제외)
+    free_pool: FreeBlockPool,
+    // 고밀도 GC 후보 (고밀도 Active Block 제외)의 무효 페이지 수별 색인
+    victims: VictimIndex,
+    // 수명을 다해 퇴역한 블록 수 (동적 캐시 크기 계산용, 스냅샷에서는 블록 상태로 다시 셈)
+    bad_blocks: usize,
+    // 주기적 메트릭 샘플러 (attach_metrics로 붙임)
+    metrics: Option<MetricsRecorder>,
+}
+
+// 장치 상태 전체를 저장/복원하기 위한 스냅샷
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SsdSnapshot {
+    blocks: Vec<Block>,
+    mapping_table: MappingTable,
+    #[serde(with = "super::stats::with_lba_writes")]
+    stats: Stats,
+    config: SlcCacheConfig,
+    slc_active: Option<usize>,
+    dense_active: usize,
+    fold_queue: VecDeque<usize>,
+    fold_next_page: usize,
+    now_us: u64,
+}
+
+impl SSD {
+    pub fn new(num_blocks: usize, num_lbas: usize, config: SlcCacheConfig) -> Result<Self, String> {
+        if config.dense_mode == CellMode::Slc {
+            return Err("SLC cache needs a multi-bit dense mode (mlc, tlc or qlc)".to_string());
+        }
+        let static_blocks = match config.policy {
+            SlcCachePolicy::Static { blocks } => blocks,
+            SlcCachePolicy::Dynamic { .. } => 0,
+        };
+        // 고밀도 블록 중 1개는 GC용 여유분
+        if static_blocks + 2 > num_blocks {
+            return Err(format!("Too many static SLC blocks: {} of {}", static_blocks, num_blocks));
+        }
+        let user_pages = (num_blocks - static_blocks - 1) * config.dense_mode.pages_per_block();
+        if num_lbas > user_pages {
+            return Err(format!("{} LBAs do not fit in {} dense pages", num_lbas, user_pages));
+        }
+
+        let blocks: Vec<Block> = (0..num_blocks)
+            .map(|i| {
+                let mode = if i < static_blocks { CellMode::Slc } else { config.dense_mode };
+                Block::with_mode(i as u32, mode)
+            })
+            .collect();
+        // 처음 고밀도 Active Block = 정적 캐시 바로 다음 블록
+        let free_pool = FreeBlockPool::from_blocks_where(&blocks, 0, |i, b| is_free_block(&config, static_blocks, None, i, b));
+
+        Ok(SSD {
+            blocks,
+            mapping_table: MappingTable::new(num_lbas),
+            stats: Stats::new(num_lbas),
+            config,
+            slc_active: None,
+            dense_active: static_blocks,
+            fold_queue: VecDeque::new(),
+            fold_next_page: 0,
+            now_us: 0,
+            next_arrival_us: 0,
+            debug_checks: false,
+            free_pool,
+            victims: VictimIndex::new(num_blocks),
+            bad_blocks: 0,
+            metrics: None,
+        })
+    }
+
+    pub fn snapshot(&self) -> SsdSnapshot {
+        SsdSnapshot {
+            blocks: self.blocks.clone(),
+            mapping_table: self.mapping_table.clone(),
+            stats: self.stats.clone(),
+            config: self.config,
+            slc_active: self.slc_active,
+            dense_active: self.dense_active,
+            fold_queue: self.fold_queue.clone(),
+            fold_next_page: self.fold_next_page,
+            now_us: self.now_us,
+        }
+    }
+
+    pub fn from_snapshot(snapshot: SsdSnapshot) -> Self {
+        let free_pool = FreeBlockPool::from_blocks_where(&snapshot.blocks, 0, |i, b| {
+            is_free_block(&snapshot.config, snapshot.dense_active, snapshot.slc_active, i, b)
+        });
+        let victims = VictimIndex::from_blocks_where(&snapshot.blocks, |i, b| {
+            is_dense_victim(&snapshot.config, snapshot.dense_active, i, b)
+        });
+        let bad_blocks = snapshot.blocks.iter().filter(|b| b.is_bad).count();
+        SSD {
+            blocks: snapshot.blocks,
+            mapping_table: snapshot.mapping_table,
+            stats: snapshot.stats,
+            config: snapshot.config,
+            slc_active: snapshot.slc_active,
+            dense_active: snapshot.dense_active,
+            fold_queue: snapshot.fold_queue,
+            fold_next_page: snapshot.fold_next_page,
+            now_us: snapshot.now_us,
+            next_arrival_us: snapshot.now_us,
+            debug_checks: false,
+            free_pool,
+            victims,
+            bad_blocks,
+            metrics: None,
+        }
+    }
+
+    pub fn save_snapshot(&self, path: &str) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+        serde_json::to_writer(BufWriter::new(file), &self.snapshot())
+            .map_err(|e| format!("Failed to write snapshot {}: {}", path, e))
+    }
+
+    pub fn load_snapshot(path: &str) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+        let snapshot: SsdSnapshot = serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| format!("Failed to parse snapshot {}: {}", path, e))?;
+        Ok(Self::from_snapshot(snapshot))
+    }
+
+    // 켜면 호스트 연산이 끝날 때마다 check_invariants()를 돌려 위반 시 에러 반환 (느림)
+    pub fn set_debug_checks(&mut self, enabled: bool) {
+        self.debug_checks = enabled;
+    }
+
+    pub fn check_invariants(&self) -> Result<(), String> {
+        invariants::check(&self.blocks, &self.mapping_table, self.dense_active, &self.stats)?;
+
+        if self.blocks[self.dense_active].cell_mode != self.config.dense_mode {
+            return Err(format!("Dense active block {} is {:?}", self.dense_active, self.blocks[self.dense_active].cell_mode));
+        }
+        if let Some(idx) = self.slc_active
+            && (self.blocks[idx].cell_mode != CellMode::Slc || self.blocks[idx].state == BlockState::Full)
+        {
+            return Err(format!("SLC active block {} is {:?} / {:?}", idx, self.blocks[idx].cell_mode, self.blocks[idx].state));
+        }
+        for &idx in &self.fold_queue {
+            let block = &self.blocks[idx];
+            if block.cell_mode != CellMode::Slc || block.state != BlockState::Full {
+                return Err(format!("Fold queue block {} is {:?} / {:?}", idx, block.cell_mode, block.state));
+            }
+        }
+        // 접기/SLC 쓰기 대상이 아닌 SLC 블록에는 데이터가 없어야 함 (어디서도 회수되지 않으므로)
+        for (idx, block) in self.blocks.iter().enumerate() {
+            let tracked = self.slc_active == Some(idx) || self.fold_queue.contains(&idx);
+            if block.cell_mode == CellMode::Slc && block.state != BlockState::Free && !tracked {
+                return Err(format!("SLC Block {} holds data but is neither active nor queued for folding", idx));
+            }
+            if self.is_static_cache(idx) && block.cell_mode != CellMode::Slc {
+                return Err(format!("Static cache Block {} is {:?}", idx, block.cell_mode));
+            }
+        }
+        let bad_blocks = self.blocks.iter().filter(|b| b.is_bad).count();
+        if bad_blocks != self.bad_blocks {
+            return Err(format!("{} bad blocks counted, but {} blocks are bad", self.bad_blocks, bad_blocks));
+        }
+        self.free_pool.check_where(&self.blocks, |i, b| {
+            is_free_block(&self.config, self.dense_active, self.slc_active, i, b)
+        })?;
+        self.victims.check_where(&self.blocks, |i, b| is_dense_victim(&self.config, self.dense_active, i, b))
+    }
+
+    fn debug_check(&self, op: &str) -> Result<(), String> {
+        if !self.debug_checks {
+            return Ok(());
+        }
+        self.check_invariants().map_err(|e| format!("Invariant violated after {}: {}", op, e))
+    }
+
+    pub fn write(&mut self, lba: usize, data: u32) -> Result<(), String> {
+        self.write_range(lba, std::slice::from_ref(&data))
+    }
+
+    // 범위 쓰기: 요청 하나로 처리 (유휴 시간 접기는 요청 시작 전에 한 번)
+    pub fn write_range(&mut self, start_lba: usize, data: &[u32]) -> Result<(), String> {
+        self.mapping_table.check_range(start_lba, data.len())?;
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.stats.record_write_request(data.len());
+        self.begin_request()?;
+
+        for (lba, data) in (start_lba..).zip(data.iter().copied()) {
+            self.stats.record_host_write(lba);
+            let cost = match self.write_slc(lba, data) {
+                Some(cost) => {
+                    self.stats.slc_writes += 1;
+                    cost
+                }
+                None => {
+                    // 캐시가 가득 참: 공간을 확보한 뒤 고밀도 블록에 직접 씀 (write cliff)
+                    while self.count_free_dense_blocks() == 0 {
+                        self.reclaim()?;
+                    }
+                    self.stats.slc_overflows += 1;
+                    self.write_dense(lba, data)?
+                }
+            };
+            self.now_us += cost;
+        }
+        self.sample_metrics();
+        self.debug_check("write")
+    }
+
+    // sink 에러는 호스트 쓰기 결과와 무관 (recorder가 보관, metrics_error / detach_metrics로 확인)
+    fn sample_metrics(&mut self) {
+        if self.metrics.as_ref().is_some_and(|m| m.is_due(self.stats.host_writes)) {
+            let free_blocks = self.count_free_dense_blocks();
+            if let Some(metrics) = self.metrics.as_mut() {
+                metrics.sample(&self.stats, &self.blocks, free_blocks);
+            }
+        }
+    }
+
+    pub fn read(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        Ok(self.read_range(lba, 1)?[0])
+    }
+
+    pub fn read_range(&mut self, start_lba: usize, len: usize) -> Result<Vec<Option<u32>>, String> {
+        self.mapping_table.check_range(start_lba, len)?;
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+        self.stats.record_read_request(len);
+        self.begin_request()?;
+
+        let mut result = Vec::with_capacity(len);
+        for lba in start_lba..start_lba + len {
+            self.stats.host_reads += 1;
+            let data = self.mapping_table.get(lba).map(|pba| {
+                let block = &self.blocks[pba.block_id as usize];
+                (block.read(pba.page_offset).content, block.cell_mode)
+            });
+            if let Some((_, mode)) = data {
+                self.stats.record_nand_read(mode);
+                self.now_us += mode.timing().read_us;
+            }
+            result.push(data.map(|(content, _)| content));
+        }
+        self.debug_check("read")?;
+        Ok(result)
+    }
+
+    // Trim: 매핑만 지우므로 NAND 동작 없음
+    pub fn trim(&mut self, lba: usize) -> Result<(), String> {
+        self.trim_range(lba, 1)
+    }
+
+    pub fn trim_range(&mut self, start_lba: usize, len: usize) -> Result<(), String> {
+        self.mapping_table.check_range(start_lba, len)?;
+        if len == 0 {
+            return Ok(());
+        }
+        self.stats.record_trim_request(len);
+        self.begin_request()?;
+
+        for lba in start_lba..start_lba + len {
+            self.stats.host_trims += 1;
+            if let Some(old_pba) = self.mapping_table.unmap(lba) {
+                self.blocks[old_pba.block_id as usize].pages[old_pba.page_offset].state = PageState::Invalid;
+                self.victims.invalidate(old_pba.block_id as usize);
+                debug!("  -> Trimmed LBA {}: Block {} Page {}", lba, old_pba.block_id, old_pba.page_offset);
+            }
+        }
+        self.debug_check("trim")
+    }
+
+    pub fn num_lbas(&self) -> usize {
+        self.mapping_table.entries().len()
+    }
+
+    // 요청 도착 전까지의 유휴 시간에 접기를 진행하고, 요청 시작 시각으로 시계를 맞춤
+    fn begin_request(&mut self) -> Result<(), String> {
+        let arrival_us = self.next_arrival_us;
+        while self.now_us < arrival_us && self.can_fold_in_background() {
+            let cost = self.fold_step()?;
+            self.now_us += cost;
+        }
+        self.now_us = self.now_us.max(arrival_us);
+        Ok(())
+    }
+
+    // 백그라운드 접기는 고밀도 여유 블록(GC용 1개)을 건드리지 않는 범위에서만
+    fn can_fold_in_background(&self) -> bool {
+        !self.fold_queue.is_empty()
+            && (self.has_free_page(self.dense_active) || self.count_free_dense_blocks() > 1)
+    }
+
+    // 유휴 시간 없이 지금 바로 SLC 캐시를 모두 비움 (걸린 시간은 시계에 반영)
+    pub fn fold_all(&mut self) -> Result<(), String> {
+        while !self.fold_queue.is_empty() {
+            let cost = self.fold_step()?;
+            self.now_us += cost;
+        }
+        Ok(())
+    }
+
+    // 접기 한 단계: 맨 앞 SLC 블록의 유효 페이지 1개를 고밀도 블록으로 옮기거나, 다 옮겼으면 erase
+    // 걸린 시간(us)을 반환
+    pub fn fold_step(&mut self) -> Result<u64, String> {
+        let victim_idx = *self.fold_queue.front().ok_or("Nothing to fold")?;
+        let victim_pages = self.blocks[victim_idx].pages.len();
+
+        while self.fold_next_page < victim_pages
+            && self.blocks[victim_idx].pages[self.fold_next_page].state != PageState::Valid
+        {
+            self.fold_next_page += 1;
+        }
+
+        if self.fold_next_page == victim_pages {
+            let cost = self.erase(victim_idx);
+            self.fold_queue.pop_front();
+            self.fold_next_page = 0;
+            debug!("[Fold] SLC Block {} folded and erased", victim_idx);
+            return Ok(cost);
+        }
+
+        let page_idx = self.fold_next_page;
+        let data = self.blocks[victim_idx].pages[page_idx].content;
+        let lba = self.find_lba_by_pba(victim_idx as u32, page_idx)
+            .ok_or_else(|| format!("Valid page {}/{} has no LBA", victim_idx, page_idx))?;
+        self.stats.record_nand_read(CellMode::Slc);
+        let cost = CellMode::Slc.timing().read_us + self.write_dense(lba, data)?;
+        self.stats.folded_pages += 1;
+        self.fold_next_page += 1;
+        Ok(cost)
+    }
+
+    // 빈 고밀도 블록이 하나도 없을 때 공간 확보: 접어서 비운 블록을 고밀도로 쓸 수 있으면(동적 캐시) 접고, 아니면 고밀도 블록 GC
+    // (정적 캐시 블록은 접어도 고밀도 빈 블록이 늘지 않으므로 고밀도 페이지만 소모함)
+    fn reclaim(&mut self) -> Result<(), String> {
+        let cost = match self.fold_queue.front() {
+            Some(&idx) if !self.is_static_cache(idx)
+                && self.count_valid_pages_after_fold_point(idx) <= self.count_dense_free_pages() => {
+                info!("[Fold] Foreground fold of SLC Block {}", idx);
+                let mut cost = 0;
+                while self.fold_queue.front() == Some(&idx) {
+                    cost += self.fold_step()?;
+                }
+                cost
+            }
+            _ => self.gc_dense()?,
+        };
+        self.now_us += cost;
+        Ok(())
+    }
+
+    fn count_valid_pages_after_fold_point(&self, idx: usize) -> usize {
+        self.blocks[idx].pages[self.fold_next_page..].iter().filter(|p| p.state == PageState::Valid).count()
+    }
+
+    // 고밀도 블록 GC (Stop-the-world): 무효 페이지가 가장 많은 고밀도 블록 하나를 정리, 걸린 시간 반환
+    // 색인을 무효 페이지가 많은 순으로 훑으므로 유효 페이지를 옮길 자리가 있는 첫 블록이 victim
+    fn gc_dense(&mut self) -> Result<u64, String> {
+        let free_pages = self.count_dense_free_pages();
+        let victim_idx = self.victims.by_invalid_desc()
+            .find(|&i| self.blocks[i].count_valid_pages() <= free_pages)
+            .ok_or("Failed to find victim block!")?;
+
+        info!("[GC] Dense victim Block {} (invalid pages {})", victim_idx, self.victims.invalid_pages(victim_idx).unwrap_or(0));
+        let free_blocks = self.count_free_dense_blocks();
+        self.stats.record_gc_start(self.blocks[victim_idx].count_valid_pages(), free_blocks);
+
+        let timing = self.config.dense_mode.timing();
+        let mut cost = 0;
+        for page_idx in 0..self.blocks[victim_idx].pages.len() {
+            if self.blocks[victim_idx].pages[page_idx].state != PageState::Valid {
+                continue;
+            }
+            let data = self.blocks[victim_idx].pages[page_idx].content;
+            if let Some(lba) = self.find_lba_by_pba(victim_idx as u32, page_idx) {
+                self.stats.record_nand_read(self.config.dense_mode);
+                cost += timing.read_us + self.write_dense(lba, data)?;
+                self.stats.gc_migrated_pages += 1;
+            }
+        }
+        Ok(cost + self.erase(victim_idx))
+    }
+
+    // SLC 캐시에 쓰기 (캐시 블록을 더 열 수 없으면 None), 걸린 시간 반환
+    fn write_slc(&mut self, lba: usize, data: u32) -> Option<u64> {
+        let idx = match self.slc_active {
+            Some(idx) => idx,
+            None => {
+                let idx = self.open_slc_block()?;
+                self.slc_active = Some(idx);
+                idx
+            }
+        };
+        let cost = self.program(idx, lba, data);
+        if self.blocks[idx].state == BlockState::Full {
+            self.fold_queue.push_back(idx);
+            self.slc_active = None;
+        }
+        Some(cost)
+    }
+
+    // 고밀도 블록에 쓰기 (Active가 꽉 차면 빈 블록을 새로 엶), 걸린 시간 반환
+    fn write_dense(&mut self, lba: usize, data: u32) -> Result<u64, String> {
+        if !self.has_free_page(self.dense_active) {
+            let next_idx = self.find_free_block().ok_or("Fatal Error: SSD is Full!")?;
+            self.blocks[next_idx].set_cell_mode(self.config.dense_mode)?;
+            self.free_pool.remove(next_idx);
+            debug!("Switching dense Active Block: {} -> {}", self.dense_active, next_idx);
+            // 다 쓴 고밀도 Active Block은 GC 후보가 됨
+            let old_idx = std::mem::replace(&mut self.dense_active, next_idx);
+            let invalid_pages = self.blocks[old_idx].pages.iter().filter(|p| p.state != PageState::Valid).count();
+            self.victims.insert(old_idx, invalid_pages);
+        }
+        Ok(self.program(self.dense_active, lba, data))
+    }
+
+    // 새 SLC 블록 열기: 정적 캐시는 비어 있는 캐시 블록, 동적 캐시는 예산 안에서 빈 고밀도 블록을 SLC로 전환
+    fn open_slc_block(&mut self) -> Option<usize> {
+        match self.config.policy {
+            SlcCachePolicy::Static { blocks } => {
+                (0..blocks).find(|&i| self.blocks[i].state == BlockState::Free && !self.blocks[i].is_bad)
+            }
+            SlcCachePolicy::Dynamic { .. } => {
+                // 고밀도 쪽 GC 여유분(빈 블록 1개)은 남겨둠
+                if self.count_slc_blocks_in_use() >= self.cache_budget_blocks() || self.count_free_dense_blocks() <= 1 {
+                    return None;
+                }
+                let idx = self.find_free_block()?;
+                self.blocks[idx].set_cell_mode(CellMode::Slc).ok()?;
+                self.free_pool.remove(idx);
+                debug!("[SLC] Block {} converted to SLC cache", idx);
+                Some(idx)
+            }
+        }
+    }
+
+    // 지금 쓸 수 있는 SLC 캐시 블록 수
+    pub fn cache_budget_blocks(&self) -> usize {
+        match self.config.policy {
+            SlcCachePolicy::Static { blocks } => blocks,
+            SlcCachePolicy::Dynamic { free_space_pct } => {
+                // 비어 있는 사용자 용량 = 고밀도 페이지 수 - 매핑된 LBA 수 (퇴역한 배드 블록 제외)
+                let dense_pages = self.config.dense_mode.pages_per_block();
+                let good_blocks = self.blocks.len().saturating_sub(self.bad_blocks + 1);
+                let unused = (good_blocks * dense_pages).saturating_sub(self.mapping_table.mapped_count());
+                (unused as f64 * free_space_pct / 100.0 / dense_pages as f64) as usize
+            }
+        }
+    }
+
+    // 데이터를 담고 있는 SLC 블록 수 (Active + 접기 대기)
+    // SLC Active Block은 열자마자 프로그램되므로 열려 있으면 데이터가 있음
+    pub fn count_slc_blocks_in_use(&self) -> usize {
+        self.fold_queue.len() + usize::from(self.slc_active.is_some())
+    }
+
+    fn program(&mut self, block_idx: usize, lba: usize, data: u32) -> u64 {
+        let block = &mut self.blocks[block_idx];
+        let page_offset = block.pages.iter().position(|p| p.state == PageState::Free)
+            .expect("caller checked that the block has a free page");
+        block.program(page_offset, data);
+        self.stats.record_nand_write(block.cell_mode);
+
+        let new_pba = PhysicalAddress { block_id: block.id, page_offset };
+        if let Some(old_pba) = self.mapping_table.update(lba, new_pba) {
+            self.blocks[old_pba.block_id as usize].pages[old_pba.page_offset].state = PageState::Invalid;
+            self.victims.invalidate(old_pba.block_id as usize);
+        }
+        self.blocks[block_idx].cell_mode.timing().program_us
+    }
+
+    // 접은 SLC 블록 / 고밀도 victim은 Active Block이 아니므로 지우면 (정적 캐시가 아니면) 바로 빈 블록 풀로
+    // 보장 P/E 사이클을 다 쓴 블록은 배드 블록으로 퇴역 (정적 캐시 블록이면 캐시가 그만큼 줄어듦)
+    fn erase(&mut self, block_idx: usize) -> u64 {
+        self.victims.remove(block_idx);
+        let block = &mut self.blocks[block_idx];
+        block.erase();
+        self.stats.record_nand_erase(block.cell_mode);
+        let erase_us = block.cell_mode.timing().erase_us;
+        if block.is_worn_out() {
+            block.is_bad = true;
+            self.bad_blocks += 1;
+            warn!("[BadBlock] Block {} retired after {} erases ({:?} endurance)", block_idx, block.erase_count, block.cell_mode);
+        } else if !self.is_static_cache(block_idx) {
+            self.free_pool.insert(&self.blocks[block_idx]);
+        }
+        erase_us
+    }
+
+    fn is_static_cache(&self, idx: usize) -> bool {
+        is_static_cache(&self.config, idx)
+    }
+
+    fn has_free_page(&self, idx: usize) -> bool {
+        self.blocks[idx].state != BlockState::Full
+    }
+
+    // 고밀도 데이터에 쓸 수 있는 빈 블록 (정적 캐시 블록, Active 블록 제외) 중 번호가 가장 작은 블록
+    // 풀에서 빼는 것은 셀 모드를 바꾼 뒤 호출한 쪽에서
+    fn find_free_block(&self) -> Option<usize> {
+        self.free_pool.first()
+    }
+
+    pub fn count_free_dense_blocks(&self) -> usize {
+        self.free_pool.len()
+    }
+
+    // 고밀도 Active Block의 남은 페이지 + 빈 블록을 고밀도로 열었을 때의 페이지
+    fn count_dense_free_pages(&self) -> usize {
+        let active = &self.blocks[self.dense_active];
+        let remaining = active.pages.iter().filter(|p| p.state == PageState::Free).count();
+        remaining + self.count_free_dense_blocks() * self.config.dense_mode.pages_per_block()
+    }
+
+    // 물리 페이지의 LBA (매핑 테이블의 역방향 색인으로 O(1))
+    fn find_lba_by_pba(&self, block_id: u32, page_offset: usize) -> Option<usize> {
+        self.mapping_table.lba_at(PhysicalAddress { block_id, page_offset })
+    }
+
+    pub fn config(&self) -> &SlcCacheConfig {
+        &self.config
+    }
+
+    pub fn now_us(&self) -> u64 {
+        self.now_us
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats.reset();
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.reset_baseline();
+        }
+    }
+
+    pub fn attach_metrics(&mut self, mut recorder: MetricsRecorder) {
+        recorder.start_at(self.stats.host_writes);
+        self.metrics = Some(recorder);
+    }
+
+    // 메트릭 sink에 기록하다 처음 난 에러
+    pub fn metrics_error(&self) -> Option<&str> {
+        self.metrics.as_ref().and_then(|m| m.error())
+    }
+
+    // 샘플러를 떼어내면서 sink에 남은 내용을 flush (기록 중 난 에러도 여기서 보고)
+    pub fn detach_metrics(&mut self) -> Result<(), String> {
+        match self.metrics.take() {
+            Some(mut recorder) => recorder.flush(),
+            None => Ok(()),
+        }
+    }
+
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    pub fn print_heatmap(&self) {
+        print!("{}", terminal_heatmap(&self.blocks, 64));
+    }
+}
+
+fn is_static_cache(config: &SlcCacheConfig, idx: usize) -> bool {
+    matches!(config.policy, SlcCachePolicy::Static { blocks } if idx < blocks)
+}
+
+// 빈 블록 풀에 있어야 하는 블록
+fn is_free_block(config: &SlcCacheConfig, dense_active: usize, slc_active: Option<usize>, idx: usize, block: &Block) -> bool {
+    !is_static_cache(config, idx) && idx != dense_active && Some(idx) != slc_active
+        && block.state == BlockState::Free && !block.is_bad
+}
+
+// 고밀도 GC 후보여야 하는 블록
+fn is_dense_victim(config: &SlcCacheConfig, dense_active: usize, idx: usize, block: &Block) -> bool {
+    idx != dense_active && block.cell_mode == config.dense_mode && block.state != BlockState::Free
+}
+
+impl BlockDevice for SSD {
+    fn read(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        SSD::read(self, lba)
+    }
+
+    fn write(&mut self, lba: usize, data: u32) -> Result<(), String> {
+        SSD::write(self, lba, data)
+    }
+
+    fn trim(&mut self, lba: usize) -> Result<(), String> {
+        SSD::trim(self, lba)
+    }
+
+    fn write_range(&mut self, start_lba: usize, data: &[u32]) -> Result<(), String> {
+        SSD::write_range(self, start_lba, data)
+    }
+
+    fn read_range(&mut self, start_lba: usize, len: usize) -> Result<Vec<Option<u32>>, String> {
+        SSD::read_range(self, start_lba, len)
+    }
+
+    fn trim_range(&mut self, start_lba: usize, len: usize) -> Result<(), String> {
+        SSD::trim_range(self, start_lba, len)
+    }
+
+    fn num_lbas(&self) -> usize {
+        SSD::num_lbas(self)
+    }
+
+    fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    fn reset_stats(&mut self) {
+        SSD::reset_stats(self)
+    }
+
+    fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        SSD::check_invariants(self)
+    }
+
+    fn save_snapshot(&self, path: &str) -> Result<(), String> {
+        SSD::save_snapshot(self, path)
+    }
+
+    fn set_arrival(&mut self, arrival_us: u64) {
+        self.next_arrival_us = arrival_us;
+    }
+
+    fn clock_us(&self) -> Option<u64> {
+        Some(self.now_us)
+    }
+}
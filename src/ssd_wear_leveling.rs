@@ -1,36 +1,280 @@
 use super::block::*;
+use super::cell::CellMode;
 use super::mapping::*;
 use super::page::*;
-use log::{info, debug, warn, trace};
+use super::stats::*;
+use super::metrics::MetricsRecorder;
+use super::visualize::terminal_heatmap;
+use super::device::BlockDevice;
+use super::invariants;
+use super::alloc::{AllocPolicy, FreeBlockPool, WriteStream};
+use super::gc::VictimIndex;
+use super::seed::{derive_seed, RngState, STREAM_FTL};
+use log::{info, debug, warn};
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+
+// 정적 wear leveling: GC와 별도로, 덜 닳은 블록에 오래 머문 cold 데이터를 더 많이 닳은 빈 블록으로 옮김
+// 옮기고 지운 (덜 닳은) 블록은 이후 자주 바뀌는 데이터를 받아 erase 횟수가 고르게 됨
+// 어떤 블록을 언제 옮길지는 알고리즘마다 다르고, 옮기는 방법(wear_level)은 모두 같음
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WlAlgorithm {
+    // gap_threshold / age_threshold 조건 (둘 중 하나라도 만족하면, 둘 다 None이면 정적 WL 없음)
+    #[default]
+    Threshold,
+    // 점진적 임계값: 허용 erase 차이 = max(base, 평균 erase 횟수 * ratio) -> 수명 초반엔 자주, 후반엔 드물게
+    Adaptive { base: u32, ratio: f64 },
+    // Dual-Pool: 블록을 hot pool / cold pool로 나누고 풀에 들어온 뒤의 erase 횟수로 풀을 조정
+    // hot pool 최대 erase - cold pool 최소 erase > threshold면 cold pool 블록의 데이터를 닳은 블록으로 옮기고 두 블록의 풀을 맞바꿈
+    DualPool { threshold: u32 },
+    // Old-Block-Left-Behind: 평균보다 threshold 넘게 닳은 블록은 새 쓰기에 배정하지 않고 (남겨두고)
+    // 그런 블록이 비면 가장 덜 닳은 블록의 cold 데이터를 옮겨 담아 쉬게 함
+    OldBlockLeftBehind { threshold: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StaticWlConfig {
+    pub algorithm: WlAlgorithm,
+    // Threshold: 가장 많이 지워진 블록과 cold 데이터가 든 블록의 erase 횟수 차이가 이보다 크면
+    pub gap_threshold: Option<u32>,
+    // Threshold: cold 데이터가 든 블록이 마지막 erase 뒤 이만큼의 호스트 쓰기 동안 그대로면
+    pub age_threshold: Option<u64>,
+    // 호스트 쓰기 몇 번마다 조건을 확인할지 (0이면 자동으로 돌지 않음 -> wear_level()을 직접 호출)
+    pub check_interval: u64,
+}
+
+impl Default for StaticWlConfig {
+    fn default() -> Self {
+        StaticWlConfig {
+            algorithm: WlAlgorithm::Threshold,
+            gap_threshold: Some(5),
+            age_threshold: None,
+            check_interval: PAGES_PER_BLOCK as u64,
+        }
+    }
+}
+
 pub struct SSD {
     blocks: Vec<Block>,
     mapping_table: MappingTable,
     active_block_idx: usize,
-    user_write_cnt: u64,
-    nand_write_cnt: u64,
-    gap_threshold: u32
+    stats: Stats,
+    // 시계열 메트릭 샘플러 (붙이지 않으면 None)
+    metrics: Option<MetricsRecorder>,
+    wl_config: StaticWlConfig,
+    // 지금까지의 호스트 쓰기 수 (통계 리셋과 무관한 장치 시계)
+    clock: u64,
+    // 블록별 마지막 erase 시각 (clock 기준)
+    last_erase: Vec<u64>,
+    // Dual-Pool: 블록별 (hot pool 여부, 풀에 들어올 때의 erase 횟수)
+    pools: Vec<(bool, u32)>,
+    // 디버그용: 매 연산 뒤 check_invariants() 실행
+    debug_checks: bool,
+    alloc_policy: AllocPolicy,
+    // 할당할 수 있는 빈 블록 (Active Block 제외)
+    free_pool: FreeBlockPool,
+    // GC 후보 블록의 유효하지 않은 페이지 수별 색인 (페이지 상태가 바뀔 때마다 갱신)
+    victims: VictimIndex,
+    // FTL 내부 난수 (Random 할당) - 마스터 seed에서 파생
+    rng: ChaCha12Rng,
+}
+
+// 장치 상태 전체를 저장/복원하기 위한 스냅샷 (메트릭 샘플러는 포함하지 않음)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SsdSnapshot {
+    blocks: Vec<Block>,
+    mapping_table: MappingTable,
+    active_block_idx: usize,
+    #[serde(with = "super::stats::with_lba_writes")]
+    stats: Stats,
+    #[serde(default)]
+    wl_config: StaticWlConfig,
+    #[serde(default)]
+    clock: u64,
+    #[serde(default)]
+    last_erase: Vec<u64>,
+    #[serde(default)]
+    pools: Vec<(bool, u32)>,
+    #[serde(default)]
+    alloc_policy: AllocPolicy,
+    // RoundRobin 할당 위치 (빈 블록 풀 자체는 블록 상태에서 다시 만듦)
+    #[serde(default)]
+    alloc_cursor: usize,
+    // 예전 스냅샷에는 없음 (seed 0으로 시작)
+    #[serde(default)]
+    rng: Option<RngState>,
 }
 
 impl SSD {
     pub fn new(num_blocks: usize, num_lbas: usize) -> Self {
+        Self::with_seed(num_blocks, num_lbas, 0)
+    }
+
+    // seed: 실험 전체의 마스터 seed (같은 seed면 Random 할당도 똑같이 재현됨)
+    pub fn with_seed(num_blocks: usize, num_lbas: usize, seed: u64) -> Self {
         let mut blocks = Vec::new();
         for i in 0..num_blocks {
             blocks.push(Block::new(i as u32));
         }
+        let free_pool = FreeBlockPool::from_blocks(&blocks, 0, 0);
 
         SSD {
             blocks,
             mapping_table: MappingTable::new(num_lbas),
             active_block_idx: 0,
-            user_write_cnt: 0,
-            nand_write_cnt: 0,
-            gap_threshold: 5
+            stats: Stats::new(num_lbas),
+            metrics: None,
+            wl_config: StaticWlConfig::default(),
+            clock: 0,
+            last_erase: vec![0; num_blocks],
+            // 처음에는 모든 블록이 hot pool
+            pools: vec![(true, 0); num_blocks],
+            debug_checks: false,
+            alloc_policy: AllocPolicy::FirstFree,
+            free_pool,
+            victims: VictimIndex::new(num_blocks),
+            rng: ChaCha12Rng::seed_from_u64(derive_seed(seed, STREAM_FTL)),
+        }
+    }
+
+    pub fn snapshot(&self) -> SsdSnapshot {
+        SsdSnapshot {
+            blocks: self.blocks.clone(),
+            mapping_table: self.mapping_table.clone(),
+            active_block_idx: self.active_block_idx,
+            stats: self.stats.clone(),
+            wl_config: self.wl_config,
+            clock: self.clock,
+            last_erase: self.last_erase.clone(),
+            pools: self.pools.clone(),
+            alloc_policy: self.alloc_policy,
+            alloc_cursor: self.free_pool.cursor(),
+            rng: Some(RngState::of(&self.rng)),
         }
     }
 
+    pub fn from_snapshot(snapshot: SsdSnapshot) -> Self {
+        // erase 시각이 없는 예전 스냅샷은 모두 0으로
+        let mut last_erase = snapshot.last_erase;
+        last_erase.resize(snapshot.blocks.len(), 0);
+        let mut pools = snapshot.pools;
+        pools.resize(snapshot.blocks.len(), (true, 0));
+        let free_pool = FreeBlockPool::from_blocks(&snapshot.blocks, snapshot.active_block_idx, snapshot.alloc_cursor);
+        let victims = VictimIndex::from_blocks(&snapshot.blocks, snapshot.active_block_idx);
+        let rng = match snapshot.rng {
+            Some(state) => state.restore(),
+            None => ChaCha12Rng::seed_from_u64(derive_seed(0, STREAM_FTL)),
+        };
+        SSD {
+            blocks: snapshot.blocks,
+            mapping_table: snapshot.mapping_table,
+            active_block_idx: snapshot.active_block_idx,
+            stats: snapshot.stats,
+            metrics: None,
+            wl_config: snapshot.wl_config,
+            clock: snapshot.clock,
+            last_erase,
+            pools,
+            debug_checks: false,
+            alloc_policy: snapshot.alloc_policy,
+            free_pool,
+            victims,
+            rng,
+        }
+    }
+
+    pub fn save_snapshot(&self, path: &str) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+        serde_json::to_writer(BufWriter::new(file), &self.snapshot())
+            .map_err(|e| format!("Failed to write snapshot {}: {}", path, e))
+    }
+
+    pub fn load_snapshot(path: &str) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+        let snapshot: SsdSnapshot = serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| format!("Failed to parse snapshot {}: {}", path, e))?;
+        Ok(Self::from_snapshot(snapshot))
+    }
+
+    // 블록의 셀 모드 변경 (지워진 블록만 가능, 혼합 모드 장치 구성용)
+    pub fn set_cell_mode(&mut self, block_idx: usize, cell_mode: CellMode) -> Result<(), String> {
+        let block = self.blocks.get_mut(block_idx).ok_or_else(|| format!("Block {} does not exist", block_idx))?;
+        block.set_cell_mode(cell_mode)?;
+        // 빈 블록 풀이 세는 페이지 수도 바뀜
+        self.free_pool.update_pages(&self.blocks[block_idx]);
+        Ok(())
+    }
+
+    pub fn set_alloc_policy(&mut self, policy: AllocPolicy) {
+        self.alloc_policy = policy;
+    }
+
+    pub fn alloc_policy(&self) -> AllocPolicy {
+        self.alloc_policy
+    }
+
+    // 켜면 호스트 연산과 GC가 끝날 때마다 check_invariants()를 돌려 위반 시 에러 반환 (느림)
+    pub fn set_debug_checks(&mut self, enabled: bool) {
+        self.debug_checks = enabled;
+    }
+
+    // 매핑 테이블 / 페이지 / 블록 상태 / 통계 카운터의 일관성 검사 (첫 번째 위반을 에러로)
+    pub fn check_invariants(&self) -> Result<(), String> {
+        invariants::check(&self.blocks, &self.mapping_table, self.active_block_idx, &self.stats)?;
+        self.free_pool.check(&self.blocks, self.active_block_idx)?;
+        self.victims.check(&self.blocks, self.active_block_idx)
+    }
+
+    fn debug_check(&self, op: &str) -> Result<(), String> {
+        if !self.debug_checks {
+            return Ok(());
+        }
+        self.check_invariants().map_err(|e| format!("Invariant violated after {}: {}", op, e))
+    }
+
     // [수정 1] 공용 Write 함수: 정책 담당 (사용자 카운트 증가 + GC 트리거 + 위임)
     pub fn write(&mut self, lba: usize, data: u32) -> Result<(), String> {
-        self.user_write_cnt += 1; // 사용자가 요청했으므로 증가
+        self.write_range(lba, std::slice::from_ref(&data))
+    }
+
+    // 범위 요청: 요청 수만 한 번 기록하고 페이지 단위 처리는 그대로
+    pub fn write_range(&mut self, start_lba: usize, data: &[u32]) -> Result<(), String> {
+        self.mapping_table.check_range(start_lba, data.len())?;
+        if !data.is_empty() {
+            self.stats.record_write_request(data.len());
+        }
+        for (lba, data) in (start_lba..).zip(data.iter().copied()) {
+            self.write_page(lba, data)?;
+        }
+        Ok(())
+    }
+
+    pub fn read_range(&mut self, start_lba: usize, len: usize) -> Result<Vec<Option<u32>>, String> {
+        self.mapping_table.check_range(start_lba, len)?;
+        if len > 0 {
+            self.stats.record_read_request(len);
+        }
+        (start_lba..start_lba + len).map(|lba| self.read_page(lba)).collect()
+    }
+
+    pub fn trim_range(&mut self, start_lba: usize, len: usize) -> Result<(), String> {
+        self.mapping_table.check_range(start_lba, len)?;
+        if len > 0 {
+            self.stats.record_trim_request(len);
+        }
+        (start_lba..start_lba + len).try_for_each(|lba| self.trim_page(lba))
+    }
+
+    fn write_page(&mut self, lba: usize, data: u32) -> Result<(), String> {
+        if lba >= self.mapping_table.entries().len() {
+            return Err(format!("LBA {} is out of range!", lba));
+        }
+        self.stats.record_host_write(lba); // 사용자가 요청했으므로 증가
 
         // [방어 로직] 빈 블록이 1개 이하로 남으면 미리 GC를 돌려서 여유 공간 확보 (Reserved Block)
         while self.count_free_blocks() == 0 {
@@ -40,11 +284,11 @@ impl SSD {
 
         // 실제 쓰기는 internal에게 위임!
         // 만약 internal이 실패하면(Active Block Full), 블록을 바꾸고 다시 시도
-        if let Err(_) = self.write_internal(lba, data) {
+        if self.write_internal(lba, data).is_err() {
             // Active Block이 꽉 찼으니 다음 빈 블록 찾기
-            if let Some(next_idx) = self.find_next_free_block() {
+            if let Some(next_idx) = self.find_next_free_block(WriteStream::Host) {
                 debug!("Switching Active Block: {} -> {}", self.active_block_idx, next_idx);
-                self.active_block_idx = next_idx;
+                self.switch_active_block(next_idx);
                 
                 // 블록 교체 후 재시도 (여기선 무조건 성공해야 함)
                 self.write_internal(lba, data)?;
@@ -53,20 +297,85 @@ impl SSD {
                 return Err("Fatal Error: SSD is Full!".to_string());
             }
         }
-        Ok(())
+
+        // sink 에러는 호스트 쓰기 결과와 무관 (recorder가 보관)
+        if self.metrics.as_ref().is_some_and(|m| m.is_due(self.stats.host_writes)) {
+            let free_blocks = self.count_free_blocks();
+            if let Some(metrics) = self.metrics.as_mut() {
+                metrics.sample(&self.stats, &self.blocks, free_blocks);
+            }
+        }
+
+        // 정적 WL은 호스트 쓰기 사이의 별도 작업 (GC와 무관하게 check_interval마다 조건 확인)
+        self.clock += 1;
+        let interval = self.wl_config.check_interval;
+        if interval > 0 && self.clock.is_multiple_of(interval) {
+            self.wear_level()?;
+        }
+        self.debug_check("write")
+    }
+
+    // 읽기: LBA가 가리키는 페이지의 데이터를 반환 (한 번도 안 쓴 LBA면 None)
+    pub fn read(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        Ok(self.read_range(lba, 1)?[0])
+    }
+
+    fn read_page(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        if lba >= self.mapping_table.entries().len() {
+            return Err(format!("LBA {} is out of range!", lba));
+        }
+
+        self.stats.host_reads += 1;
+        let data = self.mapping_table.get(lba).map(|pba| {
+            let block = &self.blocks[pba.block_id as usize];
+            (block.read(pba.page_offset).content, block.cell_mode)
+        });
+        if let Some((_, mode)) = data {
+            self.stats.record_nand_read(mode);
+        }
+        self.debug_check("read")?;
+        Ok(data.map(|(content, _)| content))
+    }
+
+    // Trim (Unmap): 파일 삭제 등으로 더 이상 필요 없는 LBA를 알려줌 -> 해당 페이지는 바로 Invalid
+    pub fn trim(&mut self, lba: usize) -> Result<(), String> {
+        self.trim_range(lba, 1)
+    }
+
+    fn trim_page(&mut self, lba: usize) -> Result<(), String> {
+        if lba >= self.mapping_table.entries().len() {
+            return Err(format!("LBA {} is out of range!", lba));
+        }
+
+        self.stats.host_trims += 1;
+        if let Some(old_pba) = self.mapping_table.unmap(lba) {
+            self.blocks[old_pba.block_id as usize].pages[old_pba.page_offset].state = PageState::Invalid;
+            self.victims.invalidate(old_pba.block_id as usize);
+            debug!("  -> Trimmed LBA {}: Block {} Page {}", lba, old_pba.block_id, old_pba.page_offset);
+        }
+        self.debug_check("trim")
+    }
+
+    pub fn num_lbas(&self) -> usize {
+        self.mapping_table.entries().len()
     }
 
     // [수정 2] 내부 Write 함수: 실제 동작 담당 (NAND 카운트 증가 + 쓰기 + 매핑)
-    // GC는 이 함수를 호출하므로 user_write_cnt가 오르지 않음 (WAF 정확도 상승)
+    // GC는 이 함수를 호출하므로 host_writes가 오르지 않음 (WAF 정확도 상승)
     fn write_internal(&mut self, lba: usize, data: u32) -> Result<(), String> {
-        let block = &mut self.blocks[self.active_block_idx];
+        self.program_into(self.active_block_idx, lba, data)
+    }
+
+    // block_idx 블록의 첫 빈 페이지에 프로그램하고 매핑 갱신 (정적 WL은 Active Block이 아닌 블록에 씀)
+    fn program_into(&mut self, block_idx: usize, lba: usize, data: u32) -> Result<(), String> {
+        let block = &mut self.blocks[block_idx];
 
         // 빈 페이지 찾기
         let mut target_page = None;
-        for page_offset in 0..PAGES_PER_BLOCK {
+        for page_offset in 0..block.pages.len() {
             if block.read(page_offset).state == PageState::Free {
                 // [수정] 여기서만 NAND 카운트를 올리면 됨 (GC 상황도 포함되므로)
-                self.nand_write_cnt += 1; 
+                self.stats.record_nand_write(block.cell_mode);
                 block.program(page_offset, data);
                 target_page = Some(page_offset);
                 break;
@@ -75,7 +384,7 @@ impl SSD {
 
         if let Some(page_offset) = target_page {
             let new_pba = PhysicalAddress {
-                block_id: self.blocks[self.active_block_idx].id,
+                block_id: self.blocks[block_idx].id,
                 page_offset,
             };
 
@@ -87,81 +396,39 @@ impl SSD {
                 if old_blk_idx < self.blocks.len() {
                     // Block에 invalidate 메서드가 있다고 가정 (직접 접근도 가능)
                     self.blocks[old_blk_idx].pages[old_pba.page_offset].state = PageState::Invalid;
+                    self.victims.invalidate(old_blk_idx);
                     debug!("  -> Invalidated Old Data: Block {} Page {}", old_blk_idx, old_pba.page_offset);
                 }
             }
             Ok(())
         } else {
-            // 현재 Active Block이 꽉 참 -> 상위 함수(write)나 GC가 처리하도록 에러 반환
-            Err("Active block is full".to_string())
+            // 블록이 꽉 참 -> 상위 함수(write)나 GC가 처리하도록 에러 반환
+            Err(format!("Block {} is full", block_idx))
         }
     }
     
+    // GC: 무효 페이지가 가장 많은 블록(Greedy)을 정리해서 빈 블록 1개 확보
+    // (wear leveling은 wear_level()에서 따로 함)
     pub fn gc(&mut self) -> Result<(), String> {
         info!("\n[GC] Started! (Free blocks: {})", self.count_free_blocks());
 
-        // 1. Wear Leveling 통계 계산
-        // (compute_wear_metrics 함수가 WearStats { min, max, avg, gap }을 반환한다고 가정)
-        let stat = self.compute_wear_metrics();
-        
-        // =========================================================
-        // [Step 1] 희생 블록(Victim) 선정
-        // =========================================================
-        let victim_idx = if stat.gap > self.gap_threshold {
-            // [Case A] Wear Leveling 트리거!
-            // 가장 지우기 횟수가 적은 블록(Cold Block)을 강제로 희생양으로 삼음
-            info!("[WL] Triggered! Gap: {} (Max: {}, Min: {})", stat.gap, stat.max, stat.min);
-            
-            // stat.min에 해당하는 블록 인덱스를 찾음
-            // (Active Block은 제외해야 함)
-            let mut target = None;
-            for (i, block) in self.blocks.iter().enumerate() {
-                if block.erase_count == stat.min && i != self.active_block_idx {
-                    target = Some(i);
-                    break;
-                }
-            }
-            
-            match target {
-                Some(idx) => {
-                    info!("[WL] Forcing Cold Block {} to be cleaned.", idx);
-                    idx
-                },
-                None => {
-                    // 이론상 여기 오면 안 되지만, 만약 Active Block이 Min이라면 
-                    // 어쩔 수 없이 일반 GC로 넘어감
-                    return Err("WL Triggered but Cold Block is Active".to_string());
-                }
-            }
-        } else {
-            // [Case B] 일반 GC (Greedy Policy)
-            // 유효 페이지(Valid Page)가 가장 적은 블록을 선정
-            let mut target = None;
-            let mut min_valid_count = usize::MAX;
-
-            for (i, block) in self.blocks.iter().enumerate() {
-                // 현재 쓰고 있는 블록이나 이미 빈 블록은 제외
-                if i == self.active_block_idx || block.state == BlockState::Free {
-                    continue;
-                }
-
-                let valid_cnt = block.count_valid_pages();
-                if valid_cnt < min_valid_count {
-                    min_valid_count = valid_cnt;
-                    target = Some(i);
-                }
-            }
+        // 무효 페이지가 가장 많은 블록을 선정 (셀 모드가 같으면 유효 페이지가 가장 적은 블록), 같으면 번호가 작은 블록
+        // WL로 데이터를 받고 덜 찬 블록의 빈 페이지도 erase하면 돌려받으므로 색인이 함께 셈
+        // 색인을 많은 순으로 훑으므로 유효 페이지를 옮길 자리가 있는 첫 블록이 victim
+        let free_pages = self.count_free_pages();
+        let target = self.victims.by_invalid_desc().find(|&i| self.blocks[i].count_valid_pages() <= free_pages);
 
-            match target {
-                Some(idx) => idx,
-                None => return Err("Failed to find victim block! (SSD might be clean)".to_string()),
-            }
+        let victim_idx = match target {
+            Some(idx) => idx,
+            None => return Err("Failed to find victim block! (SSD might be clean)".to_string()),
         };
 
     let valid_pages_cnt = self.blocks[victim_idx].count_valid_pages();
     debug!("[GC] Selected Victim: Block {} (Valid Pages: {})", victim_idx, valid_pages_cnt);
+    let free_blocks = self.count_free_blocks();
+    self.stats.record_gc_start(valid_pages_cnt, free_blocks);
         // 2. 유효 페이지 대피 (Migration)
-        for page_idx in 0..PAGES_PER_BLOCK {
+        for page_idx in 0..self.blocks[victim_idx].pages.len() {
             let is_valid = self.blocks[victim_idx].pages[page_idx].state == PageState::Valid;
     
             if is_valid {
@@ -170,16 +437,21 @@ impl SSD {
     
                 if let Some(target_lba) = lba_opt {
                     // ✅ 루프: Active Block이 또 꽉 찰 때까지 계속 전환
+                    self.stats.record_nand_read(self.blocks[victim_idx].cell_mode);
                     loop {
                         match self.write_internal(target_lba, data) {
-                            Ok(()) => break,  // 성공! 루프 탈출
+                            Ok(()) => {
+                                // 성공! 루프 탈출
+                                self.stats.gc_migrated_pages += 1;
+                                break;
+                            }
                             Err(_) => {
                                 // Active Block 꽉 찼음
-                                match self.find_next_free_block() {
+                                match self.find_next_free_block(WriteStream::Gc) {
                                     Some(next_idx) => {
                                         debug!("[GC] Switching block during migration: {} → {}", 
                                                  self.active_block_idx, next_idx);
-                                        self.active_block_idx = next_idx;
+                                        self.switch_active_block(next_idx);
                                         // 루프 재시도
                                     }
                                     None => {
@@ -196,43 +468,272 @@ impl SSD {
             }
         }
     
-        self.blocks[victim_idx].erase();
+        self.erase_block(victim_idx);
         info!("[GC] Erased Block {}. GC Finished.\n", victim_idx);
-        Ok(())
+        self.debug_check("gc")
     }
-    
+
+    // GC victim / WL cold 블록은 Active Block이 아니므로 지우면 바로 빈 블록 풀로
+    // 보장 P/E 사이클을 다 쓴 블록은 배드 블록으로 퇴역 (빈 블록 풀에 돌려놓지 않음)
+    fn erase_block(&mut self, idx: usize) {
+        self.victims.remove(idx);
+        let block = &mut self.blocks[idx];
+        block.erase();
+        self.stats.record_nand_erase(block.cell_mode);
+        self.last_erase[idx] = self.clock;
+        if block.is_worn_out() {
+            block.is_bad = true;
+            warn!("[BadBlock] Block {} retired after {} erases ({:?} endurance)", idx, block.erase_count, block.cell_mode);
+        } else {
+            self.free_pool.insert(&self.blocks[idx]);
+        }
+    }
+
+    // 정적 WL 한 번: 알고리즘이 고른 cold 블록의 유효 데이터를 더 닳은 빈 블록으로 옮기고 cold 블록을 지움
+    // cold 블록을 고를 때 블록 전체를 한 번 훑음 (색인 없음, check_interval마다 한 번이므로 쓰기당 비용은 블록 수 / check_interval)
+    // 조건이 안 맞거나 옮길 곳이 없으면 아무것도 안 하고 false
+    // 옮긴 블록에 남는 빈 페이지는 그 블록이 GC될 때 회수됨
+    pub fn wear_level(&mut self) -> Result<bool, String> {
+        if let WlAlgorithm::DualPool { threshold } = self.wl_config.algorithm {
+            self.adjust_pools(threshold);
+        }
+        let Some((cold_idx, dest_idx)) = self.plan_migration() else { return Ok(false) };
+
+        let valid = self.blocks[cold_idx].count_valid_pages();
+        info!("[WL] Moving {} cold pages: Block {} ({} erases) -> Block {} ({} erases)",
+              valid, cold_idx, self.blocks[cold_idx].erase_count, dest_idx, self.blocks[dest_idx].erase_count);
+        self.stats.wl_triggers += 1;
+        // 받을 블록은 Active Block이 아니라 직접 프로그램하므로 풀에서만 뺌 (할당 순서는 그대로)
+        self.free_pool.remove(dest_idx);
+        for page_idx in 0..self.blocks[cold_idx].pages.len() {
+            if self.blocks[cold_idx].pages[page_idx].state != PageState::Valid {
+                continue;
+            }
+            let data = self.blocks[cold_idx].pages[page_idx].content;
+            if let Some(lba) = self.find_lba_by_pba(cold_idx as u32, page_idx) {
+                self.stats.record_nand_read(self.blocks[cold_idx].cell_mode);
+                self.program_into(dest_idx, lba, data)?;
+                self.stats.wl_migrated_pages += 1;
+            }
+        }
+        // 받은 블록은 덜 찬 채로 GC 후보가 됨 (남은 빈 페이지도 회수 대상으로 셈)
+        let reclaimable = self.blocks[dest_idx].pages.iter().filter(|p| p.state != PageState::Valid).count();
+        self.victims.insert(dest_idx, reclaimable);
+        self.erase_block(cold_idx);
+
+        // Dual-Pool: cold 데이터를 받은 블록은 cold pool로, 비운 블록은 hot pool로
+        if matches!(self.wl_config.algorithm, WlAlgorithm::DualPool { .. }) {
+            self.pools[dest_idx] = (false, self.blocks[dest_idx].erase_count);
+            self.pools[cold_idx] = (true, self.blocks[cold_idx].erase_count);
+        }
+        self.debug_check("wear leveling")?;
+        Ok(true)
+    }
+
+    // 알고리즘별 (옮길 cold 블록, 받을 빈 블록)
+    fn plan_migration(&self) -> Option<(usize, usize)> {
+        let max_erase = self.good_blocks().map(|b| b.erase_count).max()?;
+        let erase = |i: usize| self.blocks[i].erase_count;
+        // cold 데이터 후보: Active가 아니고 유효 데이터가 있는 블록 (= 유효하지 않은 페이지가 전부가 아닌 GC 후보, 페이지를 세지 않음)
+        let holders = || {
+            self.victims.candidates().filter(|&i| self.victims.invalid_pages(i) < Some(self.blocks[i].pages.len()))
+        };
+
+        let cold_idx = match self.wl_config.algorithm {
+            WlAlgorithm::Threshold => {
+                let by_gap = self.wl_config.gap_threshold.and_then(|gap| {
+                    holders().min_by_key(|&i| (erase(i), self.last_erase[i])).filter(|&i| max_erase - erase(i) > gap)
+                });
+                let by_age = || self.wl_config.age_threshold.and_then(|age| {
+                    holders().min_by_key(|&i| self.last_erase[i]).filter(|&i| self.clock - self.last_erase[i] > age)
+                });
+                by_gap.or_else(by_age)?
+            }
+            WlAlgorithm::Adaptive { base, ratio } => {
+                let gap = base.max((self.average_erase() * ratio) as u32);
+                holders().min_by_key(|&i| (erase(i), self.last_erase[i])).filter(|&i| max_erase - erase(i) > gap)?
+            }
+            WlAlgorithm::DualPool { threshold } => {
+                let hot_max = self.pool_blocks(true).map(erase).max()?;
+                holders().filter(|&i| !self.pools[i].0).min_by_key(|&i| erase(i)).filter(|&i| hot_max.saturating_sub(erase(i)) > threshold)?
+            }
+            WlAlgorithm::OldBlockLeftBehind { threshold } => {
+                // 남겨둔 블록이 비어 있을 때만 (가장 덜 닳은 블록의 데이터를 거기로)
+                let old = self.old_limit(threshold);
+                self.most_worn_free_block(0, |i| erase(i) as f64 > old)?;
+                holders().min_by_key(|&i| (erase(i), self.last_erase[i]))?
+            }
+        };
+
+        let valid = self.blocks[cold_idx].count_valid_pages();
+        let dest = match self.wl_config.algorithm {
+            WlAlgorithm::OldBlockLeftBehind { threshold } => {
+                let old = self.old_limit(threshold);
+                self.most_worn_free_block(valid, |i| erase(i) as f64 > old && erase(i) > erase(cold_idx))
+            }
+            _ => self.most_worn_free_block(valid, |i| erase(i) > erase(cold_idx)),
+        };
+        if dest.is_none() {
+            debug!("[WL] No free block more worn than Block {} ({} erases), deferring", cold_idx, erase(cold_idx));
+        }
+        Some((cold_idx, dest?))
+    }
+
+    // 유효 페이지 pages개가 들어가고 조건을 만족하는 빈 블록 중 가장 많이 지워진 블록 (Active, 배드 블록 제외)
+    fn most_worn_free_block(&self, pages: usize, accept: impl Fn(usize) -> bool) -> Option<usize> {
+        (0..self.blocks.len())
+            .filter(|&i| self.free_pool.contains(i) && self.blocks[i].pages.len() >= pages && accept(i))
+            .max_by_key(|&i| self.blocks[i].erase_count)
+    }
+
+    // 배드 블록은 쓰지 않으므로 wear 통계에서 제외
+    fn good_blocks(&self) -> impl Iterator<Item = &Block> + '_ {
+        self.blocks.iter().filter(|b| !b.is_bad)
+    }
+
+    fn average_erase(&self) -> f64 {
+        let (sum, count) = self.good_blocks().fold((0.0, 0), |(sum, count), b| (sum + b.erase_count as f64, count + 1));
+        sum / count.max(1) as f64
+    }
+
+    // OBLB: erase 횟수가 이 값보다 크면 (평균보다 threshold 넘게 닳은) 남겨둘 블록
+    // 평균은 O(n)이므로 후보마다가 아니라 한 번 계산해서 비교
+    fn old_limit(&self, threshold: u32) -> f64 {
+        self.average_erase() + threshold as f64
+    }
+
+    // Dual-Pool 풀 조정 (풀에 들어온 뒤의 erase 횟수 = 유효 erase 횟수로 비교)
+    // hot pool 안에서 가장 덜 지워진 블록이 threshold 넘게 뒤처지면 cold pool로 (cold 데이터가 머무는 블록)
+    // cold pool 블록이 hot pool 최소보다 2 * threshold 넘게 지워지면 hot 데이터를 받고 있으므로 hot pool로
+    fn adjust_pools(&mut self, threshold: u32) {
+        let hot_min = self.pool_blocks(true).min_by_key(|&i| self.effective_erase(i));
+        let hot_max = self.pool_blocks(true).map(|i| self.effective_erase(i)).max();
+        if let (Some(idx), Some(max)) = (hot_min, hot_max)
+            && max - self.effective_erase(idx) > threshold {
+            debug!("[WL] Dual-Pool: Block {} hot -> cold pool", idx);
+            self.pools[idx] = (false, self.blocks[idx].erase_count);
+        }
+
+        let hot_min = self.pool_blocks(true).map(|i| self.effective_erase(i)).min();
+        let cold_max = self.pool_blocks(false).max_by_key(|&i| self.effective_erase(i));
+        if let (Some(min), Some(idx)) = (hot_min, cold_max)
+            && self.effective_erase(idx) > min + 2 * threshold {
+            debug!("[WL] Dual-Pool: Block {} cold -> hot pool", idx);
+            self.pools[idx] = (true, self.blocks[idx].erase_count);
+        }
+    }
+
+    fn pool_blocks(&self, hot: bool) -> impl Iterator<Item = usize> + '_ {
+        (0..self.blocks.len()).filter(move |&i| self.pools[i].0 == hot && !self.blocks[i].is_bad)
+    }
+
+    // 풀에 들어온 뒤의 erase 횟수
+    fn effective_erase(&self, idx: usize) -> u32 {
+        self.blocks[idx].erase_count - self.pools[idx].1
+    }
+
+    // 지금 바로 프로그램할 수 있는 페이지 수 (Active Block의 남은 페이지 + 빈 블록)
+    fn count_free_pages(&self) -> usize {
+        let active = &self.blocks[self.active_block_idx];
+        active.pages.iter().filter(|p| p.state == PageState::Free).count() + self.free_pool.free_pages()
+    }
+
     // [보조 함수] 빈 블록 개수 세기 (GC 트리거 조건용)
+    // 풀의 블록 + 아직 한 페이지도 안 쓴 Active Block
     fn count_free_blocks(&self) -> usize {
-        self.blocks.iter().filter(|b| b.state == BlockState::Free).count()
+        self.free_pool.len() + usize::from(self.blocks[self.active_block_idx].state == BlockState::Free)
+    }
+
+    // 다 쓴 Active Block은 GC 후보가 됨
+    fn switch_active_block(&mut self, next_idx: usize) {
+        let old_idx = std::mem::replace(&mut self.active_block_idx, next_idx);
+        let old = &self.blocks[old_idx];
+        if old.state != BlockState::Free {
+            let invalid_pages = old.pages.iter().filter(|p| p.state != PageState::Valid).count();
+            self.victims.insert(old_idx, invalid_pages);
+        }
     }
 
-    fn find_next_free_block(&self) -> Option<usize> {
-        for (i, block) in self.blocks.iter().enumerate() {
-            // 현재 쓰고 있는 블록은 제외하고 찾기
-            if block.state == BlockState::Free && i != self.active_block_idx {
-                return Some(i);
+
+    // 다음 Active Block을 할당 정책에 따라 빈 블록 풀에서 꺼냄 (현재 Active Block은 풀에 없음)
+    fn find_next_free_block(&mut self, stream: WriteStream) -> Option<usize> {
+        let mut idx = self.free_pool.choose(self.alloc_policy, stream, &mut self.rng)?;
+        // OBLB: 많이 닳은 블록은 다른 빈 블록이 없을 때만 새 쓰기에 배정 (있으면 가장 덜 닳은 블록으로)
+        if let WlAlgorithm::OldBlockLeftBehind { threshold } = self.wl_config.algorithm {
+            let old = self.old_limit(threshold);
+            if let Some((erase_count, youngest)) = self.free_pool.lowest_erase()
+                && self.blocks[idx].erase_count as f64 > old
+                && erase_count as f64 <= old {
+                idx = youngest;
             }
         }
-        None
+        self.free_pool.take(idx);
+        Some(idx)
     }
     
-    // MappingTable 구현에 따라 entries 접근 방식이 다를 수 있음 (여기선 Vec 직접 접근 가정)
+    // 물리 페이지의 LBA (매핑 테이블의 역방향 색인으로 O(1))
     fn find_lba_by_pba(&self, block_id: u32, page_offset: usize) -> Option<usize> {
-        // MappingTable의 entries 필드가 pub이어야 합니다.
-        // 만약 entries() 메서드를 쓰신다면 그대로 두셔도 됩니다.
-        for (lba, entry) in self.mapping_table.entries().iter().enumerate() {
-            if let Some(pba) = entry {
-                if pba.block_id == block_id && pba.page_offset == page_offset {
-                    return Some(lba);
-                }
-            }
+        self.mapping_table.lba_at(PhysicalAddress { block_id, page_offset })
+    }
+
+    // Wear Leveling을 발동시키는 erase 횟수 차이 (기본값 5)
+    pub fn set_gap_threshold(&mut self, gap_threshold: u32) {
+        self.wl_config.gap_threshold = Some(gap_threshold);
+    }
+
+    pub fn set_static_wl(&mut self, config: StaticWlConfig) {
+        self.wl_config = config;
+    }
+
+    pub fn static_wl(&self) -> StaticWlConfig {
+        self.wl_config
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    // warm-up이 끝난 뒤 측정 구간만 보고 싶을 때 (erase 횟수 등 블록 상태는 그대로)
+    pub fn reset_stats(&mut self) {
+        self.stats.reset();
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.reset_baseline();
+        }
+    }
+
+    pub fn attach_metrics(&mut self, mut recorder: MetricsRecorder) {
+        recorder.start_at(self.stats.host_writes);
+        self.metrics = Some(recorder);
+    }
+
+    // 메트릭 sink에 기록하다 처음 난 에러
+    pub fn metrics_error(&self) -> Option<&str> {
+        self.metrics.as_ref().and_then(|m| m.error())
+    }
+
+    // 샘플러를 떼어내면서 sink에 남은 내용을 flush (기록 중 난 에러도 여기서 보고)
+    pub fn detach_metrics(&mut self) -> Result<(), String> {
+        match self.metrics.take() {
+            Some(mut recorder) => recorder.flush(),
+            None => Ok(()),
         }
-        None
+    }
+
+    pub fn erase_distribution(&self) -> EraseDistribution {
+        EraseDistribution::from_blocks(&self.blocks)
     }
 
     pub fn get_waf(&self) -> f64 {
-        if self.user_write_cnt == 0 { return 0.0 }
-        self.nand_write_cnt as f64 / self.user_write_cnt as f64 
+        self.stats.waf()
+    }
+
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    // 전체 블록의 유효 페이지 비율 / erase 횟수를 한 화면에 요약
+    pub fn print_heatmap(&self) {
+        print!("{}", terminal_heatmap(&self.blocks, 64));
     }
 
     pub fn print_blocks(&self) {
@@ -242,16 +743,67 @@ impl SSD {
         println!("===============================")
     }
     // erase의 평균과 
+    // 배드 블록은 쓰지 않으므로 통계에서 제외
     pub fn compute_wear_metrics(&self) -> WearStats {
         let mut min = u32::MAX;
         let mut max = 0;
-        let sum = self.blocks.iter().fold(0, |acc, x| {
+        let sum = self.good_blocks().fold(0, |acc, x| {
             let cnt = x.erase_count;
             if cnt < min {min = cnt;}
             if cnt > max {max = cnt;}
             acc + x.erase_count}
         );
-        WearStats { min: min, max: max, avg: sum as f64/ self.blocks.len() as f64, gap: max - min }
+        WearStats { min, max, avg: sum as f64/ self.good_blocks().count() as f64, gap: max - min }
+    }
+
+}
+
+impl BlockDevice for SSD {
+    fn read(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        SSD::read(self, lba)
+    }
+
+    fn write(&mut self, lba: usize, data: u32) -> Result<(), String> {
+        SSD::write(self, lba, data)
+    }
+
+    fn trim(&mut self, lba: usize) -> Result<(), String> {
+        SSD::trim(self, lba)
+    }
+
+    fn write_range(&mut self, start_lba: usize, data: &[u32]) -> Result<(), String> {
+        SSD::write_range(self, start_lba, data)
+    }
+
+    fn read_range(&mut self, start_lba: usize, len: usize) -> Result<Vec<Option<u32>>, String> {
+        SSD::read_range(self, start_lba, len)
+    }
+
+    fn trim_range(&mut self, start_lba: usize, len: usize) -> Result<(), String> {
+        SSD::trim_range(self, start_lba, len)
     }
 
-}
\ No newline at end of file
+    fn num_lbas(&self) -> usize {
+        SSD::num_lbas(self)
+    }
+
+    fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    fn reset_stats(&mut self) {
+        SSD::reset_stats(self)
+    }
+
+    fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        SSD::check_invariants(self)
+    }
+
+    fn save_snapshot(&self, path: &str) -> Result<(), String> {
+        SSD::save_snapshot(self, path)
+    }
+}
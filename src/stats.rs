@@ -0,0 +1,333 @@
+// FTL 통계: 호스트/NAND 동작 횟수, GC/WL 활동, victim 유효 페이지 분포, 빈 블록 수 변화
+// reset()으로 warm-up 구간을 지우고 측정 구간만 따로 볼 수 있습니다. (블록의 erase 횟수는 물리 상태라 리셋 대상 아님)
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::block::{Block, PAGES_PER_BLOCK};
+use super::cell::CellMode;
+
+// free_block_samples에 남기는 최대 개수 (넘으면 하나 걸러 버리고 간격을 두 배로)
+pub const FREE_BLOCK_SAMPLES: usize = 256;
+
+// 셀 모드 하나의 NAND 동작 수
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NandOpCounts {
+    pub reads: u64,
+    pub writes: u64,
+    pub erases: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stats {
+    pub host_reads: u64,
+    pub host_writes: u64,
+    pub host_trims: u64,
+    // 호스트 요청 수 (범위 요청 하나 = 1, 페이지 수는 host_reads / host_writes / host_trims)
+    #[serde(default)]
+    pub read_requests: u64,
+    #[serde(default)]
+    pub write_requests: u64,
+    #[serde(default)]
+    pub trim_requests: u64,
+    // request_size_histogram[k] = 페이지 수가 [2^k, 2^(k+1))인 요청 수 (읽기/쓰기/trim 합산)
+    #[serde(default)]
+    pub request_size_histogram: Vec<u64>,
+    pub nand_reads: u64,
+    pub nand_writes: u64,
+    pub nand_erases: u64,
+    // nand_reads / nand_writes / nand_erases를 동작한 블록의 셀 모드별로 나눈 것 (bits_per_cell - 1 순서)
+    #[serde(default)]
+    pub nand_ops_by_mode: [NandOpCounts; 4],
+    // victim을 골라 GC를 시작한 횟수
+    pub gc_invocations: u64,
+    // GC로 옮긴 유효 페이지 수
+    pub gc_migrated_pages: u64,
+    pub wl_triggers: u64,
+    // 정적 wear leveling으로 옮긴 cold 페이지 (gc_migrated_pages와 별도, NAND read + program)
+    #[serde(default)]
+    pub wl_migrated_pages: u64,
+    // 페이지 일부만 덮는 쓰기 때문에 기존 페이지를 먼저 읽은 횟수 (nand_reads에도 포함)
+    pub rmw_reads: u64,
+    // SLC 캐시: 캐시에 받은 호스트 쓰기 / 캐시가 가득 차서 고밀도 블록에 바로 쓴 호스트 쓰기
+    #[serde(default)]
+    pub slc_writes: u64,
+    #[serde(default)]
+    pub slc_overflows: u64,
+    // SLC 블록에서 고밀도 블록으로 접어(folding) 옮긴 페이지 (NAND read + program)
+    #[serde(default)]
+    pub folded_pages: u64,
+    // 쓰기 버퍼: NAND에 가기 전에 덮어써지거나 trim되어 사라진 호스트 쓰기 / 버퍼에서 바로 돌려준 읽기
+    #[serde(default)]
+    pub buffer_absorbed: u64,
+    #[serde(default)]
+    pub buffer_read_hits: u64,
+    // 전원 차단으로 잃어버린 버퍼 페이지
+    #[serde(default)]
+    pub buffer_lost: u64,
+    // flush/FUA 때 프로그램 단위를 채우려고 쓴 더미 페이지 (nand_writes에 포함)
+    #[serde(default)]
+    pub padding_pages: u64,
+    // victim_valid_histogram[n] = 유효 페이지 n개를 가진 채 victim으로 뽑힌 횟수
+    pub victim_valid_histogram: Vec<u64>,
+    // free_block_histogram[n] = 빈 블록이 n개일 때 GC를 시작한 횟수
+    #[serde(default)]
+    pub free_block_histogram: Vec<u64>,
+    // GC 시작 free_block_sample_stride번마다 하나씩 기록한 (그때까지의 호스트 쓰기 수, 빈 블록 수)
+    // 오래 돌려도 FREE_BLOCK_SAMPLES개를 넘지 않도록 간격을 늘려 가며 솎아냄
+    pub free_block_samples: Vec<(u64, usize)>,
+    #[serde(default = "default_sample_stride")]
+    pub free_block_sample_stride: u64,
+    // LBA별 호스트 쓰기 횟수 (업데이트 빈도 히트맵용, 결과 출력에서는 빠지고 스냅샷에만 저장)
+    #[serde(skip)]
+    pub lba_writes: Vec<u64>,
+}
+
+impl Stats {
+    pub fn new(num_lbas: usize) -> Self {
+        Stats {
+            host_reads: 0,
+            host_writes: 0,
+            host_trims: 0,
+            read_requests: 0,
+            write_requests: 0,
+            trim_requests: 0,
+            request_size_histogram: Vec::new(),
+            nand_reads: 0,
+            nand_writes: 0,
+            nand_erases: 0,
+            nand_ops_by_mode: [NandOpCounts::default(); 4],
+            gc_invocations: 0,
+            gc_migrated_pages: 0,
+            wl_triggers: 0,
+            wl_migrated_pages: 0,
+            rmw_reads: 0,
+            slc_writes: 0,
+            slc_overflows: 0,
+            folded_pages: 0,
+            buffer_absorbed: 0,
+            buffer_read_hits: 0,
+            buffer_lost: 0,
+            padding_pages: 0,
+            victim_valid_histogram: vec![0; PAGES_PER_BLOCK + 1],
+            free_block_histogram: Vec::new(),
+            free_block_samples: Vec::new(),
+            free_block_sample_stride: 1,
+            lba_writes: vec![0; num_lbas],
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Stats::new(self.lba_writes.len());
+    }
+
+    pub fn record_host_write(&mut self, lba: usize) {
+        self.host_writes += 1;
+        self.lba_writes[lba] += 1;
+    }
+
+    // NAND 동작 한 번 기록 (전체 카운터와 셀 모드별 카운터를 같이 올림)
+    pub fn record_nand_read(&mut self, mode: CellMode) {
+        self.nand_reads += 1;
+        self.nand_ops_by_mode[mode.bits_per_cell() - 1].reads += 1;
+    }
+
+    pub fn record_nand_write(&mut self, mode: CellMode) {
+        self.nand_writes += 1;
+        self.nand_ops_by_mode[mode.bits_per_cell() - 1].writes += 1;
+    }
+
+    pub fn record_nand_erase(&mut self, mode: CellMode) {
+        self.nand_erases += 1;
+        self.nand_ops_by_mode[mode.bits_per_cell() - 1].erases += 1;
+    }
+
+    pub fn nand_ops(&self, mode: CellMode) -> NandOpCounts {
+        self.nand_ops_by_mode[mode.bits_per_cell() - 1]
+    }
+
+    // 범위 요청 하나 기록 (페이지별 카운터는 각 FTL이 따로 올림)
+    pub fn record_read_request(&mut self, pages: usize) {
+        self.read_requests += 1;
+        self.record_request_size(pages);
+    }
+
+    pub fn record_write_request(&mut self, pages: usize) {
+        self.write_requests += 1;
+        self.record_request_size(pages);
+    }
+
+    pub fn record_trim_request(&mut self, pages: usize) {
+        self.trim_requests += 1;
+        self.record_request_size(pages);
+    }
+
+    fn record_request_size(&mut self, pages: usize) {
+        let bucket = pages.max(1).ilog2() as usize;
+        if bucket >= self.request_size_histogram.len() {
+            self.request_size_histogram.resize(bucket + 1, 0);
+        }
+        self.request_size_histogram[bucket] += 1;
+    }
+
+    // 요청 하나당 평균 페이지 수
+    pub fn mean_request_pages(&self) -> f64 {
+        let requests = self.read_requests + self.write_requests + self.trim_requests;
+        if requests == 0 { return 0.0 }
+        (self.host_reads + self.host_writes + self.host_trims) as f64 / requests as f64
+    }
+
+    pub fn waf(&self) -> f64 {
+        if self.host_writes == 0 { return 0.0 }
+        self.nand_writes as f64 / self.host_writes as f64
+    }
+
+    // GC 시작 시점 기록 (victim의 유효 페이지 수, 현재 빈 블록 수)
+    pub fn record_gc_start(&mut self, victim_valid_pages: usize, free_blocks: usize) {
+        self.gc_invocations += 1;
+        // 기본 블록보다 페이지가 많은 셀 모드(TLC/QLC)의 victim이면 히스토그램을 늘림
+        if victim_valid_pages >= self.victim_valid_histogram.len() {
+            self.victim_valid_histogram.resize(victim_valid_pages + 1, 0);
+        }
+        self.victim_valid_histogram[victim_valid_pages] += 1;
+
+        if free_blocks >= self.free_block_histogram.len() {
+            self.free_block_histogram.resize(free_blocks + 1, 0);
+        }
+        self.free_block_histogram[free_blocks] += 1;
+        // 이번 GC가 (gc_invocations - 1)번째이므로 간격의 배수일 때만 기록
+        if (self.gc_invocations - 1).is_multiple_of(self.free_block_sample_stride) {
+            while self.free_block_samples.len() >= FREE_BLOCK_SAMPLES {
+                let mut i = 0;
+                self.free_block_samples.retain(|_| { i += 1; i % 2 == 1 });
+                self.free_block_sample_stride *= 2;
+            }
+            if (self.gc_invocations - 1).is_multiple_of(self.free_block_sample_stride) {
+                self.free_block_samples.push((self.host_writes, free_blocks));
+            }
+        }
+    }
+
+    // 호스트 쓰기 중 SLC 캐시가 받은 비율
+    pub fn slc_hit_ratio(&self) -> f64 {
+        let total = self.slc_writes + self.slc_overflows;
+        if total == 0 { return 0.0 }
+        self.slc_writes as f64 / total as f64
+    }
+
+    pub fn min_free_blocks(&self) -> Option<usize> {
+        self.free_block_histogram.iter().position(|&count| count > 0)
+    }
+
+    // victim 평균 유효 페이지 수
+    pub fn mean_victim_valid_pages(&self) -> f64 {
+        if self.gc_invocations == 0 { return 0.0 }
+        let sum: u64 = self.victim_valid_histogram.iter().enumerate().map(|(n, cnt)| n as u64 * cnt).sum();
+        sum as f64 / self.gc_invocations as f64
+    }
+}
+
+fn default_sample_stride() -> u64 {
+    1
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=== FTL Statistics ===")?;
+        writeln!(f, "  Host:  reads {} | writes {} | trims {}", self.host_reads, self.host_writes, self.host_trims)?;
+        if self.read_requests + self.write_requests + self.trim_requests > 0 {
+            writeln!(f, "  Req:   reads {} | writes {} | trims {} | avg {:.1} pages",
+                     self.read_requests, self.write_requests, self.trim_requests, self.mean_request_pages())?;
+        }
+        writeln!(f, "  NAND:  reads {} | writes {} | erases {}", self.nand_reads, self.nand_writes, self.nand_erases)?;
+        writeln!(f, "  WAF:   {:.4}", self.waf())?;
+        writeln!(f, "  GC:    invocations {} | migrated pages {} | avg victim valid {:.2}",
+                 self.gc_invocations, self.gc_migrated_pages, self.mean_victim_valid_pages())?;
+        writeln!(f, "  WL:    triggers {} | migrated pages {}", self.wl_triggers, self.wl_migrated_pages)?;
+        if self.rmw_reads > 0 {
+            writeln!(f, "  RMW:   page reads {}", self.rmw_reads)?;
+        }
+        if self.slc_writes + self.slc_overflows > 0 {
+            writeln!(f, "  SLC:   cache writes {} | overflows {} | hit {:.1}% | folded pages {}",
+                     self.slc_writes, self.slc_overflows, self.slc_hit_ratio() * 100.0, self.folded_pages)?;
+        }
+        if self.buffer_absorbed + self.buffer_read_hits + self.buffer_lost + self.padding_pages > 0 {
+            writeln!(f, "  Buf:   absorbed writes {} | read hits {} | lost {} | padding pages {}",
+                     self.buffer_absorbed, self.buffer_read_hits, self.buffer_lost, self.padding_pages)?;
+        }
+        match self.min_free_blocks() {
+            Some(min) => write!(f, "  Free:  min {} blocks at GC start", min),
+            None => write!(f, "  Free:  (no GC yet)"),
+        }
+    }
+}
+
+// 스냅샷용 직렬화: #[serde(with = "super::stats::with_lba_writes")]
+// Stats 직렬화에서 빠지는 LBA별 쓰기 횟수까지 같은 객체 안에 저장
+pub mod with_lba_writes {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Stats;
+
+    #[derive(Serialize)]
+    struct StatsRef<'a> {
+        #[serde(flatten)]
+        stats: &'a Stats,
+        lba_writes: &'a [u64],
+    }
+
+    #[derive(Deserialize)]
+    struct StatsOwned {
+        #[serde(flatten)]
+        stats: Stats,
+        lba_writes: Vec<u64>,
+    }
+
+    pub fn serialize<S: Serializer>(stats: &Stats, serializer: S) -> Result<S::Ok, S::Error> {
+        StatsRef { stats, lba_writes: &stats.lba_writes }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Stats, D::Error> {
+        let StatsOwned { mut stats, lba_writes } = StatsOwned::deserialize(deserializer)?;
+        stats.lba_writes = lba_writes;
+        Ok(stats)
+    }
+}
+
+// 블록 erase 횟수 분포 (배드 블록 제외)
+#[derive(Debug, Clone, Serialize)]
+pub struct EraseDistribution {
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub p50: u32,
+    pub p90: u32,
+    pub p99: u32,
+}
+
+impl EraseDistribution {
+    pub fn from_blocks(blocks: &[Block]) -> Self {
+        let mut counts: Vec<u32> = blocks.iter().filter(|b| !b.is_bad).map(|b| b.erase_count).collect();
+        if counts.is_empty() {
+            return EraseDistribution { min: 0, max: 0, mean: 0.0, std_dev: 0.0, p50: 0, p90: 0, p99: 0 };
+        }
+        counts.sort_unstable();
+
+        let n = counts.len() as f64;
+        let mean = counts.iter().map(|&c| c as f64).sum::<f64>() / n;
+        let variance = counts.iter().map(|&c| (c as f64 - mean).powi(2)).sum::<f64>() / n;
+        // nearest-rank 백분위수
+        let pct = |p: f64| counts[(((p / 100.0) * n).ceil() as usize).clamp(1, counts.len()) - 1];
+
+        EraseDistribution {
+            min: counts[0],
+            max: counts[counts.len() - 1],
+            mean,
+            std_dev: variance.sqrt(),
+            p50: pct(50.0),
+            p90: pct(90.0),
+            p99: pct(99.0),
+        }
+    }
+}
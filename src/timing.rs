@@ -0,0 +1,364 @@
+// NAND 동작 지연 시간 모델 + GC 스케줄링을 적용한 시간 기반 SSD 래퍼
+// 시간 단위는 모두 마이크로초(us)입니다.
+use super::block::Block;
+use super::cell::CellMode;
+use super::device::BlockDevice;
+use super::gc::*;
+use super::ssd_basic::SSD;
+use super::stats::{NandOpCounts, Stats};
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TimingModel {
+    pub read_us: u64,
+    pub program_us: u64,
+    pub erase_us: u64,
+}
+
+impl Default for TimingModel {
+    // 대략적인 낸드 수치 (기본 셀 모드 블록 기준, 다른 모드는 for_mode로 조정)
+    fn default() -> Self {
+        TimingModel {
+            read_us: 50,
+            program_us: 500,
+            erase_us: 3000,
+        }
+    }
+}
+
+impl TimingModel {
+    // 이 모델은 기본 셀 모드(MLC) 블록의 지연 시간
+    // 다른 모드의 블록은 CellMode::timing()의 모드 간 비율만큼 늘리거나 줄임 (SLC는 빠르고 QLC는 느림)
+    pub fn for_mode(&self, mode: CellMode) -> TimingModel {
+        let base = CellMode::default().timing();
+        let target = mode.timing();
+        TimingModel {
+            read_us: self.read_us * target.read_us / base.read_us,
+            program_us: self.program_us * target.program_us / base.program_us,
+            erase_us: self.erase_us * target.erase_us / base.erase_us,
+        }
+    }
+}
+
+// 요청 하나를 처리하는 동안 일어난 NAND 동작 수 (셀 모드별 통계 카운터 차이로 계산)
+#[derive(Debug, Clone, Copy)]
+struct NandOps {
+    by_mode: [NandOpCounts; 4],
+}
+
+impl NandOps {
+    fn of(stats: &Stats) -> Self {
+        NandOps { by_mode: stats.nand_ops_by_mode }
+    }
+
+    // 동작마다 그 블록의 셀 모드 지연 시간으로 계산
+    fn cost_until(&self, stats: &Stats, timing: &TimingModel) -> u64 {
+        CellMode::ALL.iter().map(|&mode| {
+            let (before, after) = (self.by_mode[mode.bits_per_cell() - 1], stats.nand_ops(mode));
+            let timing = timing.for_mode(mode);
+            (after.reads - before.reads) * timing.read_us
+                + (after.writes - before.writes) * timing.program_us
+                + (after.erases - before.erases) * timing.erase_us
+        }).sum()
+    }
+}
+
+// 요청별 지연 시간 샘플을 모아 백분위수를 계산
+#[derive(Debug, Default, Clone)]
+pub struct LatencyStats {
+    samples: Vec<u64>,
+}
+
+impl LatencyStats {
+    pub fn new() -> Self {
+        LatencyStats { samples: Vec::new() }
+    }
+
+    pub fn record(&mut self, latency_us: u64) {
+        self.samples.push(latency_us);
+    }
+
+    pub fn count(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.samples.is_empty() { return 0.0 }
+        self.samples.iter().sum::<u64>() as f64 / self.samples.len() as f64
+    }
+
+    pub fn max(&self) -> u64 {
+        self.samples.iter().copied().max().unwrap_or(0)
+    }
+
+    // p: 0.0 ~ 100.0 (예: 99.9), nearest-rank 방식
+    pub fn percentile(&self, p: f64) -> u64 {
+        if self.samples.is_empty() { return 0 }
+        let mut sorted = self.samples.clone();
+        sorted.sort_unstable();
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+}
+
+// 호스트 요청 도착 시각을 받아 장치 busy 시간, GC step, 지연 시간을 시뮬레이션
+// - 도착 전 유휴 시간에는 스케줄러 정책에 따라 백그라운드 GC step을 진행
+// - 이미 시작한 step은 끝까지 수행 (선점 단위 = step 1개)
+// - 빈 블록이 바닥나면 정책과 상관없이 포그라운드 GC로 블록 1개를 확보 (요청 도중 내부 SSD가 돌린 GC step도 포그라운드로 셈)
+pub struct TimedSSD {
+    ssd: SSD,
+    timing: TimingModel,
+    scheduler: GcScheduler,
+    now_us: u64,
+    read_latency: LatencyStats,
+    write_latency: LatencyStats,
+    background_steps: u64,
+    foreground_steps: u64,
+    // BlockDevice로 쓸 때 다음 요청의 도착 시각 (set_arrival로 지정)
+    next_arrival_us: u64,
+}
+
+impl TimedSSD {
+    pub fn new(ssd: SSD, timing: TimingModel, scheduler: GcScheduler) -> Self {
+        TimedSSD {
+            ssd,
+            timing,
+            scheduler,
+            now_us: 0,
+            read_latency: LatencyStats::new(),
+            write_latency: LatencyStats::new(),
+            background_steps: 0,
+            foreground_steps: 0,
+            next_arrival_us: 0,
+        }
+    }
+
+    // 쓰기 요청: 지연 시간(us)을 반환
+    pub fn write(&mut self, lba: usize, data: u32, arrival_us: u64) -> Result<u64, String> {
+        self.write_range(lba, std::slice::from_ref(&data), arrival_us)
+    }
+
+    // 범위 쓰기 요청: 마지막 페이지가 끝날 때까지의 지연 시간(us)을 한 번만 기록
+    // 요청 도중 빈 블록이 바닥나면 내부 SSD가 Stop-the-world GC를 돌리고 그 시간도 지연에 포함됨
+    pub fn write_range(&mut self, start_lba: usize, data: &[u32], arrival_us: u64) -> Result<u64, String> {
+        self.run_background(arrival_us)?;
+        let start = self.now_us.max(arrival_us);
+        self.now_us = start;
+
+        // 포그라운드 GC: 호스트 쓰기가 GC를 기다리는 구간 (Stop-the-world와 동일)
+        while self.ssd.count_free_blocks() == 0 {
+            let (_, cost) = self.timed_gc_step()?;
+            self.now_us += cost;
+            self.foreground_steps += 1;
+        }
+
+        // 쓰기 버퍼가 있으면 버퍼에만 들어가거나(NAND 동작 없음) 밀려난 페이지들을 프로그램함
+        // 범위가 Active Block을 넘어가면 내부 SSD가 도중에 GC를 돌릴 수 있음 (시간은 NAND 동작 수에 포함됨)
+        let before = NandOps::of(self.ssd.stats());
+        let steps_before = self.ssd.gc_steps();
+        self.ssd.write_range(start_lba, data)?;
+        self.now_us += before.cost_until(self.ssd.stats(), &self.timing);
+        self.foreground_steps += self.ssd.gc_steps() - steps_before;
+
+        let latency = self.now_us - arrival_us;
+        self.write_latency.record(latency);
+        Ok(latency)
+    }
+
+    // 읽기 요청: (데이터, 지연 시간) 반환
+    pub fn read(&mut self, lba: usize, arrival_us: u64) -> Result<(Option<u32>, u64), String> {
+        let (data, latency) = self.read_range(lba, 1, arrival_us)?;
+        Ok((data[0], latency))
+    }
+
+    // 범위 읽기 요청: (페이지별 데이터, 요청 지연 시간) 반환
+    pub fn read_range(&mut self, start_lba: usize, len: usize, arrival_us: u64) -> Result<(Vec<Option<u32>>, u64), String> {
+        self.run_background(arrival_us)?;
+        self.now_us = self.now_us.max(arrival_us);
+
+        // 매핑이 없거나 쓰기 버퍼에서 찾으면 NAND 읽기 없음
+        let before = NandOps::of(self.ssd.stats());
+        let data = self.ssd.read_range(start_lba, len)?;
+        self.now_us += before.cost_until(self.ssd.stats(), &self.timing);
+
+        let latency = self.now_us - arrival_us;
+        self.read_latency.record(latency);
+        Ok((data, latency))
+    }
+
+    // Trim 요청: 매핑만 지우므로 NAND 동작 없음 (앞선 작업이 끝날 때까지만 대기)
+    pub fn trim(&mut self, lba: usize, arrival_us: u64) -> Result<u64, String> {
+        self.trim_range(lba, 1, arrival_us)
+    }
+
+    pub fn trim_range(&mut self, start_lba: usize, len: usize, arrival_us: u64) -> Result<u64, String> {
+        self.run_background(arrival_us)?;
+        self.now_us = self.now_us.max(arrival_us);
+
+        self.ssd.trim_range(start_lba, len)?;
+        Ok(self.now_us - arrival_us)
+    }
+
+    // Flush 요청: 쓰기 버퍼를 NAND에 내려쓰는 시간만큼 대기, 지연 시간(us)을 반환
+    pub fn flush(&mut self, arrival_us: u64) -> Result<u64, String> {
+        self.run_background(arrival_us)?;
+        self.now_us = self.now_us.max(arrival_us);
+
+        let before = NandOps::of(self.ssd.stats());
+        let steps_before = self.ssd.gc_steps();
+        self.ssd.flush()?;
+        self.now_us += before.cost_until(self.ssd.stats(), &self.timing);
+        self.foreground_steps += self.ssd.gc_steps() - steps_before;
+        Ok(self.now_us - arrival_us)
+    }
+
+    // until_us 전까지 남는 유휴 시간 동안 백그라운드 GC 진행
+    fn run_background(&mut self, until_us: u64) -> Result<(), String> {
+        while self.now_us < until_us
+            && self.scheduler.wants_background(self.ssd.count_free_blocks(), self.ssd.gc_in_progress())
+        {
+            // 새로 시작할 victim이 있어도 무효 페이지가 없으면 옮겨봤자 공간이 안 생김
+            if !self.ssd.gc_in_progress() && !self.ssd.has_reclaimable_block() {
+                break;
+            }
+
+            let start = self.scheduler.next_step_time(self.now_us);
+            if start >= until_us {
+                // 토큰이 차기 전에 호스트 요청이 도착함
+                break;
+            }
+
+            self.scheduler.consume(start);
+            let (step, cost) = self.timed_gc_step()?;
+            self.now_us = start + cost;
+            self.background_steps += 1;
+            debug!("[Timing] Background GC step {:?} done at {}us", step, self.now_us);
+        }
+        Ok(())
+    }
+
+    // GC step 하나와 그 소요 시간 (복사 = victim 블록 읽기 + Active Block 프로그램, 각자의 셀 모드 기준)
+    fn timed_gc_step(&mut self) -> Result<(GcStep, u64), String> {
+        let before = NandOps::of(self.ssd.stats());
+        let step = self.ssd.gc_step()?;
+        Ok((step, before.cost_until(self.ssd.stats(), &self.timing)))
+    }
+
+    pub fn ssd(&self) -> &SSD {
+        &self.ssd
+    }
+
+    pub fn ssd_mut(&mut self) -> &mut SSD {
+        &mut self.ssd
+    }
+
+    // 타이밍 래퍼를 벗기고 내부 SSD를 돌려줌 (스냅샷 저장 등)
+    pub fn into_inner(self) -> SSD {
+        self.ssd
+    }
+
+    pub fn now_us(&self) -> u64 {
+        self.now_us
+    }
+
+    // FTL 통계와 지연 시간 기록을 함께 초기화 (warm-up 제외용)
+    pub fn reset_stats(&mut self) {
+        self.ssd.reset_stats();
+        self.read_latency = LatencyStats::new();
+        self.write_latency = LatencyStats::new();
+        self.background_steps = 0;
+        self.foreground_steps = 0;
+    }
+
+    pub fn read_latency(&self) -> &LatencyStats {
+        &self.read_latency
+    }
+
+    pub fn write_latency(&self) -> &LatencyStats {
+        &self.write_latency
+    }
+
+    pub fn background_steps(&self) -> u64 {
+        self.background_steps
+    }
+
+    pub fn foreground_steps(&self) -> u64 {
+        self.foreground_steps
+    }
+}
+
+// 요청 도착 시각은 set_arrival로 받음 (지정하지 않으면 0 = 앞 요청이 끝나자마자 도착하는 closed-loop)
+impl BlockDevice for TimedSSD {
+    fn read(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        TimedSSD::read(self, lba, self.next_arrival_us).map(|(data, _)| data)
+    }
+
+    fn write(&mut self, lba: usize, data: u32) -> Result<(), String> {
+        TimedSSD::write(self, lba, data, self.next_arrival_us).map(|_| ())
+    }
+
+    fn trim(&mut self, lba: usize) -> Result<(), String> {
+        TimedSSD::trim(self, lba, self.next_arrival_us).map(|_| ())
+    }
+
+    fn write_range(&mut self, start_lba: usize, data: &[u32]) -> Result<(), String> {
+        TimedSSD::write_range(self, start_lba, data, self.next_arrival_us).map(|_| ())
+    }
+
+    // read-modify-write의 읽기는 같은 요청의 일부: 도착 시각부터 시작해서 뒤따르는 쓰기 지연에 포함됨
+    fn read_for_update(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        self.run_background(self.next_arrival_us)?;
+        self.now_us = self.now_us.max(self.next_arrival_us);
+
+        let before = NandOps::of(self.ssd.stats());
+        let data = self.ssd.read_for_update(lba)?;
+        self.now_us += before.cost_until(self.ssd.stats(), &self.timing);
+        Ok(data)
+    }
+
+    fn read_range(&mut self, start_lba: usize, len: usize) -> Result<Vec<Option<u32>>, String> {
+        TimedSSD::read_range(self, start_lba, len, self.next_arrival_us).map(|(data, _)| data)
+    }
+
+    fn trim_range(&mut self, start_lba: usize, len: usize) -> Result<(), String> {
+        TimedSSD::trim_range(self, start_lba, len, self.next_arrival_us).map(|_| ())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        TimedSSD::flush(self, self.next_arrival_us).map(|_| ())
+    }
+
+    fn num_lbas(&self) -> usize {
+        self.ssd.num_lbas()
+    }
+
+    fn stats(&self) -> &Stats {
+        self.ssd.stats()
+    }
+
+    fn reset_stats(&mut self) {
+        TimedSSD::reset_stats(self)
+    }
+
+    fn blocks(&self) -> &[Block] {
+        self.ssd.blocks()
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        self.ssd.check_invariants()
+    }
+
+    // FTL 상태만 저장 (시계, 지연 시간 기록은 포함하지 않음)
+    fn save_snapshot(&self, path: &str) -> Result<(), String> {
+        self.ssd.save_snapshot(path)
+    }
+
+    fn set_arrival(&mut self, arrival_us: u64) {
+        self.next_arrival_us = arrival_us;
+    }
+
+    fn clock_us(&self) -> Option<u64> {
+        Some(self.now_us)
+    }
+}
@@ -0,0 +1,288 @@
+// 실제 시스템에서 수집한 블록 I/O 트레이스를 읽어 SSD에 재생(replay)
+// 지원 포맷:
+//   - MSR Cambridge CSV : Timestamp,Hostname,DiskNumber,Type,Offset,Size,ResponseTime
+//                         (Timestamp는 Windows FILETIME, 100ns 단위 / Offset, Size는 바이트)
+//   - SNIA/SPC-1 ASCII  : ASU,LBA,Size,Opcode,Timestamp
+//                         (LBA는 512B 섹터 단위 / Size는 바이트 / Timestamp는 초)
+//   - blkparse 텍스트   : dev cpu seq time pid action RWBS sector + nsectors [process]
+//                         (Q(queue) 이벤트만 사용 / sector는 512B 단위 / time은 초)
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use super::device::BlockDevice;
+use super::timing::LatencyStats;
+use log::{debug, warn};
+
+pub const SECTOR_SIZE: u64 = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TraceFormat {
+    Msr,
+    Spc,
+    Blkparse,
+}
+
+impl TraceFormat {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "msr" => Ok(TraceFormat::Msr),
+            "spc" | "snia" => Ok(TraceFormat::Spc),
+            "blkparse" | "blktrace" => Ok(TraceFormat::Blkparse),
+            _ => Err(format!("Unknown trace format: {}", name)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TraceOp {
+    Read,
+    Write,
+    Trim,
+}
+
+// 트레이스 한 줄 = 호스트 요청 하나 (바이트 단위 그대로 보관)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceRecord {
+    pub timestamp_us: u64,
+    pub op: TraceOp,
+    pub offset: u64,
+    pub size: u64,
+}
+
+// 한 줄 파싱: 주석/헤더/관심 없는 이벤트는 Ok(None)
+pub fn parse_line(format: TraceFormat, line: &str) -> Result<Option<TraceRecord>, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+
+    match format {
+        TraceFormat::Msr => parse_msr(line),
+        TraceFormat::Spc => parse_spc(line),
+        TraceFormat::Blkparse => parse_blkparse(line),
+    }
+}
+
+// 주소 계산이 u64를 넘는 레코드는 손상된 줄로 보고 에러
+fn byte_range(offset: Option<u64>, size: Option<u64>) -> Result<(u64, u64), String> {
+    match (offset, size) {
+        (Some(offset), Some(size)) if offset.checked_add(size).is_some() => Ok((offset, size)),
+        _ => Err("Byte range overflows a 64-bit offset".to_string()),
+    }
+}
+
+fn parse_num<T: std::str::FromStr>(field: &str, what: &str) -> Result<T, String> {
+    field.trim().parse::<T>().map_err(|_| format!("Invalid {}: '{}'", what, field))
+}
+
+fn parse_msr(line: &str) -> Result<Option<TraceRecord>, String> {
+    let fields: Vec<&str> = line.split(',').collect();
+    if fields.len() < 7 {
+        return Err(format!("MSR: expected 7 fields, got {}", fields.len()));
+    }
+    // 헤더 줄은 건너뜀
+    if fields[0].trim().eq_ignore_ascii_case("timestamp") {
+        return Ok(None);
+    }
+
+    let filetime: u64 = parse_num(fields[0], "timestamp")?;
+    let op = match fields[3].trim().to_ascii_lowercase().as_str() {
+        "read" => TraceOp::Read,
+        "write" => TraceOp::Write,
+        other => return Err(format!("MSR: unknown type '{}'", other)),
+    };
+
+    let (offset, size) = byte_range(Some(parse_num(fields[4], "offset")?), Some(parse_num(fields[5], "size")?))?;
+    Ok(Some(TraceRecord {
+        timestamp_us: filetime / 10,
+        op,
+        offset,
+        size,
+    }))
+}
+
+fn parse_spc(line: &str) -> Result<Option<TraceRecord>, String> {
+    let fields: Vec<&str> = line.split(',').collect();
+    if fields.len() < 5 {
+        return Err(format!("SPC: expected 5 fields, got {}", fields.len()));
+    }
+
+    let lba: u64 = parse_num(fields[1], "lba")?;
+    let op = match fields[3].trim() {
+        "r" | "R" => TraceOp::Read,
+        "w" | "W" => TraceOp::Write,
+        other => return Err(format!("SPC: unknown opcode '{}'", other)),
+    };
+    let seconds: f64 = parse_num(fields[4], "timestamp")?;
+    let (offset, size) = byte_range(lba.checked_mul(SECTOR_SIZE), Some(parse_num(fields[2], "size")?))?;
+
+    Ok(Some(TraceRecord {
+        timestamp_us: (seconds * 1_000_000.0) as u64,
+        op,
+        offset,
+        size,
+    }))
+}
+
+fn parse_blkparse(line: &str) -> Result<Option<TraceRecord>, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    // blkparse 마지막의 요약 통계 등은 형식이 달라 건너뜀
+    if fields.len() < 10 || fields[8] != "+" || fields[5] != "Q" {
+        return Ok(None);
+    }
+
+    let rwbs = fields[6];
+    let op = if rwbs.contains('D') {
+        TraceOp::Trim
+    } else if rwbs.contains('W') {
+        TraceOp::Write
+    } else if rwbs.contains('R') {
+        TraceOp::Read
+    } else {
+        // flush(F) 등 데이터가 없는 요청
+        return Ok(None);
+    };
+    let seconds: f64 = parse_num(fields[3], "time")?;
+    let sector: u64 = parse_num(fields[7], "sector")?;
+    let nsectors: u64 = parse_num(fields[9], "sector count")?;
+    let (offset, size) = byte_range(sector.checked_mul(SECTOR_SIZE), nsectors.checked_mul(SECTOR_SIZE))?;
+
+    Ok(Some(TraceRecord {
+        timestamp_us: (seconds * 1_000_000.0) as u64,
+        op,
+        offset,
+        size,
+    }))
+}
+
+// 트레이스 파일 전체를 읽음 (에러 메시지에 줄 번호 포함)
+pub fn load_trace(path: &str, format: TraceFormat) -> Result<Vec<TraceRecord>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let reader = BufReader::new(file);
+
+    let mut records = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("{}:{}: {}", path, i + 1, e))?;
+        if let Some(record) = parse_line(format, &line).map_err(|e| format!("{}:{}: {}", path, i + 1, e))? {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+pub struct ReplayConfig {
+    // SSD의 LBA 하나가 담당하는 바이트 수 (트레이스 오프셋 -> LBA 변환용)
+    pub lba_size: u64,
+    // 트레이스 주소가 SSD 용량을 넘으면 num_lbas로 나눈 나머지로 접음 (false면 에러)
+    pub wrap_lbas: bool,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        ReplayConfig {
+            lba_size: 4096,
+            wrap_lbas: true,
+        }
+    }
+}
+
+impl ReplayConfig {
+    pub fn new(lba_size: u64, wrap_lbas: bool) -> Result<Self, String> {
+        if lba_size == 0 {
+            return Err("LBA size must be at least 1 byte".to_string());
+        }
+        Ok(ReplayConfig { lba_size, wrap_lbas })
+    }
+
+    // 바이트 범위 [offset, offset + size)를 덮는 LBA 구간들 (시작 LBA, 개수)로 변환 (앞뒤를 lba_size에 맞춰 확장)
+    // 보통은 연속 구간 하나, 용량 끝에서 감아 돌면(wrap) 둘로 나뉨
+    // 요청 하나가 SSD 용량보다 크면 같은 LBA를 여러 번 덮게 되므로 에러
+    pub fn lba_range(&self, record: &TraceRecord, num_lbas: usize) -> Result<Vec<(usize, usize)>, String> {
+        if num_lbas == 0 {
+            return Err("Cannot map trace addresses onto an SSD with 0 LBAs".to_string());
+        }
+        if self.lba_size == 0 {
+            return Err("LBA size must be at least 1 byte".to_string());
+        }
+        let end = record.offset.checked_add(record.size.max(1))
+            .ok_or_else(|| format!("Trace range {}+{} overflows a 64-bit offset", record.offset, record.size))?;
+        let first = record.offset / self.lba_size;
+        let count = end.div_ceil(self.lba_size) - first;
+        let n = num_lbas as u64;
+        if count > n {
+            return Err(format!("Trace request of {} LBAs exceeds SSD capacity ({} LBAs)", count, num_lbas));
+        }
+
+        if first + count <= n {
+            return Ok(vec![(first as usize, count as usize)]);
+        }
+        if !self.wrap_lbas {
+            return Err(format!("Trace LBA {} exceeds SSD capacity ({} LBAs)", first.max(n), num_lbas));
+        }
+        let start = first % n;
+        let head = count.min(n - start);
+        let mut runs = vec![(start as usize, head as usize)];
+        if head < count {
+            runs.push((0, (count - head) as usize));
+        }
+        Ok(runs)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ReplaySummary {
+    pub requests: u64,
+    pub read_pages: u64,
+    pub write_pages: u64,
+    pub trim_pages: u64,
+    // 한 번도 쓰지 않은 LBA를 읽은 횟수
+    pub unmapped_reads: u64,
+    // 시간 모델 사용 시 요청 단위 지연 시간 (요청 내 페이지 중 가장 늦게 끝난 시점 기준)
+    pub request_latency: LatencyStats,
+}
+
+// 트레이스를 순서대로 재생
+// 시간 모델이 있는 장치(clock_us가 Some)는 트레이스 타임스탬프(첫 요청 기준 상대 시각)를 도착 시각으로 쓰고
+// 요청 단위 지연 시간을 기록, 시간 개념이 없는 장치는 도착 시각을 무시하고 순서대로만 실행
+pub fn replay<D: BlockDevice + ?Sized>(
+    device: &mut D,
+    records: &[TraceRecord],
+    config: &ReplayConfig,
+) -> Result<ReplaySummary, String> {
+    let mut summary = ReplaySummary::default();
+    let base_us = records.first().map(|r| r.timestamp_us).unwrap_or(0);
+
+    for (i, record) in records.iter().enumerate() {
+        if record.timestamp_us < base_us {
+            warn!("[Trace] Record {} goes back in time, issuing immediately", i);
+        }
+        let arrival_us = record.timestamp_us.saturating_sub(base_us);
+        device.set_arrival(arrival_us);
+
+        for (start, len) in config.lba_range(record, device.num_lbas())? {
+            match record.op {
+                TraceOp::Read => {
+                    summary.read_pages += len as u64;
+                    let data = device.read_range(start, len)?;
+                    summary.unmapped_reads += data.iter().filter(|d| d.is_none()).count() as u64;
+                }
+                TraceOp::Write => {
+                    summary.write_pages += len as u64;
+                    device.write_range(start, &vec![i as u32; len])?;
+                }
+                TraceOp::Trim => {
+                    summary.trim_pages += len as u64;
+                    device.trim_range(start, len)?;
+                }
+            }
+        }
+
+        if let Some(now_us) = device.clock_us() {
+            summary.request_latency.record(now_us.saturating_sub(arrival_us));
+        }
+        summary.requests += 1;
+    }
+
+    debug!("[Trace] Replayed {:?}", summary);
+    Ok(summary)
+}
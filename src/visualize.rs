@@ -0,0 +1,216 @@
+// 블록/LBA 상태 시각화
+// - 터미널: 전체 블록의 유효 페이지 비율과 erase 횟수를 한 글자씩 압축한 히트맵
+// - 이미지: 페이지 단위 블록 상태, 블록별 erase 횟수, LBA 업데이트 빈도를 PPM(P6) 또는 SVG로 저장
+// hot/cold 데이터가 섞인 블록이나 마모 불균형을 눈으로 확인하기 위한 용도입니다.
+use std::fmt::Write as _;
+use std::fs;
+
+use super::block::{Block, PAGES_PER_BLOCK};
+use super::page::PageState;
+
+// 밝기 순서대로 0% ~ 100%
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+fn shade(ratio: f64) -> char {
+    let idx = (ratio.clamp(0.0, 1.0) * (SHADES.len() - 1) as f64).round() as usize;
+    SHADES[idx]
+}
+
+// 한 줄에 width개 블록씩, 위에는 유효 페이지 비율 / 아래는 erase 횟수(최댓값 대비)
+pub fn terminal_heatmap(blocks: &[Block], width: usize) -> String {
+    let width = width.max(1);
+    let max_erase = blocks.iter().filter(|b| !b.is_bad).map(|b| b.erase_count).max().unwrap_or(0);
+    let mut out = String::new();
+
+    let _ = writeln!(out, "=== Block Heatmap ({} blocks) ===", blocks.len());
+    let _ = writeln!(out, "Valid ratio  (' '=0% .. '█'=100%, '·'=free, 'X'=bad)");
+    for (row, chunk) in blocks.chunks(width).enumerate() {
+        let line: String = chunk
+            .iter()
+            .map(|b| {
+                if b.is_bad {
+                    'X'
+                } else if b.pages.iter().all(|p| p.state == PageState::Free) {
+                    '·'
+                } else {
+                    shade(b.count_valid_pages() as f64 / b.pages.len() as f64)
+                }
+            })
+            .collect();
+        let _ = writeln!(out, "{:>6} |{}|", row * width, line);
+    }
+
+    let _ = writeln!(out, "Erase count  (' '=0 .. '█'={}, 'X'=bad)", max_erase);
+    for (row, chunk) in blocks.chunks(width).enumerate() {
+        let line: String = chunk
+            .iter()
+            .map(|b| {
+                if b.is_bad {
+                    'X'
+                } else if max_erase == 0 {
+                    shade(0.0)
+                } else {
+                    shade(b.erase_count as f64 / max_erase as f64)
+                }
+            })
+            .collect();
+        let _ = writeln!(out, "{:>6} |{}|", row * width, line);
+    }
+    out
+}
+
+// 0.0(차가움, 파랑) ~ 1.0(뜨거움, 빨강) 색상표
+pub fn heat_color(t: f64) -> [u8; 3] {
+    let t = t.clamp(0.0, 1.0);
+    // 파랑 -> 청록 -> 초록 -> 노랑 -> 빨강
+    let stops: [[f64; 3]; 5] = [
+        [0.0, 0.0, 255.0],
+        [0.0, 255.0, 255.0],
+        [0.0, 255.0, 0.0],
+        [255.0, 255.0, 0.0],
+        [255.0, 0.0, 0.0],
+    ];
+    let pos = t * (stops.len() - 1) as f64;
+    let i = (pos.floor() as usize).min(stops.len() - 2);
+    let frac = pos - i as f64;
+
+    let mut rgb = [0u8; 3];
+    for c in 0..3 {
+        rgb[c] = (stops[i][c] + (stops[i + 1][c] - stops[i][c]) * frac).round() as u8;
+    }
+    rgb
+}
+
+// 단순 RGB 래스터 이미지 (PPM/SVG 출력 공용)
+pub struct Raster {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Raster {
+    pub fn new(width: usize, height: usize) -> Self {
+        Raster {
+            width,
+            height,
+            pixels: vec![[0, 0, 0]; width * height],
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, rgb: [u8; 3]) {
+        self.pixels[y * self.width + x] = rgb;
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> [u8; 3] {
+        self.pixels[y * self.width + x]
+    }
+
+    // 바이너리 PPM (P6), 픽셀 하나를 scale x scale로 확대
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let scale = scale.max(1);
+        let (w, h) = (self.width * scale, self.height * scale);
+        let mut out = format!("P6\n{} {}\n255\n", w, h).into_bytes();
+        for y in 0..h {
+            for x in 0..w {
+                out.extend_from_slice(&self.get(x / scale, y / scale));
+            }
+        }
+        out
+    }
+
+    // 독립 실행 가능한 SVG (같은 색이 이어지는 가로 구간은 rect 하나로 합침)
+    pub fn to_svg(&self, scale: usize) -> String {
+        let scale = scale.max(1);
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" shape-rendering="crispEdges">"#,
+            self.width * scale,
+            self.height * scale
+        );
+        for y in 0..self.height {
+            let mut x = 0;
+            while x < self.width {
+                let rgb = self.get(x, y);
+                let mut run = 1;
+                while x + run < self.width && self.get(x + run, y) == rgb {
+                    run += 1;
+                }
+                let _ = writeln!(
+                    out,
+                    r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#{:02x}{:02x}{:02x}"/>"##,
+                    x * scale, y * scale, run * scale, scale, rgb[0], rgb[1], rgb[2]
+                );
+                x += run;
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    // 확장자(.ppm / .svg)에 따라 저장
+    pub fn save(&self, path: &str, scale: usize) -> Result<(), String> {
+        let bytes = if path.ends_with(".svg") {
+            self.to_svg(scale).into_bytes()
+        } else if path.ends_with(".ppm") {
+            self.to_ppm(scale)
+        } else {
+            return Err(format!("Unsupported image format: {} (use .ppm or .svg)", path));
+        };
+        fs::write(path, bytes).map_err(|e| format!("Failed to write {}: {}", path, e))
+    }
+}
+
+// 블록 하나 = 가로 한 줄, 페이지 하나 = 픽셀 하나 (Valid=초록, Invalid=빨강, Free=검정, 배드=회색)
+pub fn block_state_raster(blocks: &[Block]) -> Raster {
+    let width = blocks.iter().map(|b| b.pages.len()).max().unwrap_or(PAGES_PER_BLOCK);
+    let mut raster = Raster::new(width, blocks.len());
+    for (y, block) in blocks.iter().enumerate() {
+        for (x, page) in block.pages.iter().enumerate() {
+            let rgb = if block.is_bad {
+                [96, 96, 96]
+            } else {
+                match page.state {
+                    PageState::Valid => [40, 200, 60],
+                    PageState::Invalid => [220, 50, 40],
+                    PageState::Free => [0, 0, 0],
+                }
+            };
+            raster.set(x, y, rgb);
+        }
+    }
+    raster
+}
+
+// 블록별 erase 횟수 (한 줄에 cols개 블록, 최댓값 대비 색상)
+pub fn erase_count_raster(blocks: &[Block], cols: usize) -> Raster {
+    let max_erase = blocks.iter().filter(|b| !b.is_bad).map(|b| b.erase_count).max().unwrap_or(0).max(1);
+    let values: Vec<Option<f64>> = blocks
+        .iter()
+        .map(|b| (!b.is_bad).then(|| b.erase_count as f64 / max_erase as f64))
+        .collect();
+    grid_raster(&values, cols)
+}
+
+// LBA별 업데이트 빈도 (로그 스케일, 한 줄에 cols개 LBA)
+pub fn lba_frequency_raster(lba_writes: &[u64], cols: usize) -> Raster {
+    let max_log = lba_writes.iter().map(|&c| (c as f64).ln_1p()).fold(0.0, f64::max).max(f64::MIN_POSITIVE);
+    let values: Vec<Option<f64>> = lba_writes
+        .iter()
+        .map(|&c| (c > 0).then(|| (c as f64).ln_1p() / max_log))
+        .collect();
+    grid_raster(&values, cols)
+}
+
+// 값 목록을 cols열 격자로 배치 (None = 검정: 배드 블록 / 한 번도 안 쓴 LBA)
+fn grid_raster(values: &[Option<f64>], cols: usize) -> Raster {
+    let cols = cols.max(1);
+    let rows = values.len().div_ceil(cols).max(1);
+    let mut raster = Raster::new(cols, rows);
+    for (i, value) in values.iter().enumerate() {
+        if let Some(t) = value {
+            raster.set(i % cols, i / cols, heat_color(*t));
+        }
+    }
+    raster
+}
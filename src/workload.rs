@@ -0,0 +1,348 @@
+// 합성 워크로드 생성기
+// 주소 패턴(순차, 균등 랜덤, Zipfian, Hot/Cold, 이동하는 Hotspot) + 읽기/쓰기/Trim 비율 + 요청 크기를 조합하고,
+// 모든 생성기는 seed로 초기화되므로 같은 seed면 항상 같은 요청열이 나옵니다.
+// 생성기는 무한 Iterator이고, Phased로 "순차 채우기 -> 80/20 랜덤 덮어쓰기" 같은 단계를 이어붙입니다.
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::device::BlockDevice;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum IoOp {
+    Read,
+    Write,
+    Trim,
+}
+
+// 호스트 요청 하나: lba부터 len개의 연속된 LBA
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IoRequest {
+    pub op: IoOp,
+    pub lba: usize,
+    pub len: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AddressPattern {
+    // start부터 차례대로, 끝에 도달하면 0으로 돌아감
+    Sequential { start: usize },
+    Uniform,
+    // theta: 0.0(균등) ~ 0.99(매우 치우침), 낮은 LBA일수록 인기 많음
+    Zipfian { theta: f64 },
+    // 전체 쓰기 중 hot_access_pct%가 앞쪽 hot_lba_pct%의 LBA에 집중 (예: 80/20)
+    HotCold { hot_access_pct: f64, hot_lba_pct: f64 },
+    // HotCold와 같지만 shift_every 요청마다 hot 영역이 hot 영역 크기만큼 뒤로 이동
+    ShiftingHotspot { hot_access_pct: f64, hot_lba_pct: f64, shift_every: u64 },
+}
+
+impl AddressPattern {
+    // 명령줄용 짧은 표기: seq | uniform | zipf:0.99 | hotcold:80/20 | shifting:80/20/10000
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (kind, args) = spec.split_once(':').unwrap_or((spec, ""));
+        let nums: Vec<f64> = if args.is_empty() {
+            Vec::new()
+        } else {
+            args.split('/')
+                .map(|a| a.parse::<f64>().map_err(|_| format!("Invalid number '{}' in pattern '{}'", a, spec)))
+                .collect::<Result<_, _>>()?
+        };
+
+        match (kind, nums.as_slice()) {
+            ("seq" | "sequential", []) => Ok(AddressPattern::Sequential { start: 0 }),
+            ("uniform", []) => Ok(AddressPattern::Uniform),
+            ("zipf" | "zipfian", [theta]) => {
+                check_theta(*theta)?;
+                Ok(AddressPattern::Zipfian { theta: *theta })
+            }
+            ("hotcold", [access, lbas]) => Ok(AddressPattern::HotCold { hot_access_pct: *access, hot_lba_pct: *lbas }),
+            ("shifting", [access, lbas, every]) => Ok(AddressPattern::ShiftingHotspot {
+                hot_access_pct: *access,
+                hot_lba_pct: *lbas,
+                shift_every: *every as u64,
+            }),
+            _ => Err(format!(
+                "Unknown pattern '{}' (seq | uniform | zipf:THETA | hotcold:ACCESS/LBAS | shifting:ACCESS/LBAS/EVERY)",
+                spec
+            )),
+        }
+    }
+}
+
+// 요청 종류 비율 (합이 1일 필요는 없음, 상대 가중치)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OpMix {
+    pub read: f64,
+    pub write: f64,
+    pub trim: f64,
+}
+
+impl Default for OpMix {
+    fn default() -> Self {
+        OpMix { read: 0.0, write: 1.0, trim: 0.0 }
+    }
+}
+
+// 요청 하나의 크기 (LBA 개수)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RequestSize {
+    Fixed { lbas: usize },
+    Uniform { min: usize, max: usize },
+}
+
+impl Default for RequestSize {
+    fn default() -> Self {
+        RequestSize::Fixed { lbas: 1 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkloadSpec {
+    pub pattern: AddressPattern,
+    #[serde(default)]
+    pub mix: OpMix,
+    #[serde(default)]
+    pub size: RequestSize,
+}
+
+impl WorkloadSpec {
+    pub fn new(pattern: AddressPattern) -> Self {
+        WorkloadSpec {
+            pattern,
+            mix: OpMix::default(),
+            size: RequestSize::default(),
+        }
+    }
+}
+
+// theta가 1이면 alpha = 1/(1-theta)가 발산하므로 [0, 1)만 허용
+fn check_theta(theta: f64) -> Result<(), String> {
+    if (0.0..1.0).contains(&theta) {
+        Ok(())
+    } else {
+        Err(format!("Zipfian theta must be in [0, 1), got {}", theta))
+    }
+}
+
+// YCSB 방식 Zipfian 생성기 (Gray et al., "Quickly Generating Billion-Record Synthetic Databases")
+struct Zipfian {
+    n: usize,
+    theta: f64,
+    alpha: f64,
+    zetan: f64,
+    eta: f64,
+}
+
+impl Zipfian {
+    fn new(n: usize, theta: f64) -> Result<Self, String> {
+        check_theta(theta)?;
+        if n < 2 {
+            return Err(format!("Zipfian needs at least 2 LBAs, got {}", n));
+        }
+        let zeta = |count: usize| (1..=count).map(|i| 1.0 / (i as f64).powf(theta)).sum::<f64>();
+        let zetan = zeta(n);
+        let zeta2 = zeta(2);
+
+        Ok(Zipfian {
+            n,
+            theta,
+            alpha: 1.0 / (1.0 - theta),
+            zetan,
+            eta: (1.0 - (2.0 / n as f64).powf(1.0 - theta)) / (1.0 - zeta2 / zetan),
+        })
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> usize {
+        let u: f64 = rng.r#gen();
+        let uz = u * self.zetan;
+        if uz < 1.0 {
+            return 0;
+        }
+        if uz < 1.0 + 0.5f64.powf(self.theta) {
+            return 1;
+        }
+        let rank = (self.n as f64 * (self.eta * u - self.eta + 1.0).powf(self.alpha)) as usize;
+        rank.min(self.n - 1)
+    }
+}
+
+pub struct WorkloadGenerator {
+    spec: WorkloadSpec,
+    num_lbas: usize,
+    rng: StdRng,
+    next_seq: usize,
+    issued: u64,
+    zipf: Option<Zipfian>,
+}
+
+impl WorkloadGenerator {
+    pub fn new(spec: WorkloadSpec, num_lbas: usize, seed: u64) -> Result<Self, String> {
+        if num_lbas == 0 {
+            return Err("Workload needs at least 1 LBA".to_string());
+        }
+        let zipf = match spec.pattern {
+            AddressPattern::Zipfian { theta } => Some(Zipfian::new(num_lbas, theta)?),
+            _ => None,
+        };
+        let next_seq = match spec.pattern {
+            AddressPattern::Sequential { start } => start % num_lbas,
+            _ => 0,
+        };
+
+        Ok(WorkloadGenerator {
+            spec,
+            num_lbas,
+            rng: StdRng::seed_from_u64(seed),
+            next_seq,
+            issued: 0,
+            zipf,
+        })
+    }
+
+    fn next_op(&mut self) -> IoOp {
+        let mix = self.spec.mix;
+        let total = mix.read + mix.write + mix.trim;
+        if total <= 0.0 {
+            return IoOp::Write;
+        }
+
+        let r = self.rng.gen_range(0.0..total);
+        if r < mix.read {
+            IoOp::Read
+        } else if r < mix.read + mix.write {
+            IoOp::Write
+        } else {
+            IoOp::Trim
+        }
+    }
+
+    fn next_len(&mut self) -> usize {
+        let len = match self.spec.size {
+            RequestSize::Fixed { lbas } => lbas,
+            RequestSize::Uniform { min, max } => self.rng.gen_range(min..=max.max(min)),
+        };
+        len.clamp(1, self.num_lbas)
+    }
+
+    // 시작 위치 [0, span) 중 hot 영역 [hot_start, hot_start + hot_len) 안팎에서 선택
+    fn hot_cold_lba(&mut self, hot_access_pct: f64, hot_lba_pct: f64, hot_start: usize, span: usize) -> usize {
+        let hot_len = ((span as f64 * hot_lba_pct / 100.0) as usize).clamp(1, span);
+        let cold_len = span - hot_len;
+
+        let offset = if cold_len == 0 || self.rng.gen_bool((hot_access_pct / 100.0).clamp(0.0, 1.0)) {
+            self.rng.gen_range(0..hot_len)
+        } else {
+            hot_len + self.rng.gen_range(0..cold_len)
+        };
+        (hot_start + offset) % span
+    }
+
+    fn next_lba(&mut self, len: usize) -> usize {
+        // 요청이 용량 끝을 넘지 않도록 시작 위치를 [0, max_start]에서만 뽑음
+        // (뽑은 뒤 잘라내면 끝 위치에 확률이 몰림)
+        let max_start = self.num_lbas - len;
+
+        match self.spec.pattern.clone() {
+            AddressPattern::Sequential { .. } => {
+                if self.next_seq > max_start {
+                    self.next_seq = 0;
+                }
+                let lba = self.next_seq;
+                self.next_seq = (lba + len) % self.num_lbas;
+                lba
+            }
+            AddressPattern::Uniform => self.rng.gen_range(0..=max_start),
+            AddressPattern::Zipfian { .. } => {
+                // 범위를 벗어난 순위는 다시 뽑음 (순위 0은 항상 범위 안이라 끝남)
+                let zipf = self.zipf.as_ref().expect("zipfian state");
+                loop {
+                    let lba = zipf.sample(&mut self.rng);
+                    if lba <= max_start {
+                        break lba;
+                    }
+                }
+            }
+            AddressPattern::HotCold { hot_access_pct, hot_lba_pct } => {
+                self.hot_cold_lba(hot_access_pct, hot_lba_pct, 0, max_start + 1)
+            }
+            AddressPattern::ShiftingHotspot { hot_access_pct, hot_lba_pct, shift_every } => {
+                let span = max_start + 1;
+                let hot_len = ((span as f64 * hot_lba_pct / 100.0) as usize).max(1);
+                let shifts = self.issued / shift_every.max(1);
+                let hot_start = (shifts as usize * hot_len) % span;
+                self.hot_cold_lba(hot_access_pct, hot_lba_pct, hot_start, span)
+            }
+        }
+    }
+}
+
+impl Iterator for WorkloadGenerator {
+    type Item = IoRequest;
+
+    fn next(&mut self) -> Option<IoRequest> {
+        let op = self.next_op();
+        let len = self.next_len();
+        let lba = self.next_lba(len);
+        self.issued += 1;
+        Some(IoRequest { op, lba, len })
+    }
+}
+
+// 단계 하나: 어떤 워크로드를 몇 개의 요청만큼 실행할지
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Phase {
+    pub workload: WorkloadSpec,
+    pub requests: u64,
+}
+
+// 여러 단계를 순서대로 이어붙인 유한 워크로드 (단계 i의 seed = master seed + i)
+pub struct Phased {
+    generators: Vec<(WorkloadGenerator, u64)>,
+    current: usize,
+    remaining: u64,
+}
+
+impl Phased {
+    pub fn new(phases: &[Phase], num_lbas: usize, seed: u64) -> Result<Self, String> {
+        let generators: Vec<(WorkloadGenerator, u64)> = phases
+            .iter()
+            .enumerate()
+            .map(|(i, phase)| {
+                let generator = WorkloadGenerator::new(phase.workload.clone(), num_lbas, seed.wrapping_add(i as u64))?;
+                Ok((generator, phase.requests))
+            })
+            .collect::<Result<_, String>>()?;
+        let remaining = generators.first().map(|(_, n)| *n).unwrap_or(0);
+
+        Ok(Phased {
+            generators,
+            current: 0,
+            remaining,
+        })
+    }
+}
+
+impl Iterator for Phased {
+    type Item = IoRequest;
+
+    fn next(&mut self) -> Option<IoRequest> {
+        while self.remaining == 0 {
+            self.current += 1;
+            self.remaining = self.generators.get(self.current)?.1;
+        }
+
+        self.remaining -= 1;
+        self.generators[self.current].0.next()
+    }
+}
+
+// 요청 하나를 장치에 범위 요청 하나로 실행 (쓰기 데이터는 모든 페이지에 data)
+pub fn execute<D: BlockDevice + ?Sized>(device: &mut D, request: &IoRequest, data: u32) -> Result<(), String> {
+    match request.op {
+        IoOp::Read => device.read_range(request.lba, request.len).map(|_| ()),
+        IoOp::Write => device.write_range(request.lba, &vec![data; request.len]),
+        IoOp::Trim => device.trim_range(request.lba, request.len),
+    }
+}
@@ -0,0 +1,174 @@
+// 컨트롤러 DRAM의 휘발성 쓰기 버퍼
+// 호스트 쓰기를 먼저 버퍼에 받아, 같은 LBA를 다시 쓰면 NAND에 가기 전에 덮어씀(coalescing).
+// 버퍼가 넘치면 정책에 따라 내보내고(evict), flush/FUA 때 전부 또는 일부를 NAND에 내려씁니다.
+// 전원이 끊기면 버퍼에 남은 데이터는 사라집니다.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    // 가장 오래 쓰이지(읽히지) 않은 LBA부터
+    #[default]
+    Lru,
+    // 버퍼에 처음 들어온 순서대로 (덮어써도 순서 유지)
+    Fifo,
+    // LRU 순서로, 한 번에 프로그램 단위(워드라인)를 채울 만큼 내보냄
+    // flush/FUA 때 남는 단위를 더미 대신 버퍼 데이터로 채움
+    PaddingAware,
+}
+
+impl EvictionPolicy {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "lru" => Ok(EvictionPolicy::Lru),
+            "fifo" => Ok(EvictionPolicy::Fifo),
+            "padding" | "padding_aware" => Ok(EvictionPolicy::PaddingAware),
+            _ => Err(format!("Unknown eviction policy: {} (lru, fifo, padding)", name)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WriteBufferConfig {
+    // 버퍼에 담을 수 있는 페이지(LBA) 수
+    pub capacity: usize,
+    #[serde(default)]
+    pub policy: EvictionPolicy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WriteBuffer {
+    config: WriteBufferConfig,
+    // lba -> (순번, 데이터)
+    entries: BTreeMap<usize, (u64, u32)>,
+    // 순번 -> lba (가장 작은 순번이 다음 evict 대상)
+    order: BTreeMap<u64, usize>,
+    next_seq: u64,
+}
+
+impl WriteBuffer {
+    pub fn new(config: WriteBufferConfig) -> Result<Self, String> {
+        if config.capacity == 0 {
+            return Err("Write buffer capacity must be at least 1 page".to_string());
+        }
+        Ok(WriteBuffer {
+            config,
+            entries: BTreeMap::new(),
+            order: BTreeMap::new(),
+            next_seq: 0,
+        })
+    }
+
+    pub fn config(&self) -> WriteBufferConfig {
+        self.config
+    }
+
+    pub fn policy(&self) -> EvictionPolicy {
+        self.config.policy
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.config.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn is_overflowing(&self) -> bool {
+        self.entries.len() > self.config.capacity
+    }
+
+    pub fn contains(&self, lba: usize) -> bool {
+        self.entries.contains_key(&lba)
+    }
+
+    // 버퍼에 있으면 데이터 반환 (LRU 계열은 최근 사용으로 갱신)
+    pub fn lookup(&mut self, lba: usize) -> Option<u32> {
+        let data = self.entries.get(&lba)?.1;
+        if self.config.policy != EvictionPolicy::Fifo {
+            self.touch(lba);
+        }
+        Some(data)
+    }
+
+    // 버퍼에 넣음. 이미 있던 LBA를 덮어썼으면(이전 쓰기가 NAND에 안 가게 됨) true
+    pub fn insert(&mut self, lba: usize, data: u32) -> bool {
+        if let Some(entry) = self.entries.get_mut(&lba) {
+            entry.1 = data;
+            if self.config.policy != EvictionPolicy::Fifo {
+                self.touch(lba);
+            }
+            return true;
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.entries.insert(lba, (seq, data));
+        self.order.insert(seq, lba);
+        false
+    }
+
+    pub fn remove(&mut self, lba: usize) -> Option<u32> {
+        let (seq, data) = self.entries.remove(&lba)?;
+        self.order.remove(&seq);
+        Some(data)
+    }
+
+    // 다음 evict 대상 (LRU / FIFO 순서상 가장 오래된 것)
+    pub fn pop_oldest(&mut self) -> Option<(usize, u32)> {
+        let (_, lba) = self.order.pop_first()?;
+        let (_, data) = self.entries.remove(&lba)?;
+        Some((lba, data))
+    }
+
+    // 버퍼를 비우고 내용을 evict 순서대로 반환
+    pub fn drain(&mut self) -> Vec<(usize, u32)> {
+        let mut drained = Vec::with_capacity(self.entries.len());
+        while let Some(entry) = self.pop_oldest() {
+            drained.push(entry);
+        }
+        drained
+    }
+
+    // 전원 차단: 내용을 버리고 잃어버린 페이지 수 반환
+    pub fn clear(&mut self) -> usize {
+        let lost = self.entries.len();
+        self.entries.clear();
+        self.order.clear();
+        lost
+    }
+
+    // 버퍼 내부 일관성 (불변식 검사용)
+    pub fn check(&self, num_lbas: usize) -> Result<(), String> {
+        if self.is_overflowing() {
+            return Err(format!("Write buffer holds {} pages but capacity is {}", self.entries.len(), self.config.capacity));
+        }
+        if self.entries.len() != self.order.len() {
+            return Err(format!("Write buffer has {} entries but {} order slots", self.entries.len(), self.order.len()));
+        }
+        for (&lba, &(seq, _)) in &self.entries {
+            if lba >= num_lbas {
+                return Err(format!("Write buffer holds LBA {} but the device has {} LBAs", lba, num_lbas));
+            }
+            if self.order.get(&seq) != Some(&lba) || seq >= self.next_seq {
+                return Err(format!("Write buffer LBA {} has a stale order slot {}", lba, seq));
+            }
+        }
+        Ok(())
+    }
+
+    fn touch(&mut self, lba: usize) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        if let Some(entry) = self.entries.get_mut(&lba) {
+            self.order.remove(&entry.0);
+            entry.0 = seq;
+            self.order.insert(seq, lba);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use ssd_rs::block::Block;
+use ssd_rs::alloc::{AllocPolicy, FreeBlockPool, WriteStream};
+use ssd_rs::difftest;
+use ssd_rs::experiment;
+use ssd_rs::ssd_basic::SSD;
+
+const POLICIES: [AllocPolicy; 5] = [
+    AllocPolicy::FirstFree,
+    AllocPolicy::LowestErase,
+    AllocPolicy::RoundRobin,
+    AllocPolicy::Random,
+    AllocPolicy::HotCold,
+];
+
+// 블록 i의 erase 횟수 = erases[i]
+fn pool(erases: &[u32]) -> FreeBlockPool {
+    let mut pool = FreeBlockPool::new(erases.len());
+    for (idx, &erase_count) in erases.iter().enumerate() {
+        let mut block = Block::new(idx as u32);
+        block.erase_count = erase_count;
+        pool.insert(&block);
+    }
+    pool
+}
+
+#[test]
+fn each_policy_picks_its_block() {
+    let erases = [5, 2, 9, 2, 7];
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut pick = |policy, stream| pool(&erases).allocate(policy, stream, &mut rng).unwrap();
+
+    assert_eq!(pick(AllocPolicy::FirstFree, WriteStream::Host), 0);
+    assert_eq!(pick(AllocPolicy::LowestErase, WriteStream::Gc), 1);
+    assert_eq!(pick(AllocPolicy::HotCold, WriteStream::Host), 1);
+    assert_eq!(pick(AllocPolicy::HotCold, WriteStream::Gc), 2);
+
+    let mut round_robin = pool(&erases);
+    round_robin.remove(2);
+    let order: Vec<usize> = (0..4)
+        .map(|_| round_robin.allocate(AllocPolicy::RoundRobin, WriteStream::Host, &mut rng).unwrap())
+        .collect();
+    assert_eq!(order, [1, 3, 4, 0]);
+    assert!(round_robin.allocate(AllocPolicy::RoundRobin, WriteStream::Host, &mut rng).is_none());
+
+    let mut random = pool(&erases);
+    let mut drawn: Vec<usize> = (0..5)
+        .map(|_| random.allocate(AllocPolicy::Random, WriteStream::Host, &mut rng).unwrap())
+        .collect();
+    drawn.sort();
+    assert_eq!(drawn, [0, 1, 2, 3, 4]);
+    assert!(random.is_empty());
+}
+
+// GC가 끝나고 나면 빈 블록이 하나뿐이라 정책 차이가 없음 -> 빈 블록을 여러 개 미리 확보하는 백그라운드 GC로
+#[test]
+fn wear_aware_policies_spread_erases() {
+    let exp = experiment::load("./test/allocation.json").unwrap();
+    let rows = experiment::run(&exp).unwrap();
+    let spread = |name: &str| {
+        let row = rows.iter().find(|r| r.params.contains(name)).unwrap();
+        row.erase_max - row.erase_min
+    };
+    for policy in ["lowest_erase", "round_robin", "random", "hot_cold"] {
+        assert!(spread(policy) < spread("first_free"), "{}: {} vs {}", policy, spread(policy), spread("first_free"));
+    }
+}
+
+#[test]
+fn every_policy_matches_oracle() {
+    for policy in POLICIES {
+        let factory = || {
+            let mut ssd = SSD::with_seed(8, 300, 3);
+            ssd.set_alloc_policy(policy);
+            ssd.place_bad_blocks(1).unwrap();
+            ssd.set_debug_checks(true);
+            ssd
+        };
+        if let Err(failure) = difftest::run(factory, 3000, 5) {
+            panic!("{:?}: {}", policy, failure);
+        }
+    }
+}
+
+#[test]
+fn snapshot_keeps_policy_and_allocation_order() {
+    let mut ssd = SSD::with_seed(8, 300, 9);
+    ssd.set_alloc_policy(AllocPolicy::RoundRobin);
+    for i in 0..1500u32 {
+        ssd.write(i as usize % 300, i).unwrap();
+    }
+    let mut restored = SSD::from_snapshot(ssd.snapshot());
+    assert_eq!(restored.alloc_policy(), AllocPolicy::RoundRobin);
+
+    for i in 0..1500u32 {
+        ssd.write(i as usize * 13 % 300, i).unwrap();
+        restored.write(i as usize * 13 % 300, i).unwrap();
+    }
+    restored.check_invariants().unwrap();
+    let erases = |ssd: &SSD| ssd.blocks().iter().map(|b| b.erase_count).collect::<Vec<_>>();
+    assert_eq!(erases(&ssd), erases(&restored));
+}
@@ -0,0 +1,64 @@
+use ssd_rs::device::BlockDevice;
+use ssd_rs::gc::{GcPolicy, GcScheduler};
+use ssd_rs::ssd_basic::SSD as BasicSSD;
+use ssd_rs::ssd_wear_leveling::SSD as WearLevelingSSD;
+use ssd_rs::timing::{TimedSSD, TimingModel};
+
+// 모든 BlockDevice 구현이 지켜야 하는 호스트 동작 (GC가 여러 번 돌 만큼 덮어씀)
+fn check_host_semantics(device: &mut dyn BlockDevice) {
+    let num_lbas = device.num_lbas();
+    assert_eq!(device.read(0).unwrap(), None);
+    assert!(device.write(num_lbas, 1).is_err());
+
+    for round in 0..5u32 {
+        for lba in 0..num_lbas {
+            device.write(lba, round * 1000 + lba as u32).unwrap();
+        }
+    }
+    device.trim(7).unwrap();
+    device.flush().unwrap();
+
+    for lba in 0..num_lbas {
+        let expected = if lba == 7 { None } else { Some(4000 + lba as u32) };
+        assert_eq!(device.read(lba).unwrap(), expected, "LBA {}", lba);
+    }
+
+    let stats = device.stats();
+    assert_eq!(stats.host_writes, 5 * num_lbas as u64);
+    assert_eq!(stats.host_trims, 1);
+    assert!(stats.gc_invocations > 0);
+    assert!(device.erase_distribution().max > 0);
+
+    device.reset_stats();
+    assert_eq!(device.stats().host_writes, 0);
+}
+
+#[test]
+fn every_ftl_implements_the_same_host_interface() {
+    check_host_semantics(&mut BasicSSD::new(8, 400));
+    check_host_semantics(&mut WearLevelingSSD::new(16, 400));
+
+    let scheduler = GcScheduler::new(GcPolicy::GreedyPreemptive, 2, 4);
+    let mut timed = TimedSSD::new(BasicSSD::new(8, 400), TimingModel::default(), scheduler);
+    check_host_semantics(&mut timed);
+    assert!(timed.clock_us().unwrap() > 0);
+}
+
+#[test]
+fn wear_leveling_snapshot_round_trip() {
+    let mut ssd = WearLevelingSSD::new(16, 400);
+    for i in 0..2000 {
+        ssd.write(i % 400, i as u32).unwrap();
+    }
+
+    let path = std::env::temp_dir().join(format!("wl_snapshot_{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    BlockDevice::save_snapshot(&ssd, path).unwrap();
+    let mut restored = WearLevelingSSD::load_snapshot(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    for lba in 0..400 {
+        assert_eq!(restored.read(lba).unwrap(), ssd.read(lba).unwrap());
+    }
+    assert_eq!(restored.stats().nand_writes, ssd.stats().nand_writes);
+}
@@ -0,0 +1,189 @@
+use std::io::{BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+
+use ssd_rs::byte_io::SsdFile;
+use ssd_rs::device::BlockDevice;
+use ssd_rs::gc::{GcPolicy, GcScheduler};
+use ssd_rs::sector::{SectorConfig, SectorDevice};
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::ssd_slc_cache::{SlcCacheConfig, SSD as SlcCacheSSD};
+use ssd_rs::ssd_wear_leveling::SSD as WearLevelingSSD;
+use ssd_rs::timing::{TimedSSD, TimingModel};
+use ssd_rs::write_buffer::{EvictionPolicy, WriteBufferConfig};
+
+const PAGE: usize = 4096;
+
+#[test]
+fn behaves_like_a_file() {
+    let mut file = SsdFile::new(SSD::new(8, 300), PAGE).unwrap();
+    let size = file.size();
+
+    {
+        let mut writer = BufWriter::new(&mut file);
+        for i in 0..500 {
+            writeln!(writer, "log record {:04}", i).unwrap();
+        }
+    }
+    let written = file.position();
+    assert_eq!(written, 500 * "log record 0000\n".len() as u64);
+
+    file.seek(SeekFrom::Start(0)).unwrap();
+    let mut text = String::new();
+    (&mut file).take(written).read_to_string(&mut text).unwrap();
+    assert_eq!(text.lines().nth(321), Some("log record 0321"));
+
+    // 끝에서 상대 이동, 음수 위치는 에러
+    assert_eq!(file.seek(SeekFrom::End(-4)).unwrap(), size - 4);
+    assert!(file.seek(SeekFrom::Current(-(size as i64) - 1)).is_err());
+
+    // 장치 끝에서 읽기는 EOF, 쓰기는 WriteZero
+    file.seek(SeekFrom::End(0)).unwrap();
+    assert_eq!(file.read(&mut [0u8; 8]).unwrap(), 0);
+    assert!(file.write_all(b"x").is_err());
+}
+
+#[test]
+fn partial_writes_are_read_modify_write() {
+    let mut file = SsdFile::new(SSD::new(8, 300), PAGE).unwrap();
+
+    // 빈 페이지에 대한 부분 쓰기는 읽을 게 없음
+    file.write_at(10, b"hello").unwrap();
+    assert_eq!(file.ssd().stats().rmw_reads, 0);
+
+    // 이미 쓴 페이지를 일부만 덮으면 기존 페이지를 한 번 읽음
+    file.write_at(100, b"world").unwrap();
+    assert_eq!(file.ssd().stats().rmw_reads, 1);
+    assert_eq!(file.ssd().stats().host_reads, 0);
+
+    // 두 페이지에 걸친 쓰기 -> 앞 페이지만 기존 데이터가 있음
+    file.write_at(PAGE as u64 - 2, b"span").unwrap();
+    assert_eq!(file.ssd().stats().rmw_reads, 2);
+
+    // 페이지 전체 쓰기는 RMW 없음
+    file.write_at(2 * PAGE as u64, &[7u8; PAGE]).unwrap();
+    assert_eq!(file.ssd().stats().rmw_reads, 2);
+    assert_eq!(file.byte_stats().partial_page_writes, 4);
+
+    let mut buf = [0u8; 5];
+    file.read_at(10, &mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+    file.read_at(100, &mut buf).unwrap();
+    assert_eq!(&buf, b"world");
+    let mut span = [0u8; 4];
+    file.read_at(PAGE as u64 - 2, &mut span).unwrap();
+    assert_eq!(&span, b"span");
+
+    // 4 + 5 + 5 + 4096 바이트를 쓰려고 페이지 5개를 프로그램
+    assert_eq!(file.ssd().stats().nand_writes, 5);
+    let expected = (5 * PAGE) as f64 / (5 + 5 + 4 + PAGE) as f64;
+    assert!((file.byte_waf() - expected).abs() < 1e-9);
+}
+
+#[test]
+fn trim_drops_only_fully_covered_pages() {
+    let mut file = SsdFile::new(SSD::new(8, 300), PAGE).unwrap();
+    file.write_at(0, &vec![0x5A; 3 * PAGE]).unwrap();
+
+    file.trim_at(PAGE as u64 / 2, 2 * PAGE as u64).unwrap();
+
+    let mut buf = vec![0u8; 3 * PAGE];
+    file.read_at(0, &mut buf).unwrap();
+    assert!(buf[..PAGE].iter().all(|&b| b == 0x5A));
+    assert!(buf[PAGE..2 * PAGE].iter().all(|&b| b == 0));
+    assert!(buf[2 * PAGE..].iter().all(|&b| b == 0x5A));
+}
+
+#[test]
+fn rejects_zero_page_size_and_reads_eof_past_the_end() {
+    assert!(SsdFile::new(SSD::new(8, 300), 0).is_err());
+
+    let mut file = SsdFile::new(SSD::new(8, 300), PAGE).unwrap();
+    let size = file.size();
+    assert_eq!(file.seek(SeekFrom::Start(size + 100)).unwrap(), size + 100);
+    assert_eq!(file.read(&mut [0u8; 8]).unwrap(), 0);
+    assert_eq!(file.write(b"past").unwrap(), 0);
+    assert_eq!(file.position(), size + 100);
+}
+
+#[test]
+fn follows_device_resize() {
+    let mut file = SsdFile::new(SSD::new(8, 300), PAGE).unwrap();
+    file.write_at(299 * PAGE as u64, b"last").unwrap();
+
+    file.ssd_mut().resize(350).unwrap();
+    file.write_at(349 * PAGE as u64, b"grown").unwrap();
+    let mut buf = [0u8; 5];
+    file.read_at(349 * PAGE as u64, &mut buf).unwrap();
+    assert_eq!(&buf, b"grown");
+
+    // 줄였다가 다시 늘리면 잘려 나간 LBA는 빈 페이지
+    file.ssd_mut().resize(200).unwrap();
+    assert!(file.read_at(299 * PAGE as u64, &mut buf).is_err());
+    file.ssd_mut().resize(300).unwrap();
+    file.read_at(299 * PAGE as u64, &mut buf).unwrap();
+    assert_eq!(buf, [0u8; 5]);
+}
+
+#[test]
+fn bytes_lost_in_a_power_loss_are_not_returned() {
+    let mut ssd = SSD::new(8, 300);
+    ssd.set_write_buffer(Some(WriteBufferConfig { capacity: 8, policy: EvictionPolicy::Lru })).unwrap();
+    let mut file = SsdFile::new(ssd, PAGE).unwrap();
+
+    file.write_at(0, &[1u8; PAGE]).unwrap();
+    file.flush().unwrap();
+    file.write_at(0, &[2u8; PAGE]).unwrap();
+    file.write_at(PAGE as u64, &[3u8; PAGE]).unwrap();
+    assert_eq!(file.ssd_mut().power_loss(), 2);
+
+    // LBA 0: NAND에는 첫 버전이 남았지만 그 바이트는 이미 덮어써서 없음
+    let mut buf = vec![0u8; PAGE];
+    let err = file.read_at(0, &mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(file.write_at(10, b"rmw").is_err());
+    // LBA 1: 한 번도 NAND에 가지 않았으므로 빈 페이지
+    file.read_at(PAGE as u64, &mut buf).unwrap();
+    assert!(buf.iter().all(|&b| b == 0));
+
+    // 페이지 전체를 다시 쓰면 복구됨
+    file.write_at(0, &[4u8; PAGE]).unwrap();
+    file.read_at(0, &mut buf).unwrap();
+    assert!(buf.iter().all(|&b| b == 4));
+}
+
+// 비정렬 쓰기를 몇 바퀴 덮어써서 GC까지 거친 뒤 마지막 바퀴 내용이 그대로 읽히는지
+fn overwrite_and_verify<D: BlockDevice>(device: D) -> SsdFile<D> {
+    let mut file = SsdFile::new(device, PAGE).unwrap();
+    let records = file.size() / 1000 - 1;
+    for round in 0..4u8 {
+        for i in 0..records {
+            file.write_at(i * 1000 + 7, &[round ^ i as u8; 1000]).unwrap();
+        }
+    }
+    file.flush().unwrap();
+
+    let mut buf = [0u8; 1000];
+    for i in 0..records {
+        file.read_at(i * 1000 + 7, &mut buf).unwrap();
+        assert!(buf.iter().all(|&b| b == 3 ^ i as u8), "record {}", i);
+    }
+    assert!(file.byte_stats().partial_page_writes > 0);
+    file.ssd().check_invariants().unwrap();
+    file
+}
+
+#[test]
+fn wraps_any_block_device() {
+    let file = overwrite_and_verify(WearLevelingSSD::new(8, 300));
+    assert!(file.ssd().stats().gc_invocations > 0);
+
+    let file = overwrite_and_verify(SlcCacheSSD::new(16, 600, SlcCacheConfig::default()).unwrap());
+    assert!(file.ssd().stats().slc_writes > 0);
+
+    let scheduler = GcScheduler::new(GcPolicy::StopTheWorld, 1, 2);
+    let file = overwrite_and_verify(TimedSSD::new(SSD::new(8, 300), TimingModel::default(), scheduler));
+    assert!(file.ssd().write_latency().count() > 0);
+
+    let config = SectorConfig { sector_size: 1024, page_size: PAGE };
+    let file = overwrite_and_verify(SectorDevice::new(SSD::new(8, 300), config).unwrap());
+    assert_eq!(file.ssd().num_lbas(), 300 * 4);
+}
@@ -0,0 +1,115 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ssd_rs::capacity::{OpMode, OverProvisioning};
+use ssd_rs::difftest;
+use ssd_rs::experiment::Geometry;
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::write_buffer::WriteBufferConfig;
+
+#[test]
+fn op_from_ratio_or_reserved_blocks() {
+    assert_eq!(OverProvisioning::Percent { pct: 25.0 }.user_pages(64, 64).unwrap(), 3276);
+    assert_eq!(OverProvisioning::ReservedBlocks { blocks: 8 }.user_pages(64, 64).unwrap(), 56 * 64);
+    // 여유 블록이 하나도 없으면 GC를 못 함
+    assert!(OverProvisioning::ReservedBlocks { blocks: 0 }.user_pages(64, 64).is_err());
+    assert!(OverProvisioning::ReservedBlocks { blocks: 64 }.user_pages(64, 64).is_err());
+    assert!(OverProvisioning::Percent { pct: -5.0 }.user_pages(64, 64).is_err());
+
+    let geometry: Geometry = serde_json::from_str(r#"{"num_blocks": 64, "reserved_blocks": 4}"#).unwrap();
+    assert_eq!(geometry.resolve_num_lbas().unwrap(), 60 * 64);
+    let both: Geometry = serde_json::from_str(r#"{"num_blocks": 64, "op_pct": 7, "reserved_blocks": 4}"#).unwrap();
+    assert!(both.resolve_num_lbas().is_err());
+
+    let ssd = SSD::with_over_provisioning(64, OverProvisioning::ReservedBlocks { blocks: 8 }, 0).unwrap();
+    let capacity = ssd.capacity();
+    assert_eq!((capacity.raw_pages, capacity.user_pages, capacity.spare_pages()), (4096, 3584, 512));
+    assert!((capacity.op_pct() - 512.0 / 3584.0 * 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn trimmed_space_counts_as_effective_spare() {
+    let mut ssd = SSD::new(16, 800);
+    ssd.write_range(0, &[1; 500]).unwrap();
+    ssd.trim_range(0, 200).unwrap();
+
+    let capacity = ssd.capacity();
+    assert_eq!(capacity.valid_pages, 300);
+    assert_eq!(capacity.effective_spare_pages(), 1024 - 300);
+    assert_eq!(capacity.spare_pages(), 1024 - 800);
+}
+
+#[test]
+fn static_op_keeps_trimmed_pages_until_overwritten() {
+    let mut ssd = SSD::new(16, 800);
+    ssd.set_op_mode(OpMode::Static);
+    ssd.write_range(0, &[1; 500]).unwrap();
+    ssd.trim_range(0, 200).unwrap();
+
+    // 호스트에는 trim된 것으로 보이지만 페이지는 여전히 유효
+    assert_eq!(ssd.read(10).unwrap(), None);
+    assert_eq!(ssd.capacity().valid_pages, 500);
+    ssd.write(10, 7).unwrap();
+    assert_eq!(ssd.read(10).unwrap(), Some(7));
+    assert_eq!(ssd.capacity().valid_pages, 500);
+
+    // 동적 모드로 바꾸면 남은 trim을 바로 회수
+    ssd.set_op_mode(OpMode::Dynamic);
+    assert_eq!(ssd.capacity().valid_pages, 301);
+    assert_eq!(ssd.read(20).unwrap(), None);
+    ssd.check_invariants().unwrap();
+}
+
+#[test]
+fn static_op_matches_oracle() {
+    let factory = || {
+        let mut ssd = SSD::new(12, 500);
+        ssd.set_op_mode(OpMode::Static);
+        ssd.set_write_buffer(Some(WriteBufferConfig { capacity: 8, policy: Default::default() })).unwrap();
+        ssd.set_debug_checks(true);
+        ssd
+    };
+    if let Err(failure) = difftest::run(factory, 3000, 11) {
+        panic!("{}", failure);
+    }
+}
+
+#[test]
+fn dynamic_op_lowers_waf_under_trim() {
+    let waf = |mode| {
+        let mut ssd = SSD::new(32, 1800);
+        ssd.set_op_mode(mode);
+        let mut rng = StdRng::seed_from_u64(5);
+        ssd.write_range(0, &vec![0; 1800]).unwrap();
+        // 앞쪽 절반을 trim한 뒤 나머지만 계속 덮어씀
+        ssd.trim_range(0, 900).unwrap();
+        ssd.reset_stats();
+        for i in 0..20_000 {
+            ssd.write(rng.gen_range(900..1800), i).unwrap();
+        }
+        ssd.stats().waf()
+    };
+    let (dynamic, fixed) = (waf(OpMode::Dynamic), waf(OpMode::Static));
+    assert!(dynamic < fixed, "dynamic {} vs static {}", dynamic, fixed);
+}
+
+#[test]
+fn resize_shrinks_and_grows_exported_capacity() {
+    let mut ssd = SSD::new(16, 800);
+    ssd.set_debug_checks(true);
+    ssd.write_range(0, &[3; 800]).unwrap();
+
+    ssd.resize(600).unwrap();
+    assert_eq!(ssd.num_lbas(), 600);
+    assert_eq!(ssd.capacity().valid_pages, 600);
+    assert!(ssd.read(700).is_err());
+
+    ssd.resize(900).unwrap();
+    assert_eq!(ssd.read(599).unwrap(), Some(3));
+    assert_eq!(ssd.read(700).unwrap(), None);
+    ssd.write(899, 4).unwrap();
+
+    // 여유 블록 하나는 남아야 함
+    assert!(ssd.resize(1000).is_err());
+    assert!(ssd.resize(0).is_err());
+    ssd.check_invariants().unwrap();
+}
@@ -0,0 +1,161 @@
+use ssd_rs::block::{Block, PAGES_PER_BLOCK};
+use ssd_rs::cell::{CellMode, PageType};
+use ssd_rs::difftest;
+use ssd_rs::gc::{GcPolicy, GcScheduler};
+use ssd_rs::device::BlockDevice;
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::ssd_slc_cache::{SlcCacheConfig, SlcCachePolicy, SSD as SlcCacheSSD};
+use ssd_rs::ssd_wear_leveling::SSD as WearLevelingSSD;
+use ssd_rs::timing::{TimedSSD, TimingModel};
+
+#[test]
+fn page_counts_and_default_geometry() {
+    let pages: Vec<usize> = CellMode::ALL.iter().map(|m| m.pages_per_block()).collect();
+    assert_eq!(pages, vec![32, 64, 96, 128]);
+    assert_eq!(Block::new(0).pages.len(), PAGES_PER_BLOCK);
+    assert!(CellMode::Slc.endurance() > CellMode::Qlc.endurance());
+    assert!(CellMode::Slc.timing().program_us < CellMode::Qlc.timing().program_us);
+}
+
+#[test]
+fn paired_page_program_order() {
+    let tlc: Vec<(usize, PageType)> = (0..6).map(|p| CellMode::Tlc.page_location(p)).collect();
+    assert_eq!(tlc, vec![
+        (0, PageType::Lower),
+        (1, PageType::Lower),
+        (0, PageType::Upper),
+        (2, PageType::Lower),
+        (1, PageType::Upper),
+        (0, PageType::Extra),
+    ]);
+    assert_eq!(CellMode::Tlc.paired_pages(0), vec![2, 5]);
+    assert!(CellMode::Slc.paired_pages(7).is_empty());
+
+    // 오프셋 순서대로 프로그램하면 모든 모드에서 선행 페이지가 항상 먼저 프로그램됨
+    for mode in CellMode::ALL {
+        for offset in 0..mode.pages_per_block() {
+            assert!(mode.program_prerequisites(offset).all(|p| p < offset), "{:?} page {}", mode, offset);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "must be programmed first")]
+fn upper_page_before_lower_page_panics() {
+    let mut block = Block::with_mode(0, CellMode::Tlc);
+    block.program(0, 1);
+    // U0은 L1 뒤에만 프로그램 가능
+    block.program(2, 1);
+}
+
+#[test]
+fn cell_mode_changes_only_on_erased_blocks() {
+    let mut block = Block::with_mode(0, CellMode::Qlc);
+    block.program(0, 1);
+    assert!(block.set_cell_mode(CellMode::Slc).is_err());
+
+    block.erase();
+    block.set_cell_mode(CellMode::Slc).unwrap();
+    assert_eq!(block.pages.len(), 32);
+    assert_eq!(block.erase_count, 1);
+}
+
+#[test]
+fn mixed_mode_device_matches_oracle() {
+    let make = || {
+        let mut ssd = SSD::new(10, 500);
+        for idx in 0..10 {
+            ssd.set_cell_mode(idx, CellMode::ALL[idx % 4]).unwrap();
+        }
+        ssd.set_debug_checks(true);
+        ssd
+    };
+    if let Err(failure) = difftest::run(make, 5000, 9) {
+        panic!("{}", failure);
+    }
+}
+
+// NAND 동작 지연 시간은 그 블록의 셀 모드 기준 (SLC 블록에 쓰면 빠르고 QLC 블록에 쓰면 느림)
+#[test]
+fn timed_device_costs_each_operation_by_cell_mode() {
+    let timing = TimingModel::default();
+    let write_latency = |mode: CellMode| {
+        let mut ssd = SSD::new(8, 100);
+        for idx in 0..8 {
+            ssd.set_cell_mode(idx, mode).unwrap();
+        }
+        let mut timed = TimedSSD::new(ssd, timing, GcScheduler::new(GcPolicy::StopTheWorld, 1, 2));
+        let write = timed.write(0, 7, 0).unwrap();
+        let (_, read) = timed.read(0, timed.now_us()).unwrap();
+        (write, read)
+    };
+
+    assert_eq!(write_latency(CellMode::default()), (timing.program_us, timing.read_us));
+    for mode in CellMode::ALL {
+        let scaled = timing.for_mode(mode);
+        assert_eq!(write_latency(mode), (scaled.program_us, scaled.read_us), "{:?}", mode);
+    }
+    assert!(timing.for_mode(CellMode::Slc).program_us < timing.program_us);
+    assert!(timing.for_mode(CellMode::Qlc).program_us > timing.program_us);
+}
+
+// 보장 P/E 사이클을 다 쓴 블록은 erase 직후 배드 블록으로 퇴역하고 다시 할당되지 않음
+// 앞쪽 블록 count개만 수명 직전으로 (스냅샷 JSON을 직접 고침)
+fn near_end_of_life(mut snapshot: serde_json::Value, count: usize) -> serde_json::Value {
+    for block in &mut snapshot["blocks"].as_array_mut().unwrap()[..count] {
+        let mode: CellMode = serde_json::from_value(block["cell_mode"].clone()).unwrap();
+        block["erase_count"] = (mode.endurance() - 1).into();
+    }
+    snapshot
+}
+
+fn check_retired(device: &mut impl BlockDevice, writes: u32) {
+    let num_lbas = device.num_lbas();
+    for i in 0..writes {
+        device.write(i as usize % num_lbas, i).unwrap();
+    }
+    let retired: Vec<&Block> = device.blocks().iter().filter(|b| b.is_bad).collect();
+    assert!(!retired.is_empty());
+    assert!(retired.iter().all(|b| b.id < 4));
+    for block in device.blocks() {
+        assert!(block.erase_count <= block.cell_mode.endurance());
+        assert_eq!(block.is_bad, block.is_worn_out(), "Block {}", block.id);
+    }
+    // 배드 블록은 wear 통계에서 빠짐
+    let good_max = device.blocks().iter().filter(|b| !b.is_bad).map(|b| b.erase_count).max();
+    assert_eq!(Some(device.erase_distribution().max), good_max);
+    for lba in 0..num_lbas {
+        assert_eq!(device.read(lba).unwrap(), Some((writes as usize - num_lbas + lba) as u32));
+    }
+    device.check_invariants().unwrap();
+}
+
+#[test]
+fn worn_out_blocks_are_retired_on_erase() {
+    let mut ssd = SSD::new(12, 200);
+    ssd.set_cell_mode(3, CellMode::Qlc).unwrap();
+    let snapshot = near_end_of_life(serde_json::to_value(ssd.snapshot()).unwrap(), 4);
+    let mut ssd = SSD::from_snapshot(serde_json::from_value(snapshot).unwrap());
+    ssd.set_debug_checks(true);
+    check_retired(&mut ssd, 800);
+
+    let mut wl = WearLevelingSSD::new(12, 200);
+    wl.set_cell_mode(3, CellMode::Qlc).unwrap();
+    let snapshot = near_end_of_life(serde_json::to_value(wl.snapshot()).unwrap(), 4);
+    let mut wl = WearLevelingSSD::from_snapshot(serde_json::from_value(snapshot).unwrap());
+    wl.set_debug_checks(true);
+    check_retired(&mut wl, 800);
+
+    // 정적 캐시 블록 2개 + 고밀도 블록 2개
+    let config = SlcCacheConfig { policy: SlcCachePolicy::Static { blocks: 2 }, dense_mode: CellMode::Tlc };
+    let slc = SlcCacheSSD::new(12, 200, config).unwrap();
+    let snapshot = near_end_of_life(serde_json::to_value(slc.snapshot()).unwrap(), 4);
+    let mut slc = SlcCacheSSD::from_snapshot(serde_json::from_value(snapshot).unwrap());
+    slc.set_debug_checks(true);
+    check_retired(&mut slc, 3000);
+    // 정적 캐시 블록은 접고 나서 erase할 때 퇴역
+    slc.fold_all().unwrap();
+    assert!(slc.blocks()[..2].iter().all(|b| b.is_bad));
+    slc.write(0, 1).unwrap();
+    assert_eq!(slc.read(0).unwrap(), Some(1));
+}
@@ -0,0 +1,48 @@
+use ssd_rs::experiment::{self, Experiment};
+
+// 모든 무작위 요소(워크로드, 도착 시각, d-choices GC, 배드 블록 배치)를 켠 작은 실험
+fn experiment_with_seed(seed: u64) -> Experiment {
+    let config = format!(r#"{{
+        "name": "determinism",
+        "geometry": {{ "num_blocks": 24, "op_pct": 20, "bad_blocks": 2 }},
+        "ftl": {{ "type": "basic", "victim_policy": {{ "type": "d_choices", "d": 4 }} }},
+        "timing": {{
+            "gc_policy": {{ "type": "greedy_preemptive" }},
+            "low_watermark": 2, "high_watermark": 4, "mean_interarrival_us": 800
+        }},
+        "warmup": [
+            {{ "workload": {{ "pattern": {{ "type": "sequential", "start": 0 }} }}, "capacity_multiple": 1 }}
+        ],
+        "measure": [
+            {{
+                "workload": {{
+                    "pattern": {{ "type": "zipfian", "theta": 0.9 }},
+                    "mix": {{ "read": 0.3, "write": 0.6, "trim": 0.1 }},
+                    "size": {{ "type": "uniform", "min": 1, "max": 4 }}
+                }},
+                "capacity_multiple": 5
+            }}
+        ],
+        "seed": {},
+        "repetitions": 2
+    }}"#, seed);
+    serde_json::from_str(&config).expect("valid experiment config")
+}
+
+fn run_to_json(exp: &Experiment) -> String {
+    let rows = experiment::run(exp).expect("experiment runs");
+    serde_json::to_string(&rows).expect("rows serialize")
+}
+
+#[test]
+fn same_seed_gives_identical_statistics() {
+    let exp = experiment_with_seed(1234);
+    assert_eq!(run_to_json(&exp), run_to_json(&exp));
+}
+
+#[test]
+fn different_seed_gives_different_statistics() {
+    let a = run_to_json(&experiment_with_seed(1));
+    let b = run_to_json(&experiment_with_seed(2));
+    assert_ne!(a, b);
+}
@@ -0,0 +1,110 @@
+use ssd_rs::block::Block;
+use ssd_rs::device::BlockDevice;
+use ssd_rs::difftest::{self, Op};
+use ssd_rs::gc::{GcPolicy, GcScheduler, VictimPolicy};
+use ssd_rs::ssd_basic::SSD as BasicSSD;
+use ssd_rs::ssd_wear_leveling::SSD as WearLevelingSSD;
+use ssd_rs::stats::Stats;
+use ssd_rs::timing::{TimedSSD, TimingModel};
+
+// 작은 장치 + 많은 연산 -> GC가 수백 번 돌도록
+const BLOCKS: usize = 8;
+const LBAS: usize = 400;
+const OPS: usize = 6000;
+const SEEDS: [u64; 4] = [1, 7, 42, 2024];
+
+fn assert_passes<D: BlockDevice>(make: impl Fn() -> D) {
+    for seed in SEEDS {
+        if let Err(failure) = difftest::run(&make, OPS, seed) {
+            panic!("seed {}: {}", seed, failure);
+        }
+    }
+}
+
+#[test]
+fn basic_greedy_matches_oracle() {
+    assert_passes(|| BasicSSD::new(BLOCKS, LBAS));
+}
+
+#[test]
+fn basic_d_choices_with_bad_blocks_matches_oracle() {
+    assert_passes(|| {
+        let mut ssd = BasicSSD::with_seed(BLOCKS + 2, LBAS, 3);
+        ssd.set_victim_policy(VictimPolicy::DChoices { d: 2 });
+        ssd.place_bad_blocks(2).unwrap();
+        ssd
+    });
+}
+
+#[test]
+fn wear_leveling_matches_oracle() {
+    assert_passes(|| {
+        let mut ssd = WearLevelingSSD::new(BLOCKS, LBAS);
+        ssd.set_gap_threshold(2);
+        ssd
+    });
+}
+
+#[test]
+fn timed_background_gc_matches_oracle() {
+    assert_passes(|| {
+        let scheduler = GcScheduler::new(GcPolicy::GreedyPreemptive, 2, 4);
+        TimedSSD::new(BasicSSD::new(BLOCKS, LBAS), TimingModel::default(), scheduler)
+    });
+}
+
+// 일부러 망가뜨린 장치: LBA 13에 대한 trim을 무시
+struct IgnoresTrimOf13(BasicSSD);
+
+impl BlockDevice for IgnoresTrimOf13 {
+    fn read(&mut self, lba: usize) -> Result<Option<u32>, String> {
+        self.0.read(lba)
+    }
+
+    fn write(&mut self, lba: usize, data: u32) -> Result<(), String> {
+        self.0.write(lba, data)
+    }
+
+    fn trim(&mut self, lba: usize) -> Result<(), String> {
+        if lba == 13 { Ok(()) } else { self.0.trim(lba) }
+    }
+
+    fn num_lbas(&self) -> usize {
+        self.0.num_lbas()
+    }
+
+    fn stats(&self) -> &Stats {
+        self.0.stats()
+    }
+
+    fn reset_stats(&mut self) {
+        self.0.reset_stats()
+    }
+
+    fn blocks(&self) -> &[Block] {
+        self.0.blocks()
+    }
+
+    fn save_snapshot(&self, path: &str) -> Result<(), String> {
+        self.0.save_snapshot(path)
+    }
+}
+
+#[test]
+fn harness_finds_and_shrinks_a_bug() {
+    let ops: Vec<Op> = (0..2000)
+        .map(|i| match i % 50 {
+            10 => Op::Write { lba: 13, data: i },
+            30 => Op::Trim { lba: 13 },
+            _ => Op::Write { lba: (i as usize * 7) % LBAS, data: i },
+        })
+        .collect();
+
+    let make = || IgnoresTrimOf13(BasicSSD::new(BLOCKS, LBAS));
+    let failure = difftest::check(&make, &ops).expect_err("bug must be detected");
+    let minimal = difftest::shrink(&make, failure);
+
+    assert_eq!(minimal.ops.len(), 2, "{}", minimal);
+    assert!(matches!(minimal.ops[0], Op::Write { lba: 13, .. }));
+    assert_eq!(minimal.ops[1], Op::Trim { lba: 13 });
+}
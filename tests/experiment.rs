@@ -0,0 +1,105 @@
+use ssd_rs::experiment::{self, Experiment};
+
+fn small_experiment(extra: &str) -> Experiment {
+    let json = format!(
+        r#"{{
+            "name": "small",
+            "geometry": {{ "num_blocks": 16, "op_pct": 25 }},
+            "warmup": [ {{ "workload": {{ "pattern": {{ "type": "sequential", "start": 0 }} }}, "capacity_multiple": 1 }} ],
+            "measure": [ {{ "workload": {{ "pattern": {{ "type": "uniform" }} }}, "capacity_multiple": 4 }} ],
+            "seed": 7
+            {}
+        }}"#,
+        extra
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn every_bundled_experiment_parses_and_expands() {
+    for entry in std::fs::read_dir("test").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let exp = experiment::load(path.to_str().unwrap()).unwrap();
+            let variants = experiment::expand(&exp).unwrap();
+            let expected: usize = exp.sweep.iter().map(|s| s.values.len()).product();
+            assert_eq!(variants.len(), expected, "{}", path.display());
+        }
+    }
+    assert!(experiment::load("test/missing.json").is_err());
+}
+
+#[test]
+fn sweeps_expand_to_the_cross_product() {
+    let exp = small_experiment(
+        r#", "sweep": [
+            { "param": "geometry.op_pct", "values": [10, 20] },
+            { "param": "ftl", "values": [{ "type": "basic" }, { "type": "wear_leveling", "gap_threshold": 5 }] }
+        ]"#,
+    );
+    let variants = experiment::expand(&exp).unwrap();
+    let labels: Vec<&str> = variants.iter().map(|(label, _)| label.as_str()).collect();
+    assert_eq!(labels, vec![
+        "geometry.op_pct=10 ftl=basic",
+        "geometry.op_pct=10 ftl=wear_leveling(gap_threshold=5)",
+        "geometry.op_pct=20 ftl=basic",
+        "geometry.op_pct=20 ftl=wear_leveling(gap_threshold=5)",
+    ]);
+    assert_eq!(variants[2].1.geometry.op_pct, Some(20.0));
+    assert!(variants.iter().all(|(_, v)| v.sweep.is_empty()));
+
+    let unknown = small_experiment(r#", "sweep": [{ "param": "nope.op_pct", "values": [1] }]"#);
+    assert!(experiment::expand(&unknown).is_err());
+}
+
+#[test]
+fn repetitions_use_consecutive_seeds_and_are_reproducible() {
+    let exp = small_experiment(r#", "repetitions": 3"#);
+    let rows = experiment::run(&exp).unwrap();
+    assert_eq!(rows.iter().map(|r| (r.repetition, r.seed)).collect::<Vec<_>>(), vec![(0, 7), (1, 8), (2, 9)]);
+
+    let again = experiment::run(&exp).unwrap();
+    for (a, b) in rows.iter().zip(&again) {
+        assert_eq!((a.waf, a.gc_migrated_pages, a.erase_max), (b.waf, b.gc_migrated_pages, b.erase_max));
+    }
+    // 시간 모델이 없으면 지연 시간 열은 비어 있음
+    assert!(rows.iter().all(|r| r.write_p99_us.is_none()));
+}
+
+#[test]
+fn more_over_provisioning_lowers_measured_waf() {
+    let exp = small_experiment(r#", "sweep": [{ "param": "geometry.op_pct", "values": [10, 50] }]"#);
+    let rows = experiment::run(&exp).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert!(rows[0].op_pct < rows[1].op_pct);
+    assert!(rows[0].waf > rows[1].waf, "{} vs {}", rows[0].waf, rows[1].waf);
+    // warm-up의 순차 채우기는 측정에서 빠짐 (GC 없는 순차 쓰기만이면 WAF 1)
+    assert!(rows.iter().all(|r| r.waf > 1.0));
+}
+
+#[test]
+fn timing_config_records_latency_percentiles() {
+    let exp = small_experiment(
+        r#", "timing": { "gc_policy": { "type": "greedy_preemptive" }, "low_watermark": 2, "high_watermark": 4, "mean_interarrival_us": 2000 }"#,
+    );
+    let row = &experiment::run(&exp).unwrap()[0];
+    assert!(row.write_p99_us.unwrap() >= 500);
+    assert!(row.write_p999_us.unwrap() >= row.write_p99_us.unwrap());
+}
+
+#[test]
+fn invalid_configs_are_rejected() {
+    let both = r#"{ "num_blocks": 16, "num_lbas": 800, "op_pct": 10 }"#;
+    let mut exp = small_experiment("");
+    exp.geometry = serde_json::from_str(both).unwrap();
+    assert!(experiment::run(&exp).is_err());
+
+    let mut exp = small_experiment("");
+    exp.measure[0].requests = Some(100);
+    assert!(experiment::run(&exp).is_err());
+
+    let exp = small_experiment(
+        r#", "ftl": { "type": "wear_leveling", "gap_threshold": 5 }, "timing": { "gc_policy": { "type": "lazy" }, "low_watermark": 2, "high_watermark": 4, "mean_interarrival_us": 100 }"#,
+    );
+    assert!(experiment::run(&exp).is_err());
+}
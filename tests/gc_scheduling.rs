@@ -0,0 +1,145 @@
+use ssd_rs::block::PAGES_PER_BLOCK;
+use ssd_rs::gc::{GcPolicy, GcScheduler, GcStep, VictimPolicy};
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::timing::{LatencyStats, TimedSSD, TimingModel};
+
+// 모든 블록이 차도록 덮어써서 victim 후보를 만듦
+fn fill_and_overwrite(ssd: &mut SSD) {
+    for round in 0..2u32 {
+        for lba in 0..ssd.num_lbas() {
+            ssd.write(lba, round * 1000 + lba as u32).unwrap();
+        }
+    }
+}
+
+#[test]
+fn gc_step_copies_one_page_at_a_time_then_erases() {
+    let mut ssd = SSD::new(8, 400);
+    fill_and_overwrite(&mut ssd);
+    let free_before = ssd.count_free_blocks();
+    let migrated_before = ssd.stats().gc_migrated_pages;
+
+    let mut copied = 0;
+    while let GcStep::Copied { .. } = ssd.gc_step().unwrap() {
+        copied += 1;
+        assert!(ssd.gc_in_progress());
+    }
+
+    assert!(!ssd.gc_in_progress());
+    assert_eq!(ssd.stats().gc_migrated_pages - migrated_before, copied);
+    assert!(ssd.count_free_blocks() >= free_before);
+    for lba in 0..400 {
+        assert_eq!(ssd.read(lba).unwrap(), Some(1000 + lba as u32));
+    }
+    ssd.check_invariants().unwrap();
+}
+
+#[test]
+fn scheduler_policies_decide_when_to_run_in_the_background() {
+    let stw = GcScheduler::new(GcPolicy::StopTheWorld, 2, 4);
+    assert!(!stw.wants_background(0, true));
+
+    let lazy = GcScheduler::new(GcPolicy::Lazy, 2, 4);
+    assert!(!lazy.wants_background(3, false));
+    assert!(lazy.wants_background(2, false));
+    // 시작한 작업은 watermark와 상관없이 끝까지 진행
+    assert!(lazy.wants_background(10, true));
+
+    let greedy = GcScheduler::new(GcPolicy::GreedyPreemptive, 2, 4);
+    assert!(greedy.wants_background(3, false));
+    assert!(!greedy.wants_background(4, false));
+}
+
+#[test]
+fn token_bucket_waits_for_tokens() {
+    let mut scheduler = GcScheduler::new(GcPolicy::TokenBucket { rate_per_sec: 1000.0, burst: 2 }, 2, 4);
+    assert_eq!(scheduler.next_step_time(0), 0);
+    scheduler.consume(0);
+    scheduler.consume(0);
+    // 토큰이 바닥나면 1개가 찰 때까지 (1ms) 기다려야 함
+    assert_eq!(scheduler.next_step_time(0), 1000);
+    assert_eq!(scheduler.next_step_time(1000), 1000);
+}
+
+#[test]
+fn latency_percentiles_use_nearest_rank() {
+    let mut latency = LatencyStats::new();
+    assert_eq!(latency.percentile(99.0), 0);
+    for us in 1..=100 {
+        latency.record(us);
+    }
+    assert_eq!(latency.count(), 100);
+    assert_eq!(latency.percentile(50.0), 50);
+    assert_eq!(latency.percentile(99.0), 99);
+    assert_eq!(latency.percentile(99.9), 100);
+    assert_eq!(latency.max(), 100);
+    assert!((latency.mean() - 50.5).abs() < 1e-9);
+}
+
+// 요청 사이에 유휴 시간이 충분하면 선점형 GC는 호스트 쓰기를 기다리게 하지 않음
+#[test]
+fn background_gc_hides_gc_latency_from_host_writes() {
+    let timing = TimingModel::default();
+    let run = |policy: GcPolicy| {
+        let mut timed = TimedSSD::new(SSD::new(16, 400), timing, GcScheduler::new(policy, 2, 4));
+        for i in 0..4000u64 {
+            timed.write((i % 400) as usize, i as u32, i * 20_000).unwrap();
+        }
+        timed
+    };
+
+    let mut stw = run(GcPolicy::StopTheWorld);
+    let mut preemptive = run(GcPolicy::GreedyPreemptive);
+
+    assert_eq!(stw.background_steps(), 0);
+    assert!(stw.foreground_steps() > 0);
+    assert!(stw.write_latency().percentile(99.9) > timing.program_us);
+
+    assert!(preemptive.background_steps() > 0);
+    assert_eq!(preemptive.foreground_steps(), 0);
+    assert_eq!(preemptive.write_latency().max(), timing.program_us);
+
+    for lba in 0..400 {
+        assert_eq!(preemptive.ssd_mut().read(lba).unwrap(), stw.ssd_mut().read(lba).unwrap());
+    }
+    preemptive.ssd().check_invariants().unwrap();
+}
+
+// 범위 쓰기가 Active Block을 넘어가며 빈 블록이 바닥나면 내부 SSD가 요청 도중에 GC를 돌림 -> 포그라운드로 셈
+#[test]
+fn gc_inside_a_range_write_counts_as_foreground() {
+    let mut timed = TimedSSD::new(SSD::new(16, 400), TimingModel::default(), GcScheduler::new(GcPolicy::StopTheWorld, 2, 4));
+    let mut arrival = 0;
+    for round in 0..6u32 {
+        for start in (0..400).step_by(100) {
+            timed.write_range(start, &[round; 100], arrival).unwrap();
+            arrival += 1_000_000;
+        }
+    }
+    let steps = timed.ssd().gc_steps();
+    assert!(steps > 0);
+    assert_eq!(timed.background_steps(), 0);
+    assert_eq!(timed.foreground_steps(), steps);
+}
+
+// 후보 3개 중 2개를 중복 없이 뽑으면 무효 페이지가 가장 적은 블록은 절대 victim이 되지 않음
+#[test]
+fn d_choices_samples_distinct_candidates() {
+    for seed in 0..50 {
+        let mut ssd = SSD::with_seed(6, 3 * PAGES_PER_BLOCK, seed);
+        ssd.set_victim_policy(VictimPolicy::DChoices { d: 2 });
+        for lba in 0..3 * PAGES_PER_BLOCK {
+            ssd.write(lba, 0).unwrap();
+        }
+        // 블록 0, 1, 2의 무효 페이지 = 1, 2, 3
+        for (block, invalid) in [(0, 1), (1, 2), (2, 3)] {
+            for lba in block * PAGES_PER_BLOCK..block * PAGES_PER_BLOCK + invalid {
+                ssd.write(lba, 1).unwrap();
+            }
+        }
+        let victim = match ssd.gc_step().unwrap() {
+            GcStep::Copied { victim_idx, .. } | GcStep::Erased { victim_idx } => victim_idx,
+        };
+        assert_ne!(victim, 0, "seed {}", seed);
+    }
+}
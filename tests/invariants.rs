@@ -0,0 +1,109 @@
+use ssd_rs::ssd_basic::SSD as BasicSSD;
+use ssd_rs::ssd_wear_leveling::SSD as WearLevelingSSD;
+use ssd_rs::ssd_slc_cache::{SlcCacheConfig, SlcCachePolicy, SSD as SlcCacheSSD};
+
+fn churn(write: &mut impl FnMut(usize, u32) -> Result<(), String>) {
+    for i in 0..3000u32 {
+        write((i as usize * 7) % 100, i).unwrap();
+    }
+}
+
+#[test]
+fn debug_checks_pass_through_gc() {
+    let mut ssd = BasicSSD::new(6, 200);
+    ssd.set_debug_checks(true);
+    churn(&mut |lba, data| ssd.write(lba, data));
+    for lba in 0..50 {
+        ssd.trim(lba).unwrap();
+    }
+    ssd.gc_step().unwrap();
+    ssd.check_invariants().unwrap();
+    assert!(ssd.stats().gc_invocations > 0);
+
+    let mut wl = WearLevelingSSD::new(6, 200);
+    wl.set_debug_checks(true);
+    wl.set_gap_threshold(1);
+    churn(&mut |lba, data| wl.write(lba, data));
+    wl.check_invariants().unwrap();
+}
+
+// 스냅샷 JSON을 직접 망가뜨려서 각 위반이 잡히는지 확인
+fn corrupted(edit: impl Fn(&mut serde_json::Value)) -> Result<(), String> {
+    let mut ssd = BasicSSD::new(4, 100);
+    for lba in 0..80 {
+        ssd.write(lba, lba as u32).unwrap();
+    }
+    let mut json = serde_json::to_value(ssd.snapshot()).unwrap();
+    edit(&mut json);
+    BasicSSD::from_snapshot(serde_json::from_value(json).unwrap()).check_invariants()
+}
+
+#[test]
+fn detects_corruption() {
+    assert!(corrupted(|_| {}).is_ok());
+
+    let err = corrupted(|s| s["blocks"][0]["pages"][5]["state"] = "Invalid".into()).unwrap_err();
+    assert!(err.contains("LBA 5 maps to Block 0 Page 5"), "{}", err);
+
+    let err = corrupted(|s| s["mapping_table"]["entries"][5] = serde_json::Value::Null).unwrap_err();
+    assert!(err.contains("Block 0 Page 5 is Valid but no LBA maps to it"), "{}", err);
+
+    let err = corrupted(|s| s["mapping_table"]["entries"][6] = s["mapping_table"]["entries"][5].clone()).unwrap_err();
+    assert!(err.contains("both map to"), "{}", err);
+
+    let err = corrupted(|s| s["blocks"][1]["state"] = "Free".into()).unwrap_err();
+    assert!(err.contains("Block 1 is Free"), "{}", err);
+
+    let err = corrupted(|s| s["stats"]["nand_writes"] = 500.into()).unwrap_err();
+    assert!(err.contains("nand_writes 500"), "{}", err);
+}
+
+// 빈 블록 풀 / victim 색인 / 역방향 매핑은 스냅샷에 없으므로 복원할 때 다시 만들어져야 함
+#[test]
+fn indexes_survive_cell_modes_and_snapshots() {
+    let mut ssd = BasicSSD::new(8, 200);
+    for idx in [2, 5] {
+        ssd.set_cell_mode(idx, ssd_rs::cell::CellMode::Slc).unwrap();
+    }
+    ssd.set_debug_checks(true);
+    churn(&mut |lba, data| ssd.write(lba, data));
+    for lba in 0..30 {
+        ssd.trim(lba).unwrap();
+    }
+
+    let mut restored = BasicSSD::from_snapshot(ssd.snapshot());
+    restored.check_invariants().unwrap();
+    restored.set_debug_checks(true);
+    churn(&mut |lba, data| restored.write(lba, data));
+    let last_write = (0..3000u32).rfind(|i| *i as usize * 7 % 100 == 99);
+    assert_eq!(restored.read(99).unwrap(), last_write);
+    assert!(restored.stats().gc_invocations > 0);
+}
+
+#[test]
+fn wear_leveling_and_slc_cache_indexes_survive_snapshots() {
+    let mut wl = WearLevelingSSD::new(8, 200);
+    wl.set_gap_threshold(1);
+    wl.set_debug_checks(true);
+    churn(&mut |lba, data| wl.write(lba, data));
+    for lba in 0..30 {
+        wl.trim(lba).unwrap();
+    }
+    let mut restored = WearLevelingSSD::from_snapshot(wl.snapshot());
+    restored.check_invariants().unwrap();
+    restored.set_debug_checks(true);
+    churn(&mut |lba, data| restored.write(lba, data));
+    assert!(restored.stats().gc_invocations > 0);
+
+    let config = SlcCacheConfig { policy: SlcCachePolicy::Static { blocks: 2 }, ..SlcCacheConfig::default() };
+    let mut slc = SlcCacheSSD::new(8, 200, config).unwrap();
+    slc.set_debug_checks(true);
+    churn(&mut |lba, data| slc.write(lba, data));
+    let mut restored = SlcCacheSSD::from_snapshot(slc.snapshot());
+    restored.check_invariants().unwrap();
+    restored.set_debug_checks(true);
+    churn(&mut |lba, data| restored.write(lba, data));
+    restored.fold_all().unwrap();
+    restored.check_invariants().unwrap();
+    assert!(restored.stats().gc_invocations > 0);
+}
@@ -0,0 +1,161 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ssd_rs::metrics::{CsvSink, JsonlSink, MetricsRecorder, MetricsSample, MetricsSink};
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::ssd_slc_cache::{SlcCacheConfig, SSD as SlcCacheSSD};
+use ssd_rs::ssd_wear_leveling::SSD as WearLevelingSSD;
+
+// 받은 샘플을 그대로 모아 두는 sink
+struct Collect(Rc<RefCell<Vec<MetricsSample>>>);
+
+impl MetricsSink for Collect {
+    fn record(&mut self, sample: &MetricsSample) -> Result<(), String> {
+        self.0.borrow_mut().push(sample.clone());
+        Ok(())
+    }
+}
+
+// 디스크가 가득 찬 것처럼 항상 실패하는 sink
+struct Broken;
+
+impl MetricsSink for Broken {
+    fn record(&mut self, _: &MetricsSample) -> Result<(), String> {
+        Err("No space left on device".to_string())
+    }
+}
+
+fn overwrite(ssd: &mut SSD, writes: usize) {
+    for i in 0..writes {
+        ssd.write(i % ssd.num_lbas(), i as u32).unwrap();
+    }
+}
+
+#[test]
+fn samples_every_interval_host_writes() {
+    let samples = Rc::new(RefCell::new(Vec::new()));
+    let mut ssd = SSD::new(8, 300);
+    ssd.attach_metrics(MetricsRecorder::new(Box::new(Collect(samples.clone())), 100));
+    overwrite(&mut ssd, 1250);
+    ssd.detach_metrics().unwrap();
+
+    let samples = samples.borrow();
+    assert_eq!(samples.iter().map(|s| s.host_writes).collect::<Vec<_>>(), (1..=12).map(|i| i * 100).collect::<Vec<_>>());
+    // 첫 바퀴는 GC 없이 순차 쓰기
+    assert_eq!(samples[0].instant_waf, 1.0);
+    assert_eq!(samples[0].gc_count, 0);
+
+    let last = samples.last().unwrap();
+    assert!((last.cumulative_waf - last.nand_writes as f64 / last.host_writes as f64).abs() < 1e-12);
+    assert!(last.gc_count > 0);
+    // 구간 WAF의 가중 평균 = 누적 WAF
+    let nand: f64 = samples.iter().map(|s| s.instant_waf * 100.0).sum();
+    assert!((nand - last.nand_writes as f64).abs() < 1e-6);
+}
+
+#[test]
+fn reset_stats_restarts_the_interval_baseline() {
+    let samples = Rc::new(RefCell::new(Vec::new()));
+    let mut ssd = SSD::new(8, 300);
+    overwrite(&mut ssd, 900);
+    ssd.attach_metrics(MetricsRecorder::new(Box::new(Collect(samples.clone())), 50));
+    ssd.reset_stats();
+    overwrite(&mut ssd, 50);
+
+    let samples = samples.borrow();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].host_writes, 50);
+    assert_eq!(samples[0].instant_waf, samples[0].cumulative_waf);
+}
+
+#[test]
+fn sink_failures_do_not_fail_host_writes() {
+    let mut ssd = SSD::new(8, 300);
+    ssd.attach_metrics(MetricsRecorder::new(Box::new(Broken), 10));
+    overwrite(&mut ssd, 100);
+    ssd.write_fua(0, 7).unwrap();
+    assert_eq!(ssd.read(0).unwrap(), Some(7));
+    assert_eq!(ssd.metrics_error(), Some("No space left on device"));
+
+    let err = ssd.detach_metrics().unwrap_err();
+    assert!(err.contains("dropped 10 samples"), "{}", err);
+    assert_eq!(ssd.metrics_error(), None);
+
+    let mut wl = WearLevelingSSD::new(16, 300);
+    wl.attach_metrics(MetricsRecorder::new(Box::new(Broken), 10));
+    for i in 0..100 {
+        wl.write(i, 1).unwrap();
+    }
+    assert!(wl.metrics_error().is_some());
+    assert!(wl.detach_metrics().is_err());
+}
+
+#[test]
+fn csv_and_jsonl_sinks_write_one_row_per_sample() {
+    let dir = std::env::temp_dir();
+    let csv_path = dir.join(format!("metrics_{}.csv", std::process::id()));
+    let jsonl_path = dir.join(format!("metrics_{}.jsonl", std::process::id()));
+
+    let mut ssd = SSD::new(8, 300);
+    ssd.attach_metrics(MetricsRecorder::new(Box::new(CsvSink::create(csv_path.to_str().unwrap()).unwrap()), 100));
+    overwrite(&mut ssd, 300);
+    ssd.detach_metrics().unwrap();
+    ssd.attach_metrics(MetricsRecorder::new(Box::new(JsonlSink::create(jsonl_path.to_str().unwrap()).unwrap()), 100));
+    overwrite(&mut ssd, 300);
+    ssd.detach_metrics().unwrap();
+
+    let csv = std::fs::read_to_string(&csv_path).unwrap();
+    let jsonl = std::fs::read_to_string(&jsonl_path).unwrap();
+    std::fs::remove_file(&csv_path).unwrap();
+    std::fs::remove_file(&jsonl_path).unwrap();
+
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "host_writes,nand_writes,instant_waf,cumulative_waf,free_blocks,gc_count,wear_gap,erase_variance");
+    assert_eq!(lines.len(), 4);
+    assert!(lines[1].starts_with("100,100,1.000000,1.000000,"));
+
+    let rows: Vec<serde_json::Value> = jsonl.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[2]["host_writes"], 600);
+}
+
+#[test]
+fn range_writes_that_cross_an_interval_still_sample() {
+    let samples = Rc::new(RefCell::new(Vec::new()));
+    let mut ssd = SSD::new(8, 300);
+    ssd.attach_metrics(MetricsRecorder::new(Box::new(Collect(samples.clone())), 100));
+    // 32페이지씩: 96 -> 128에서 100을 건너뜀, 192 -> 224에서 200을 건너뜀
+    for i in 0..8 {
+        ssd.write_range((i * 32) % 256, &[i as u32; 32]).unwrap();
+    }
+    ssd.detach_metrics().unwrap();
+
+    let samples = samples.borrow();
+    assert_eq!(samples.iter().map(|s| s.host_writes).collect::<Vec<_>>(), vec![128, 224]);
+}
+
+#[test]
+fn slc_cache_ftl_samples_and_reports_sink_errors() {
+    let samples = Rc::new(RefCell::new(Vec::new()));
+    let mut slc = SlcCacheSSD::new(16, 1000, SlcCacheConfig::default()).unwrap();
+    slc.attach_metrics(MetricsRecorder::new(Box::new(Collect(samples.clone())), 500));
+    for i in 0..3000 {
+        slc.write(i % 1000, i as u32).unwrap();
+    }
+    slc.detach_metrics().unwrap();
+
+    let samples = samples.borrow();
+    assert_eq!(samples.iter().map(|s| s.host_writes).collect::<Vec<_>>(), (1..=6).map(|i| i * 500).collect::<Vec<_>>());
+    let last = samples.last().unwrap();
+    assert_eq!(last.nand_writes, slc.stats().nand_writes);
+    assert_eq!(last.gc_count, slc.stats().gc_invocations);
+
+    let mut slc = SlcCacheSSD::new(16, 1000, SlcCacheConfig::default()).unwrap();
+    slc.attach_metrics(MetricsRecorder::new(Box::new(Broken), 10));
+    for i in 0..100 {
+        slc.write(i, 1).unwrap();
+    }
+    assert_eq!(slc.metrics_error(), Some("No space left on device"));
+    assert!(slc.detach_metrics().is_err());
+    assert_eq!(slc.metrics_error(), None);
+}
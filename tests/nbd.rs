@@ -0,0 +1,174 @@
+#![cfg(unix)]
+
+use std::io::Write;
+use std::os::unix::net::UnixStream;
+use std::thread;
+
+use ssd_rs::device::BlockDevice;
+use ssd_rs::nbd::{NbdClient, NbdServer};
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::ssd_wear_leveling::SSD as WearLevelingSSD;
+
+const PAGE: usize = 4096;
+
+// 서버는 현재 스레드에서, 클라이언트는 별도 스레드에서 소켓 쌍으로 연결
+fn with_client<D: BlockDevice, T: Send + 'static>(
+    server: &mut NbdServer<D>,
+    client_fn: impl FnOnce(&mut NbdClient<UnixStream>) -> T + Send + 'static,
+) -> T {
+    let (server_end, client_end) = UnixStream::pair().expect("socket pair");
+    let client = thread::spawn(move || {
+        let mut client = NbdClient::connect(client_end, "").expect("handshake");
+        let result = client_fn(&mut client);
+        client.disconnect().expect("disconnect");
+        result
+    });
+    server.serve(server_end).expect("serve");
+    client.join().expect("client thread")
+}
+
+#[test]
+fn bytes_round_trip_through_the_ftl() {
+    let mut server = NbdServer::new(SSD::new(8, 300), PAGE).unwrap();
+    let size = server.size();
+
+    let (reported_size, aligned, unaligned, unwritten) = with_client(&mut server, |client| {
+        let page: Vec<u8> = (0..PAGE).map(|i| (i % 251) as u8).collect();
+        client.write(0, &page).unwrap();
+        // 두 페이지에 걸친 비정렬 쓰기 -> 양쪽 모두 read-modify-write
+        client.write(PAGE as u64 - 100, &[0xAB; 200]).unwrap();
+        client.flush().unwrap();
+
+        (
+            client.size(),
+            client.read(0, 100).unwrap(),
+            client.read(PAGE as u64 - 150, 300).unwrap(),
+            client.read(10 * PAGE as u64, 16).unwrap(),
+        )
+    });
+
+    assert_eq!(reported_size, size);
+    assert_eq!(aligned, (0..100).map(|i| (i % 251) as u8).collect::<Vec<u8>>());
+    let expected: Vec<u8> = (PAGE - 150..PAGE - 100)
+        .map(|i| (i % 251) as u8)
+        .chain(std::iter::repeat_n(0xAB, 200))
+        .chain(std::iter::repeat_n(0, 50))
+        .collect();
+    assert_eq!(unaligned, expected);
+    assert_eq!(unwritten, vec![0; 16]);
+
+    // 페이지 0은 두 번, 페이지 1은 한 번 쓰임
+    assert_eq!(server.ssd().stats().host_writes, 3);
+}
+
+#[test]
+fn trim_unmaps_whole_pages_only() {
+    let mut server = NbdServer::new(SSD::new(8, 300), PAGE).unwrap();
+
+    let (trimmed, partial) = with_client(&mut server, |client| {
+        client.write(0, &vec![0x11; 3 * PAGE]).unwrap();
+        // 페이지 1 전체 + 페이지 2 앞부분만 trim
+        client.trim(PAGE as u64, PAGE as u32 + 10).unwrap();
+        (client.read(PAGE as u64, 8).unwrap(), client.read(2 * PAGE as u64, 8).unwrap())
+    });
+
+    assert_eq!(trimmed, vec![0; 8]);
+    assert_eq!(partial, vec![0x11; 8]);
+    assert_eq!(server.ssd().stats().host_trims, 1);
+}
+
+#[test]
+fn data_survives_garbage_collection() {
+    let mut server = NbdServer::new(SSD::new(6, 256), PAGE).unwrap();
+    let lbas = 256u64;
+
+    let mismatches = with_client(&mut server, move |client| {
+        for round in 0..4u8 {
+            for lba in 0..lbas {
+                client.write(lba * PAGE as u64, &[round ^ lba as u8; PAGE]).unwrap();
+            }
+        }
+        (0..lbas)
+            .filter(|&lba| client.read(lba * PAGE as u64, PAGE as u32).unwrap() != vec![3 ^ lba as u8; PAGE])
+            .count()
+    });
+
+    assert_eq!(mismatches, 0);
+    assert!(server.ssd().stats().gc_invocations > 0);
+    assert!(server.ssd().stats().nand_erases > 0);
+}
+
+#[test]
+fn exports_other_ftls() {
+    let mut server = NbdServer::new(WearLevelingSSD::new(6, 256), PAGE).unwrap();
+    let lbas = 256u64;
+
+    let mismatches = with_client(&mut server, move |client| {
+        for round in 0..4u8 {
+            for lba in 0..lbas {
+                client.write(lba * PAGE as u64 + 100, &[round ^ lba as u8; 200]).unwrap();
+            }
+        }
+        (0..lbas)
+            .filter(|&lba| client.read(lba * PAGE as u64 + 100, 200).unwrap() != vec![3 ^ lba as u8; 200])
+            .count()
+    });
+
+    assert_eq!(mismatches, 0);
+    assert!(server.ssd().stats().gc_invocations > 0);
+    server.ssd().check_invariants().unwrap();
+}
+
+#[test]
+fn out_of_range_requests_fail_without_dropping_the_connection() {
+    let mut server = NbdServer::new(SSD::new(8, 300), PAGE).unwrap();
+    let size = server.size();
+
+    let (read_err, huge_read_err, write_err, still_ok) = with_client(&mut server, move |client| {
+        (
+            client.read(size - 10, 20).is_err(),
+            // 서버가 4 GiB 버퍼를 잡지 않고 EINVAL로 거절
+            client.read(0, u32::MAX).is_err(),
+            client.write(size, &[1, 2, 3]).is_err(),
+            client.read(0, 4).is_ok(),
+        )
+    });
+
+    assert!(read_err && huge_read_err && write_err && still_ok);
+}
+
+// 핸드셰이크를 망친 클라이언트가 있어도 서버는 다음 연결을 계속 받음
+#[test]
+fn a_broken_client_does_not_stop_the_server() {
+    let path = std::env::temp_dir().join(format!("nbd_{}.sock", std::process::id()));
+    let path = path.to_str().unwrap().to_string();
+    let mut server = NbdServer::new(SSD::new(8, 300), PAGE).unwrap();
+
+    let client_path = path.clone();
+    let clients = thread::spawn(move || {
+        let connect = || loop {
+            match UnixStream::connect(&client_path) {
+                Ok(stream) => return stream,
+                Err(_) => thread::sleep(std::time::Duration::from_millis(10)),
+            }
+        };
+        let mut broken = connect();
+        broken.write_all(&[0xFF; 64]).unwrap();
+        drop(broken);
+
+        let mut client = NbdClient::connect(connect(), "").unwrap();
+        client.write(0, &[9u8; PAGE]).unwrap();
+        client.disconnect().unwrap();
+    });
+
+    let mut disconnects = 0;
+    server.listen_unix(&path, |_| {
+        disconnects += 1;
+        disconnects < 2
+    }).unwrap();
+    clients.join().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(disconnects, 2);
+    assert_eq!(server.ssd().stats().host_writes, 1);
+}
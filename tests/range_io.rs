@@ -0,0 +1,108 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ssd_rs::device::BlockDevice;
+use ssd_rs::gc::{GcPolicy, GcScheduler};
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::ssd_slc_cache::{SlcCacheConfig, SSD as SlcCacheSSD};
+use ssd_rs::ssd_wear_leveling::SSD as WearLevelingSSD;
+use ssd_rs::timing::{TimedSSD, TimingModel};
+
+// 128 KiB ~ 1 MiB 요청 (4 KiB 페이지 기준 32 ~ 256 페이지)를 섞은 무작위 범위 요청열을 장치와 Vec 오라클에 함께 실행
+fn run_ranges(device: &mut dyn BlockDevice, ops: usize, seed: u64) {
+    let num_lbas = device.num_lbas();
+    let mut oracle = vec![None; num_lbas];
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    for i in 0..ops {
+        let len = if rng.gen_bool(0.5) { rng.gen_range(1..8) } else { rng.gen_range(32..=256) }.min(num_lbas);
+        let start = rng.gen_range(0..=num_lbas - len);
+        match rng.gen_range(0..10) {
+            0..=5 => {
+                let data: Vec<u32> = (0..len).map(|p| (i * 1000 + p) as u32).collect();
+                device.write_range(start, &data).unwrap();
+                for (p, &d) in data.iter().enumerate() {
+                    oracle[start + p] = Some(d);
+                }
+            }
+            6..=8 => assert_eq!(device.read_range(start, len).unwrap(), oracle[start..start + len], "op {}", i),
+            _ => {
+                device.trim_range(start, len).unwrap();
+                oracle[start..start + len].fill(None);
+            }
+        }
+    }
+    assert_eq!(device.read_range(0, num_lbas).unwrap(), oracle);
+    device.check_invariants().unwrap();
+}
+
+#[test]
+fn range_requests_match_oracle_on_every_ftl() {
+    let mut basic = SSD::new(24, 1000);
+    basic.set_debug_checks(true);
+    run_ranges(&mut basic, 400, 1);
+
+    run_ranges(&mut WearLevelingSSD::new(24, 1000), 400, 2);
+    run_ranges(&mut SlcCacheSSD::new(24, 1200, SlcCacheConfig::default()).unwrap(), 400, 3);
+
+    let scheduler = GcScheduler::new(GcPolicy::GreedyPreemptive, 2, 4);
+    run_ranges(&mut TimedSSD::new(SSD::new(24, 1000), TimingModel::default(), scheduler), 400, 4);
+}
+
+#[test]
+fn write_range_places_pages_like_single_writes() {
+    let mut ranged = SSD::with_seed(16, 700, 9);
+    let mut single = SSD::with_seed(16, 700, 9);
+    let mut rng = StdRng::seed_from_u64(9);
+
+    for i in 0..300u32 {
+        let len = rng.gen_range(1..=64);
+        let start = rng.gen_range(0..=700 - len);
+        let data = vec![i; len];
+        ranged.write_range(start, &data).unwrap();
+        for lba in start..start + len {
+            single.write(lba, i).unwrap();
+        }
+    }
+
+    // GC 시점과 배치가 같으므로 물리 상태가 완전히 같음 (요청 수만 다름)
+    assert_eq!(ranged.mapping_table().entries(), single.mapping_table().entries());
+    let (r, s) = (ranged.stats(), single.stats());
+    assert_eq!((r.host_writes, r.nand_writes, r.nand_erases, r.gc_invocations), (s.host_writes, s.nand_writes, s.nand_erases, s.gc_invocations));
+    assert_eq!(r.write_requests, 300);
+    assert_eq!(s.write_requests, s.host_writes);
+}
+
+#[test]
+fn per_request_stats() {
+    let mut ssd = SSD::new(16, 700);
+    ssd.write_range(0, &[7; 256]).unwrap();
+    ssd.write_range(300, &[8; 32]).unwrap();
+    ssd.read_range(0, 64).unwrap();
+    ssd.trim_range(0, 100).unwrap();
+    ssd.write(500, 1).unwrap();
+
+    let stats = ssd.stats();
+    assert_eq!((stats.write_requests, stats.read_requests, stats.trim_requests), (3, 1, 1));
+    assert_eq!((stats.host_writes, stats.host_reads, stats.host_trims), (289, 64, 100));
+    // 크기 구간: 1 / 32 / 64 / 100 / 256 페이지
+    assert_eq!(stats.request_size_histogram, vec![1, 0, 0, 0, 0, 1, 2, 0, 1]);
+    assert!((stats.mean_request_pages() - 453.0 / 5.0).abs() < 1e-9);
+}
+
+#[test]
+fn out_of_range_request_is_rejected_whole() {
+    let mut ssd = SSD::new(16, 700);
+    assert_eq!(ssd.write_range(690, &[1; 20]).unwrap_err(), "LBA 700 is out of range!");
+    assert_eq!(ssd.trim_range(700, 1).unwrap_err(), "LBA 700 is out of range!");
+    assert_eq!(ssd.stats().host_writes, 0);
+    assert_eq!(ssd.read(690).unwrap(), None);
+}
+
+#[test]
+fn timed_range_request_has_one_latency_sample() {
+    let scheduler = GcScheduler::new(GcPolicy::GreedyPreemptive, 2, 4);
+    let mut timed = TimedSSD::new(SSD::new(16, 700), TimingModel::default(), scheduler);
+    let latency = timed.write_range(0, &[1; 32], 0).unwrap();
+    assert_eq!(latency, 32 * TimingModel::default().program_us);
+    assert_eq!(timed.write_latency().count(), 1);
+}
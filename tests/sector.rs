@@ -0,0 +1,119 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ssd_rs::device::BlockDevice;
+use ssd_rs::sector::{SectorConfig, SectorDevice};
+use ssd_rs::ssd_basic::SSD;
+
+// 4 KiB 섹터, 16 KiB 페이지
+fn device() -> SectorDevice<SSD> {
+    let config = SectorConfig { sector_size: 4096, page_size: 16384 };
+    SectorDevice::new(SSD::new(16, 600), config).unwrap()
+}
+
+#[test]
+fn sector_requests_match_oracle() {
+    let mut dev = device();
+    let num_sectors = dev.num_lbas();
+    assert_eq!(num_sectors, 2400);
+    let mut oracle = vec![None; num_sectors];
+    let mut rng = StdRng::seed_from_u64(7);
+
+    for i in 0..3000 {
+        let len = rng.gen_range(1..=9);
+        let start = rng.gen_range(0..=num_sectors - len);
+        match rng.gen_range(0..10) {
+            0..=5 => {
+                let data: Vec<u32> = (0..len).map(|s| (i * 100 + s) as u32).collect();
+                dev.write_range(start, &data).unwrap();
+                for (s, &d) in data.iter().enumerate() {
+                    oracle[start + s] = Some(d);
+                }
+            }
+            6..=8 => assert_eq!(dev.read_range(start, len).unwrap(), oracle[start..start + len], "op {}", i),
+            _ => {
+                dev.trim_range(start, len).unwrap();
+                oracle[start..start + len].fill(None);
+            }
+        }
+    }
+    assert_eq!(dev.read_range(0, num_sectors).unwrap(), oracle);
+    dev.check_invariants().unwrap();
+}
+
+#[test]
+fn random_sub_page_writes_are_read_modify_write() {
+    let mut dev = device();
+    let mut rng = StdRng::seed_from_u64(3);
+    // 먼저 전체를 채운 뒤 4 KiB 무작위 쓰기
+    for start in (0..dev.num_lbas()).step_by(4) {
+        dev.write_range(start, &[1; 4]).unwrap();
+    }
+    dev.reset_stats();
+    for i in 0..2000 {
+        let sector = rng.gen_range(0..dev.num_lbas());
+        dev.write(sector, i).unwrap();
+    }
+
+    assert!((dev.translation_amplification() - 4.0).abs() < 1e-9);
+    assert_eq!(dev.sector_stats().rmw_reads, 2000);
+    assert!(dev.total_amplification() > dev.translation_amplification());
+}
+
+#[test]
+fn aligned_writes_need_no_rmw() {
+    let mut dev = device();
+    dev.write_range(8, &[1; 8]).unwrap();
+    dev.write_range(8, &[2; 8]).unwrap();
+    assert_eq!(dev.sector_stats().partial_page_writes, 0);
+    assert_eq!(dev.stats().host_writes, 4);
+
+    // 페이지 경계에 걸친 4섹터 쓰기: 두 페이지 모두 일부만 덮지만 기존 데이터는 앞 페이지에만 있음
+    dev.write_range(14, &[3; 4]).unwrap();
+    assert_eq!((dev.sector_stats().partial_page_writes, dev.sector_stats().rmw_reads), (2, 1));
+    assert_eq!(dev.read_range(12, 7).unwrap(), [vec![Some(2); 2], vec![Some(3); 4], vec![None]].concat());
+}
+
+#[test]
+fn page_is_trimmed_once_all_sectors_are_gone() {
+    let mut dev = device();
+    dev.write_range(0, &[1; 4]).unwrap();
+    dev.trim_range(0, 3).unwrap();
+    assert_eq!(dev.stats().host_trims, 0);
+    assert_eq!(dev.read(3).unwrap(), Some(1));
+    dev.trim(3).unwrap();
+    assert_eq!(dev.stats().host_trims, 1);
+    assert_eq!(dev.device().mapping_table().get(0), None);
+}
+
+#[test]
+fn page_size_must_be_a_multiple_of_sector_size() {
+    let config = SectorConfig { sector_size: 3000, page_size: 16384 };
+    assert!(SectorDevice::new(SSD::new(16, 600), config).is_err());
+    assert_eq!(device().write(2400, 1).unwrap_err(), "Sector 2400 is out of range!");
+}
+
+#[test]
+fn follows_inner_device_resize() {
+    let mut dev = device();
+    dev.write_range(2392, &[5; 8]).unwrap();
+
+    // 줄이면 잘려 나간 섹터는 범위 밖
+    dev.device_mut().resize(500).unwrap();
+    assert_eq!(dev.num_lbas(), 2000);
+    assert!(dev.write(2000, 1).is_err());
+    dev.write_range(1996, &[6; 4]).unwrap();
+    assert_eq!(dev.read_range(1996, 4).unwrap(), vec![Some(6); 4]);
+
+    // 다시 늘리면 잘려 나갔던 섹터는 빈 섹터
+    dev.device_mut().resize(600).unwrap();
+    assert_eq!(dev.num_lbas(), 2400);
+    assert_eq!(dev.read_range(2392, 8).unwrap(), vec![None; 8]);
+
+    // 사이에 접근 없이 줄였다 늘려도 그 페이지에 대한 부분 쓰기가 이전 내용을 되살리지 않음
+    dev.write_range(2392, &[8; 8]).unwrap();
+    dev.device_mut().resize(500).unwrap();
+    dev.device_mut().resize(600).unwrap();
+    dev.write(2393, 7).unwrap();
+    assert_eq!(dev.read_range(2392, 8).unwrap(), [vec![None, Some(7)], vec![None; 6]].concat());
+    dev.check_invariants().unwrap();
+}
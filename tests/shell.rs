@@ -0,0 +1,107 @@
+use ssd_rs::shell::{Outcome, Shell};
+use ssd_rs::ssd_basic::SSD;
+
+fn run(shell: &mut Shell, line: &str) -> String {
+    match shell.execute(line).unwrap() {
+        Outcome::Output(out) => out,
+        Outcome::Quit => panic!("unexpected quit"),
+    }
+}
+
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir().join(format!("shell_{}_{}", std::process::id(), name)).to_string_lossy().to_string()
+}
+
+#[test]
+fn undo_reverts_a_command_that_failed_halfway() {
+    let mut shell = Shell::new(SSD::new(4, 170));
+    run(&mut shell, "write 0 1");
+
+    // LBA 170부터 범위를 벗어나 실패하지만 앞쪽 LBA는 이미 써짐
+    let err = shell.execute("fill 0 1000").unwrap_err();
+    assert!(err.contains("170"), "{}", err);
+    assert_eq!(run(&mut shell, "read 100"), "LBA 100 = 0x00000064");
+
+    assert_eq!(run(&mut shell, "undo"), "Undone (1 more steps available)");
+    assert_eq!(run(&mut shell, "read 0"), "LBA 0 = 0x00000001");
+    assert_eq!(run(&mut shell, "read 100"), "LBA 100 is unmapped");
+    run(&mut shell, "check");
+
+    run(&mut shell, "undo");
+    assert_eq!(run(&mut shell, "read 0"), "LBA 0 is unmapped");
+    assert_eq!(run(&mut shell, "undo"), "Nothing to undo");
+}
+
+#[test]
+fn data_wider_than_32_bits_is_rejected() {
+    let mut shell = Shell::new(SSD::new(4, 170));
+    assert!(shell.execute("write 0 0x100000000").is_err());
+    assert!(shell.execute("fua 0 4294967296").is_err());
+    assert!(shell.execute("write 0 zero").is_err());
+    assert_eq!(run(&mut shell, "read 0"), "LBA 0 is unmapped");
+
+    assert_eq!(run(&mut shell, "write 0 0xFFFFFFFF"), "LBA 0 <- 0xFFFFFFFF");
+    assert_eq!(shell.ssd().stats().host_writes, 1);
+}
+
+#[test]
+fn gc_show_and_stats_commands() {
+    let mut shell = Shell::new(SSD::new(8, 170));
+    run(&mut shell, "fill 0 170");
+    run(&mut shell, "fill 0 64");
+
+    assert_eq!(run(&mut shell, "show map 3"), "LBA [3] -> Block 2 | Page 45");
+    assert!(shell.execute("show map 170").is_err());
+    assert!(run(&mut shell, "show block 0").contains("Valid Pgs:  0/64"));
+    assert!(shell.execute("show block 9").is_err());
+
+    // 블록 0은 전부 무효 -> 옮길 페이지 없이 바로 erase
+    assert_eq!(run(&mut shell, "gc step"), "Erased { victim_idx: 0 } (free blocks: 5)");
+    assert!(run(&mut shell, "stats").contains("GC:    invocations 1"));
+    assert_eq!(run(&mut shell, "check"), "Invariants OK");
+    assert!(run(&mut shell, "show heatmap").starts_with("=== Block Heatmap (8 blocks) ==="));
+    assert_eq!(run(&mut shell, "# comment"), "");
+    assert!(shell.execute("explode").is_err());
+    assert!(matches!(shell.execute("quit").unwrap(), Outcome::Quit));
+}
+
+#[test]
+fn snapshot_and_load_go_through_undo_history() {
+    let path = temp_path("snap.json");
+    let mut shell = Shell::new(SSD::new(4, 170));
+    run(&mut shell, "write 5 7");
+    run(&mut shell, &format!("snapshot {}", path));
+    run(&mut shell, "trim 5");
+    run(&mut shell, &format!("load {}", path));
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(run(&mut shell, "read 5"), "LBA 5 = 0x00000007");
+    run(&mut shell, "undo");
+    assert_eq!(run(&mut shell, "read 5"), "LBA 5 is unmapped");
+}
+
+#[test]
+fn scripts_print_a_transcript_and_stop_at_the_first_error() {
+    let ok = temp_path("ok.txt");
+    std::fs::write(&ok, "# setup\nwrite 1 0x10\n\nread 1\n").unwrap();
+    let mut shell = Shell::new(SSD::new(4, 170));
+    let Outcome::Output(out) = shell.run_script(&ok).unwrap() else { panic!() };
+    assert_eq!(out, "> write 1 0x10\nLBA 1 <- 0x00000010\n> read 1\nLBA 1 = 0x00000010");
+
+    let bad = temp_path("bad.txt");
+    std::fs::write(&bad, "write 2 2\nwrite 999 1\nwrite 3 3\n").unwrap();
+    let err = shell.run_script(&bad).unwrap_err();
+    assert!(err.starts_with(&format!("{}:2:", bad)), "{}", err);
+    assert_eq!(run(&mut shell, "read 3"), "LBA 3 is unmapped");
+
+    // 명령줄에서도 같은 스크립트를 재생
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_ssd-sim"))
+        .args(["shell", "--blocks", "8", "--script", &ok])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("LBA 1 = 0x00000010"));
+
+    std::fs::remove_file(&ok).unwrap();
+    std::fs::remove_file(&bad).unwrap();
+}
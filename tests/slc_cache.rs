@@ -0,0 +1,94 @@
+use ssd_rs::cell::CellMode;
+use ssd_rs::device::BlockDevice;
+use ssd_rs::difftest;
+use ssd_rs::ssd_slc_cache::{SlcCacheConfig, SlcCachePolicy, SSD};
+
+const STATIC: SlcCachePolicy = SlcCachePolicy::Static { blocks: 2 };
+const DYNAMIC: SlcCachePolicy = SlcCachePolicy::Dynamic { free_space_pct: 30.0 };
+
+fn device(num_blocks: usize, num_lbas: usize, policy: SlcCachePolicy) -> SSD {
+    let mut ssd = SSD::new(num_blocks, num_lbas, SlcCacheConfig { policy, dense_mode: CellMode::Tlc }).unwrap();
+    ssd.set_debug_checks(true);
+    ssd
+}
+
+#[test]
+fn static_and_dynamic_caches_match_oracle() {
+    for policy in [STATIC, DYNAMIC] {
+        for seed in [3, 11] {
+            if let Err(failure) = difftest::run(|| device(12, 600, policy), 2000, seed) {
+                panic!("{:?} seed {}: {}", policy, seed, failure);
+            }
+        }
+    }
+}
+
+// 쓰기가 TLC 프로그램보다 빠르게 계속 들어오면: 처음엔 SLC 속도, 캐시가 차면 TLC 속도 이상 (write cliff)
+fn sustained_latencies(policy: SlcCachePolicy) -> (Vec<u64>, SSD) {
+    let mut ssd = device(16, 1000, policy);
+    ssd.set_debug_checks(false);
+    let mut latencies = Vec::new();
+    for i in 0..1000u64 {
+        let arrival = i * 300;
+        ssd.set_arrival(arrival);
+        BlockDevice::write(&mut ssd, i as usize, i as u32).unwrap();
+        latencies.push(ssd.now_us() - arrival);
+    }
+    (latencies, ssd)
+}
+
+#[test]
+fn sustained_writes_fall_off_the_cliff() {
+    let slc = CellMode::Slc.timing().program_us;
+    let tlc = CellMode::Tlc.timing().program_us;
+
+    for policy in [STATIC, DYNAMIC] {
+        let (latencies, ssd) = sustained_latencies(policy);
+        assert!(latencies[..32].iter().all(|&l| l == slc), "{:?}: {:?}", policy, &latencies[..32]);
+        assert!(*latencies.last().unwrap() > tlc, "{:?}: no cliff", policy);
+
+        let stats = ssd.stats();
+        assert!(stats.slc_writes > 0 && stats.slc_overflows > 0);
+        assert_eq!(stats.slc_writes + stats.slc_overflows, 1000);
+        ssd.check_invariants().unwrap();
+    }
+
+    // 같은 입력이면 같은 결과 (재현 가능)
+    assert_eq!(sustained_latencies(DYNAMIC).0, sustained_latencies(DYNAMIC).0);
+}
+
+#[test]
+fn idle_time_folds_the_cache() {
+    let mut ssd = device(12, 600, STATIC);
+    for lba in 0..64 {
+        ssd.write(lba, lba as u32).unwrap();
+    }
+    assert_eq!(ssd.count_slc_blocks_in_use(), 2);
+    assert_eq!(ssd.stats().slc_writes, 64);
+
+    // 충분히 쉰 뒤의 쓰기는 다시 SLC 캐시로
+    ssd.set_arrival(ssd.now_us() + 1_000_000);
+    BlockDevice::write(&mut ssd, 100, 1).unwrap();
+    assert_eq!(ssd.stats().folded_pages, 64);
+    assert_eq!(ssd.stats().slc_writes, 65);
+    assert_eq!(ssd.count_slc_blocks_in_use(), 1);
+
+    for lba in 0..64 {
+        assert_eq!(ssd.read(lba).unwrap(), Some(lba as u32));
+    }
+}
+
+#[test]
+fn dynamic_cache_shrinks_as_the_drive_fills() {
+    let mut ssd = device(16, 1200, DYNAMIC);
+    let empty = ssd.cache_budget_blocks();
+    for lba in 0..1200 {
+        ssd.write(lba, 0).unwrap();
+    }
+    assert!(ssd.cache_budget_blocks() < empty);
+
+    for lba in 0..1200 {
+        ssd.trim(lba).unwrap();
+    }
+    assert_eq!(ssd.cache_budget_blocks(), empty);
+}
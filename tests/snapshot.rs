@@ -0,0 +1,96 @@
+use std::process::Command;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ssd_rs::alloc::AllocPolicy;
+use ssd_rs::gc::VictimPolicy;
+use ssd_rs::ssd_basic::{SsdSnapshot, SSD};
+
+// FTL 난수를 쓰는 설정 (d-choices GC, 무작위 할당, 배드 블록 배치)
+fn randomized_ssd() -> SSD {
+    let mut ssd = SSD::with_seed(32, 1500, 5);
+    ssd.set_victim_policy(VictimPolicy::DChoices { d: 3 });
+    ssd.set_alloc_policy(AllocPolicy::Random);
+    ssd.place_bad_blocks(2).unwrap();
+    ssd
+}
+
+fn overwrite(ssd: &mut SSD, seed: u64, writes: usize) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    for i in 0..writes {
+        ssd.write(rng.gen_range(0..ssd.num_lbas()), i as u32).unwrap();
+    }
+}
+
+fn state(ssd: &SSD) -> String {
+    serde_json::to_string(&ssd.snapshot()).unwrap()
+}
+
+#[test]
+fn restored_device_continues_exactly_like_the_original() {
+    let mut original = randomized_ssd();
+    let mut control = randomized_ssd();
+    overwrite(&mut original, 1, 6000);
+    overwrite(&mut control, 1, 6000);
+
+    // 파일로 저장했다가 읽는 것과 같은 경로 (JSON 왕복)
+    let json = serde_json::to_string(&original.snapshot()).unwrap();
+    let mut restored = SSD::from_snapshot(serde_json::from_str::<SsdSnapshot>(&json).unwrap());
+    assert_eq!(state(&restored), state(&original));
+    // 스냅샷을 떠도 원본 장치의 난수열은 그대로
+    assert_eq!(state(&original), state(&control));
+
+    for ssd in [&mut original, &mut control, &mut restored] {
+        overwrite(ssd, 2, 6000);
+        ssd.check_invariants().unwrap();
+    }
+    assert_eq!(state(&original), state(&control));
+    assert_eq!(state(&restored), state(&original));
+    assert_eq!(restored.stats().lba_writes, control.stats().lba_writes);
+    assert!(control.stats().gc_invocations > 0);
+}
+
+#[test]
+fn lba_writes_are_only_serialized_inside_snapshots() {
+    let mut ssd = SSD::new(8, 100);
+    ssd.write(3, 1).unwrap();
+
+    let stats = serde_json::to_value(ssd.stats()).unwrap();
+    assert!(stats.get("lba_writes").is_none());
+    assert_eq!(stats["host_writes"], 1);
+
+    let snapshot = serde_json::to_value(ssd.snapshot()).unwrap();
+    assert_eq!(snapshot["stats"]["lba_writes"][3], 1);
+    assert_eq!(snapshot["stats"]["host_writes"], 1);
+}
+
+fn ssd_sim(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_ssd-sim")).args(args).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn cli_saves_resumes_and_reports_snapshots() {
+    let dir = std::env::temp_dir();
+    let path = |name: &str| dir.join(format!("ssd_sim_{}_{}", std::process::id(), name)).to_string_lossy().to_string();
+    let (first, resumed, again, report) = (path("first.json"), path("resumed.json"), path("again.json"), path("report.json"));
+
+    ssd_sim(&["workload", "--blocks", "32", "--op", "20", "--fill", "--requests", "3000", "--save", &first]);
+    ssd_sim(&["workload", "--load", &first, "--requests", "3000", "--seed", "1", "--save", &resumed]);
+    ssd_sim(&["workload", "--load", &first, "--requests", "3000", "--seed", "1", "--save", &again]);
+    // 같은 스냅샷에서 같은 워크로드를 이어 돌리면 결과 장치도 같음
+    assert_eq!(std::fs::read(&resumed).unwrap(), std::fs::read(&again).unwrap());
+
+    let out = ssd_sim(&["stats", &resumed, "--json", &report]);
+    assert!(out.contains("Device: 32 blocks"), "{}", out);
+    let report_json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    let restored = SSD::load_snapshot(&resumed).unwrap();
+    assert_eq!(report_json["stats"]["host_writes"], restored.stats().host_writes);
+    assert!(report_json["stats"].get("lba_writes").is_none());
+    restored.check_invariants().unwrap();
+
+    for file in [first, resumed, again, report] {
+        std::fs::remove_file(file).unwrap();
+    }
+}
@@ -0,0 +1,139 @@
+use ssd_rs::block::Block;
+use ssd_rs::experiment::{self, Experiment};
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::stats::{EraseDistribution, FREE_BLOCK_SAMPLES};
+
+#[test]
+fn counts_host_and_nand_operations() {
+    let mut ssd = SSD::new(8, 300);
+    for round in 0..4u32 {
+        for lba in 0..300 {
+            ssd.write(lba, round).unwrap();
+        }
+    }
+    ssd.read(0).unwrap();
+    ssd.trim(1).unwrap();
+
+    let stats = ssd.stats();
+    assert_eq!((stats.host_writes, stats.host_reads, stats.host_trims), (1200, 1, 1));
+    assert_eq!(stats.nand_writes, stats.host_writes + stats.gc_migrated_pages);
+    assert_eq!(stats.victim_valid_histogram.iter().sum::<u64>(), stats.gc_invocations);
+    assert_eq!(stats.free_block_histogram.iter().sum::<u64>(), stats.gc_invocations);
+    assert_eq!(stats.free_block_samples.len() as u64, stats.gc_invocations);
+    assert!(stats.nand_erases > 0 && stats.nand_erases <= stats.gc_invocations);
+    assert!((stats.waf() - stats.nand_writes as f64 / 1200.0).abs() < 1e-12);
+    assert_eq!(stats.lba_writes[0], 4);
+    assert!(stats.min_free_blocks().is_some());
+}
+
+#[test]
+fn free_block_samples_stay_bounded() {
+    let mut ssd = SSD::new(8, 300);
+    for i in 0..100_000 {
+        ssd.write(i % 300, i as u32).unwrap();
+    }
+
+    let stats = ssd.stats();
+    assert!(stats.gc_invocations > 4 * FREE_BLOCK_SAMPLES as u64);
+    assert!(stats.free_block_samples.len() <= FREE_BLOCK_SAMPLES);
+    assert!(stats.free_block_sample_stride >= 4);
+    // 시계열은 처음부터 끝까지 고르게 남음
+    assert_eq!(stats.free_block_samples.len() as u64, stats.gc_invocations.div_ceil(stats.free_block_sample_stride));
+    assert!(stats.free_block_samples.windows(2).all(|w| w[0].0 < w[1].0));
+    assert!(stats.free_block_samples.last().unwrap().0 > 90_000);
+    // 최솟값은 솎아낸 표본이 아니라 전체 GC 기준
+    assert_eq!(stats.free_block_histogram.iter().sum::<u64>(), stats.gc_invocations);
+    assert_eq!(stats.min_free_blocks(), stats.free_block_histogram.iter().position(|&n| n > 0));
+    ssd.check_invariants().unwrap();
+}
+
+#[test]
+fn reset_clears_counters_but_not_wear() {
+    let mut ssd = SSD::new(8, 300);
+    for i in 0..1500 {
+        ssd.write(i % 300, i as u32).unwrap();
+    }
+    let wear = ssd.erase_distribution();
+    ssd.reset_stats();
+
+    let stats = ssd.stats();
+    assert_eq!((stats.host_writes, stats.nand_writes, stats.gc_invocations), (0, 0, 0));
+    assert_eq!(stats.waf(), 0.0);
+    assert_eq!(stats.lba_writes.len(), 300);
+    assert!(stats.min_free_blocks().is_none());
+    assert_eq!(ssd.erase_distribution().max, wear.max);
+}
+
+#[test]
+fn erase_distribution_percentiles_skip_bad_blocks() {
+    let mut blocks: Vec<Block> = (0..10).map(Block::new).collect();
+    for (i, block) in blocks.iter_mut().enumerate() {
+        block.erase_count = i as u32 + 1;
+    }
+    blocks[9].is_bad = true;
+
+    let dist = EraseDistribution::from_blocks(&blocks);
+    assert_eq!((dist.min, dist.max), (1, 9));
+    assert_eq!((dist.p50, dist.p90, dist.p99), (5, 9, 9));
+    assert!((dist.mean - 5.0).abs() < 1e-12);
+    assert!((dist.std_dev - (60.0f64 / 9.0).sqrt()).abs() < 1e-12);
+
+    let empty = EraseDistribution::from_blocks(&[]);
+    assert_eq!((empty.min, empty.max, empty.std_dev), (0, 0, 0.0));
+}
+
+// 열 위치로 읽는 스크립트가 있으므로 기존 열은 그대로, 새 열은 뒤에
+#[test]
+fn csv_keeps_existing_columns_and_appends_new_ones() {
+    let exp: Experiment = serde_json::from_str(
+        r#"{
+            "name": "csv",
+            "geometry": { "num_blocks": 16, "op_pct": 25 },
+            "measure": [ { "workload": { "pattern": { "type": "uniform" } }, "capacity_multiple": 3 } ]
+        }"#,
+    )
+    .unwrap();
+    let rows = experiment::run(&exp).unwrap();
+    let path = std::env::temp_dir().join(format!("experiment_{}.csv", std::process::id()));
+    experiment::write_csv(&rows, path.to_str().unwrap()).unwrap();
+    let csv = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let mut lines = csv.lines();
+    let header: Vec<&str> = lines.next().unwrap().split(',').collect();
+    assert_eq!(&header[..14], &[
+        "experiment", "params", "repetition", "seed", "num_blocks", "num_lbas", "op_pct", "waf",
+        "erase_min", "erase_max", "erase_gap", "read_p99_us", "write_p99_us", "write_p999_us",
+    ]);
+    assert_eq!(&header[14..], &["gc_invocations", "gc_migrated_pages", "erase_std_dev"]);
+
+    let row = &rows[0];
+    let fields: Vec<&str> = lines.next().unwrap().split(',').collect();
+    assert_eq!(fields.len(), header.len());
+    assert_eq!(row.erase_gap, row.erase_max - row.erase_min);
+    assert_eq!(fields[10], row.erase_gap.to_string());
+    assert_eq!(fields[14], row.gc_invocations.to_string());
+}
+
+#[test]
+fn csv_quotes_experiment_names() {
+    let exp: Experiment = serde_json::from_str(
+        r#"{
+            "name": "hot, \"80/20\"",
+            "geometry": { "num_blocks": 16, "op_pct": 25 },
+            "measure": [ { "workload": { "pattern": { "type": "uniform" } }, "capacity_multiple": 1 } ]
+        }"#,
+    )
+    .unwrap();
+    let rows = experiment::run(&exp).unwrap();
+    let path = std::env::temp_dir().join(format!("experiment_quoted_{}.csv", std::process::id()));
+    experiment::write_csv(&rows, path.to_str().unwrap()).unwrap();
+    let csv = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let line = csv.lines().nth(1).unwrap();
+    assert!(line.starts_with(r#""hot, ""80/20""","","#), "{}", line);
+    // 따옴표 안의 쉼표를 빼면 열 수는 헤더와 같음
+    let header = csv.lines().next().unwrap().split(',').count();
+    assert_eq!(line.split(',').count(), header + 1);
+}
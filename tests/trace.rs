@@ -0,0 +1,121 @@
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::trace::{self, ReplayConfig, TraceFormat, TraceOp, TraceRecord};
+
+fn record(op: TraceOp, offset: u64, size: u64) -> TraceRecord {
+    TraceRecord { timestamp_us: 0, op, offset, size }
+}
+
+#[test]
+fn parses_each_format() {
+    let msr = trace::parse_line(TraceFormat::Msr, "128166372003061629,hm,0,Write,8192,4096,1331").unwrap();
+    assert_eq!(msr, Some(TraceRecord { timestamp_us: 12816637200306162, op: TraceOp::Write, offset: 8192, size: 4096 }));
+
+    let spc = trace::parse_line(TraceFormat::Spc, "0,16,4096,r,1.5").unwrap();
+    assert_eq!(spc, Some(TraceRecord { timestamp_us: 1_500_000, op: TraceOp::Read, offset: 16 * 512, size: 4096 }));
+
+    let blk = trace::parse_line(TraceFormat::Blkparse, "8,0 1 1 0.000100 42 Q D 2048 + 16 [fstrim]").unwrap();
+    assert_eq!(blk, Some(TraceRecord { timestamp_us: 100, op: TraceOp::Trim, offset: 2048 * 512, size: 16 * 512 }));
+}
+
+#[test]
+fn skips_headers_comments_and_other_events() {
+    assert_eq!(trace::parse_line(TraceFormat::Msr, "Timestamp,Hostname,DiskNumber,Type,Offset,Size,ResponseTime").unwrap(), None);
+    assert_eq!(trace::parse_line(TraceFormat::Spc, "# comment").unwrap(), None);
+    assert_eq!(trace::parse_line(TraceFormat::Spc, "   ").unwrap(), None);
+    // Q가 아닌 이벤트와 데이터 없는 flush
+    assert_eq!(trace::parse_line(TraceFormat::Blkparse, "8,0 1 2 0.000200 42 C W 2048 + 8 [0]").unwrap(), None);
+    assert_eq!(trace::parse_line(TraceFormat::Blkparse, "8,0 1 3 0.000300 42 Q F 0 + 0 [sync]").unwrap(), None);
+    assert_eq!(trace::parse_line(TraceFormat::Blkparse, "CPU0 (8,0):").unwrap(), None);
+}
+
+#[test]
+fn rejects_short_and_malformed_lines() {
+    // ResponseTime이 빠진 6필드 MSR 줄
+    assert!(trace::parse_line(TraceFormat::Msr, "128166372003061629,hm,0,Write,8192,4096").is_err());
+    assert!(trace::parse_line(TraceFormat::Msr, "128166372003061629,hm,0,Erase,8192,4096,1331").is_err());
+    assert!(trace::parse_line(TraceFormat::Msr, "soon,hm,0,Read,8192,4096,1331").is_err());
+    assert!(trace::parse_line(TraceFormat::Spc, "0,16,4096,r").is_err());
+    assert!(trace::parse_line(TraceFormat::Spc, "0,-16,4096,r,1.5").is_err());
+    assert!(trace::parse_line(TraceFormat::Spc, "0,16,4096,x,1.5").is_err());
+    assert!(trace::parse_line(TraceFormat::Blkparse, "8,0 1 1 0.0001 42 Q W many + 8 [dd]").is_err());
+    assert!(TraceFormat::from_name("csv").is_err());
+}
+
+#[test]
+fn rejects_addresses_that_overflow() {
+    let huge = u64::MAX / 512 + 1;
+    assert!(trace::parse_line(TraceFormat::Spc, &format!("0,{},4096,w,0.0", huge)).is_err());
+    assert!(trace::parse_line(TraceFormat::Blkparse, &format!("8,0 1 1 0.0001 42 Q W {} + 8 [dd]", huge)).is_err());
+    assert!(trace::parse_line(TraceFormat::Blkparse, &format!("8,0 1 1 0.0001 42 Q W 0 + {} [dd]", huge)).is_err());
+    assert!(trace::parse_line(TraceFormat::Msr, &format!("0,hm,0,Write,{},4096,1331", u64::MAX - 100)).is_err());
+
+    let path = std::env::temp_dir().join(format!("trace_overflow_{}.csv", std::process::id()));
+    std::fs::write(&path, format!("0,0,4096,w,0.0\n0,{},4096,w,0.1\n", huge)).unwrap();
+    let err = trace::load_trace(path.to_str().unwrap(), TraceFormat::Spc).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(err.contains(":2:"), "{}", err);
+
+    // 직접 만든 레코드도 변환 중에 넘치지 않고 에러
+    let config = ReplayConfig::default();
+    assert!(config.lba_range(&record(TraceOp::Write, u64::MAX - 10, 4096), 10).is_err());
+}
+
+#[test]
+fn load_trace_reports_line_numbers() {
+    let path = std::env::temp_dir().join(format!("trace_{}.csv", std::process::id()));
+    std::fs::write(&path, "0,0,4096,w,0.0\n0,8,4096,w,0.1\n0,8,4096\n").unwrap();
+    let err = trace::load_trace(path.to_str().unwrap(), TraceFormat::Spc).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(err.contains(":3:"), "{}", err);
+}
+
+#[test]
+fn lba_range_aligns_and_wraps() {
+    let config = ReplayConfig::default();
+    assert_eq!(config.lba_range(&record(TraceOp::Read, 4000, 200), 10).unwrap(), vec![(0, 2)]);
+    assert_eq!(config.lba_range(&record(TraceOp::Read, 8192, 0), 10).unwrap(), vec![(2, 1)]);
+    assert_eq!(config.lba_range(&record(TraceOp::Write, 9 * 4096, 8192), 10).unwrap(), vec![(9, 1), (0, 1)]);
+    assert_eq!(config.lba_range(&record(TraceOp::Write, 25 * 4096, 4 * 4096), 10).unwrap(), vec![(5, 4)]);
+    assert_eq!(config.lba_range(&record(TraceOp::Write, 4096, 10 * 4096), 10).unwrap(), vec![(1, 9), (0, 1)]);
+    assert!(config.lba_range(&record(TraceOp::Write, 0, 4096), 0).is_err());
+
+    let strict = ReplayConfig::new(4096, false).unwrap();
+    assert!(strict.lba_range(&record(TraceOp::Write, 9 * 4096, 8192), 10).is_err());
+
+    assert!(ReplayConfig::new(0, true).is_err());
+    let zero = ReplayConfig { lba_size: 0, ..ReplayConfig::default() };
+    assert!(zero.lba_range(&record(TraceOp::Read, 0, 4096), 10).is_err());
+}
+
+// 용량보다 큰 요청은 LBA를 하나씩 펼치지 않고 바로 에러 (같은 LBA를 여러 번 덮지도 않음)
+#[test]
+fn rejects_requests_larger_than_the_device() {
+    let huge = trace::parse_line(TraceFormat::Spc, "0,0,18446744073709551615,w,0").unwrap().unwrap();
+    let config = ReplayConfig::default();
+    let err = config.lba_range(&huge, 10).unwrap_err();
+    assert!(err.contains("exceeds SSD capacity"), "{}", err);
+    assert!(config.lba_range(&record(TraceOp::Write, 0, 11 * 4096), 10).is_err());
+
+    let mut ssd = SSD::new(16, 400);
+    assert!(trace::replay(&mut ssd, &[huge], &config).is_err());
+    assert_eq!(ssd.stats().host_writes, 0);
+}
+
+#[test]
+fn replay_applies_writes_reads_and_trims() {
+    let records = [
+        record(TraceOp::Write, 0, 3 * 4096),
+        record(TraceOp::Read, 0, 4 * 4096),
+        record(TraceOp::Trim, 4096, 4096),
+    ];
+    let mut ssd = SSD::new(8, 100);
+    let summary = trace::replay(&mut ssd, &records, &ReplayConfig::default()).unwrap();
+
+    assert_eq!(summary.requests, 3);
+    assert_eq!((summary.write_pages, summary.read_pages, summary.trim_pages), (3, 4, 1));
+    assert_eq!(summary.unmapped_reads, 1);
+    // 쓰기 데이터는 레코드 번호
+    assert_eq!(ssd.read(0).unwrap(), Some(0));
+    assert_eq!(ssd.read(1).unwrap(), None);
+    assert_eq!(ssd.read(2).unwrap(), Some(0));
+}
@@ -0,0 +1,97 @@
+use ssd_rs::block::{Block, PAGES_PER_BLOCK};
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::visualize::*;
+
+// 블록 0~3에 순차로 쓰고 블록 0의 절반을 덮어써서 무효화
+fn half_invalid_ssd() -> SSD {
+    let mut ssd = SSD::new(8, 256);
+    for lba in 0..256 {
+        ssd.write(lba, 0).unwrap();
+    }
+    for lba in 0..PAGES_PER_BLOCK / 2 {
+        ssd.write(lba, 1).unwrap();
+    }
+    ssd
+}
+
+#[test]
+fn terminal_heatmap_shows_valid_ratio_and_erase_count() {
+    let mut blocks: Vec<Block> = (0..6).map(Block::new).collect();
+    blocks[0].erase_count = 4;
+    blocks[1].erase_count = 2;
+    blocks[5].is_bad = true;
+
+    let ssd = half_invalid_ssd();
+    blocks[2] = ssd.blocks()[0].clone();
+    blocks[3] = ssd.blocks()[1].clone();
+
+    let out = terminal_heatmap(&blocks, 3);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[0], "=== Block Heatmap (6 blocks) ===");
+    // 빈 블록 '·', 절반 유효 '▒', 전부 유효 '█', 배드 'X' / 한 줄에 3개
+    assert_eq!(lines[2], "     0 |··▒|");
+    assert_eq!(lines[3], "     3 |█·X|");
+    assert_eq!(lines[4], "Erase count  (' '=0 .. '█'=4, 'X'=bad)");
+    assert_eq!(lines[5], "     0 |█▒ |");
+    assert_eq!(lines[6], "     3 |  X|");
+}
+
+#[test]
+fn block_state_raster_has_one_pixel_per_page() {
+    let ssd = half_invalid_ssd();
+    let raster = block_state_raster(ssd.blocks());
+    assert_eq!((raster.width, raster.height), (PAGES_PER_BLOCK, 8));
+    assert_eq!(raster.get(0, 0), [220, 50, 40]);
+    assert_eq!(raster.get(PAGES_PER_BLOCK - 1, 0), [40, 200, 60]);
+    assert_eq!(raster.get(0, 7), [0, 0, 0]);
+}
+
+#[test]
+fn erase_and_lba_rasters_use_the_heat_scale() {
+    assert_eq!(heat_color(0.0), [0, 0, 255]);
+    assert_eq!(heat_color(0.5), [0, 255, 0]);
+    assert_eq!(heat_color(1.0), [255, 0, 0]);
+    assert_eq!(heat_color(7.0), [255, 0, 0]);
+
+    let mut blocks: Vec<Block> = (0..5).map(Block::new).collect();
+    blocks[1].erase_count = 10;
+    blocks[4].is_bad = true;
+    let raster = erase_count_raster(&blocks, 2);
+    assert_eq!((raster.width, raster.height), (2, 3));
+    assert_eq!(raster.get(0, 0), heat_color(0.0));
+    assert_eq!(raster.get(1, 0), heat_color(1.0));
+    // 배드 블록과 남는 칸은 검정
+    assert_eq!(raster.get(0, 2), [0, 0, 0]);
+    assert_eq!(raster.get(1, 2), [0, 0, 0]);
+
+    let raster = lba_frequency_raster(&[0, 1, 100], 3);
+    assert_eq!(raster.get(0, 0), [0, 0, 0]);
+    assert_eq!(raster.get(2, 0), heat_color(1.0));
+    assert_ne!(raster.get(1, 0), heat_color(1.0));
+}
+
+#[test]
+fn ppm_and_svg_export() {
+    let mut raster = Raster::new(3, 2);
+    raster.set(0, 0, [255, 0, 0]);
+    raster.set(1, 0, [255, 0, 0]);
+
+    let ppm = raster.to_ppm(2);
+    let header = b"P6\n6 4\n255\n";
+    assert_eq!(&ppm[..header.len()], header);
+    assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+    assert_eq!(&ppm[header.len()..header.len() + 3], &[255, 0, 0]);
+
+    let svg = raster.to_svg(1);
+    assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="3" height="2""#));
+    // 같은 색이 이어지는 구간은 rect 하나
+    assert!(svg.contains(r##"<rect x="0" y="0" width="2" height="1" fill="#ff0000"/>"##));
+    assert_eq!(svg.matches("<rect").count(), 3);
+    assert!(svg.ends_with("</svg>\n"));
+
+    let path = std::env::temp_dir().join(format!("raster_{}.ppm", std::process::id()));
+    raster.save(path.to_str().unwrap(), 1).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), raster.to_ppm(1));
+    std::fs::remove_file(&path).unwrap();
+    assert!(raster.save("image.png", 1).is_err());
+}
@@ -0,0 +1,134 @@
+use ssd_rs::difftest;
+use ssd_rs::alloc::AllocPolicy;
+use ssd_rs::ssd_wear_leveling::{StaticWlConfig, WlAlgorithm, SSD};
+
+// 앞쪽 cold 영역은 한 번만 쓰고, 뒤쪽 hot 영역만 계속 덮어씀
+fn hot_cold(config: StaticWlConfig) -> SSD {
+    let mut ssd = SSD::new(16, 700);
+    ssd.set_static_wl(config);
+    ssd.write_range(0, &[1; 600]).unwrap();
+    for i in 0..30_000u32 {
+        ssd.write(600 + (i as usize * 13) % 100, i).unwrap();
+    }
+    ssd
+}
+
+fn erase_gap(ssd: &SSD) -> u32 {
+    let erase = ssd.erase_distribution();
+    erase.max - erase.min
+}
+
+const NO_WL: StaticWlConfig =
+    StaticWlConfig { algorithm: WlAlgorithm::Threshold, gap_threshold: None, age_threshold: None, check_interval: 64 };
+
+#[test]
+fn static_wl_moves_cold_data_and_narrows_the_erase_gap() {
+    let without = hot_cold(NO_WL);
+    let mut with = hot_cold(StaticWlConfig { gap_threshold: Some(4), ..NO_WL });
+
+    assert_eq!(without.stats().wl_triggers, 0);
+    assert!(with.stats().wl_triggers > 0);
+    assert!(erase_gap(&with) < erase_gap(&without), "{} vs {}", erase_gap(&with), erase_gap(&without));
+
+    // WL로 옮긴 페이지는 GC 이동과 따로 집계되고 nand_writes에는 포함
+    let stats = with.stats();
+    assert!(stats.wl_migrated_pages > 0);
+    assert_eq!(stats.nand_writes, stats.host_writes + stats.gc_migrated_pages + stats.wl_migrated_pages);
+    assert_eq!(with.read(0).unwrap(), Some(1));
+    with.check_invariants().unwrap();
+}
+
+#[test]
+fn age_trigger_moves_data_that_sat_too_long() {
+    let ssd = hot_cold(StaticWlConfig { age_threshold: Some(5_000), ..NO_WL });
+    assert!(ssd.stats().wl_triggers > 0);
+    assert!(erase_gap(&ssd) < erase_gap(&hot_cold(NO_WL)));
+}
+
+#[test]
+fn manual_wear_leveling_when_automatic_checks_are_off() {
+    let mut ssd = hot_cold(StaticWlConfig { gap_threshold: Some(2), check_interval: 0, ..NO_WL });
+    assert_eq!(ssd.stats().wl_triggers, 0);
+
+    let erases = ssd.stats().nand_erases;
+    assert!(ssd.wear_level().unwrap());
+    assert_eq!(ssd.stats().wl_triggers, 1);
+    assert_eq!(ssd.stats().nand_erases, erases + 1);
+    assert_eq!(ssd.read(0).unwrap(), Some(1));
+}
+
+#[test]
+fn gc_alone_never_migrates_for_wear() {
+    let ssd = hot_cold(NO_WL);
+    assert_eq!(ssd.stats().wl_migrated_pages, 0);
+    assert_eq!(ssd.stats().nand_writes, ssd.stats().host_writes + ssd.stats().gc_migrated_pages);
+}
+
+const ALGORITHMS: [WlAlgorithm; 3] = [
+    WlAlgorithm::Adaptive { base: 2, ratio: 0.1 },
+    WlAlgorithm::DualPool { threshold: 4 },
+    WlAlgorithm::OldBlockLeftBehind { threshold: 2 },
+];
+
+#[test]
+fn every_algorithm_narrows_the_erase_gap() {
+    let baseline = erase_gap(&hot_cold(NO_WL));
+    for algorithm in ALGORITHMS {
+        let mut ssd = hot_cold(StaticWlConfig { algorithm, ..NO_WL });
+        assert!(ssd.stats().wl_triggers > 0, "{:?}", algorithm);
+        assert!(erase_gap(&ssd) < baseline, "{:?}: {} vs {}", algorithm, erase_gap(&ssd), baseline);
+        assert_eq!(ssd.read(0).unwrap(), Some(1));
+        ssd.check_invariants().unwrap();
+    }
+}
+
+#[test]
+fn every_algorithm_matches_oracle() {
+    for algorithm in ALGORITHMS {
+        let factory = || {
+            let mut ssd = SSD::new(8, 300);
+            ssd.set_static_wl(StaticWlConfig { algorithm, check_interval: 16, ..NO_WL });
+            ssd.set_debug_checks(true);
+            ssd
+        };
+        if let Err(failure) = difftest::run(factory, 3000, 8) {
+            panic!("{:?}: {}", algorithm, failure);
+        }
+    }
+}
+
+#[test]
+fn alloc_policies_match_oracle_with_every_algorithm() {
+    for policy in [AllocPolicy::LowestErase, AllocPolicy::RoundRobin, AllocPolicy::Random] {
+        for algorithm in ALGORITHMS {
+            let factory = || {
+                let mut ssd = SSD::with_seed(8, 300, 5);
+                ssd.set_alloc_policy(policy);
+                ssd.set_static_wl(StaticWlConfig { algorithm, check_interval: 16, ..NO_WL });
+                ssd.set_debug_checks(true);
+                ssd
+            };
+            if let Err(failure) = difftest::run(factory, 3000, 4) {
+                panic!("{:?} / {:?}: {}", policy, algorithm, failure);
+            }
+        }
+    }
+}
+
+#[test]
+fn snapshot_keeps_alloc_policy() {
+    let mut ssd = SSD::with_seed(8, 300, 9);
+    ssd.set_alloc_policy(AllocPolicy::Random);
+    for i in 0..1500u32 {
+        ssd.write((i as usize * 7) % 300, i).unwrap();
+    }
+    let mut restored = SSD::from_snapshot(ssd.snapshot());
+    assert_eq!(restored.alloc_policy(), AllocPolicy::Random);
+    for i in 0..1500u32 {
+        ssd.write((i as usize * 11) % 300, i).unwrap();
+        restored.write((i as usize * 11) % 300, i).unwrap();
+    }
+    restored.check_invariants().unwrap();
+    let erases = |ssd: &SSD| ssd.blocks().iter().map(|b| b.erase_count).collect::<Vec<_>>();
+    assert_eq!(erases(&ssd), erases(&restored));
+}
@@ -0,0 +1,132 @@
+use ssd_rs::workload::{AddressPattern, IoOp, OpMix, Phase, Phased, RequestSize, WorkloadGenerator, WorkloadSpec};
+
+const SAMPLES: usize = 200_000;
+
+// 시작 LBA별 등장 횟수
+fn histogram(spec: WorkloadSpec, num_lbas: usize) -> Vec<usize> {
+    let mut counts = vec![0; num_lbas];
+    for request in WorkloadGenerator::new(spec, num_lbas, 1).unwrap().take(SAMPLES) {
+        assert!(request.lba + request.len <= num_lbas, "{:?}", request);
+        counts[request.lba] += 1;
+    }
+    counts
+}
+
+fn sized(pattern: AddressPattern, lbas: usize) -> WorkloadSpec {
+    WorkloadSpec { size: RequestSize::Fixed { lbas }, ..WorkloadSpec::new(pattern) }
+}
+
+#[test]
+fn parse_accepts_short_forms_and_rejects_bad_theta() {
+    assert_eq!(AddressPattern::parse("seq").unwrap(), AddressPattern::Sequential { start: 0 });
+    assert_eq!(AddressPattern::parse("zipf:0.99").unwrap(), AddressPattern::Zipfian { theta: 0.99 });
+    assert_eq!(
+        AddressPattern::parse("shifting:80/20/500").unwrap(),
+        AddressPattern::ShiftingHotspot { hot_access_pct: 80.0, hot_lba_pct: 20.0, shift_every: 500 }
+    );
+    for bad in ["zipf:1", "zipf:1.2", "zipf:-0.1", "zipf:NaN", "zipf", "hotcold:80", "lognormal"] {
+        assert!(AddressPattern::parse(bad).is_err(), "{}", bad);
+    }
+}
+
+#[test]
+fn zipfian_generator_validates_its_parameters() {
+    let zipf = |theta| WorkloadSpec::new(AddressPattern::Zipfian { theta });
+    assert!(WorkloadGenerator::new(zipf(1.0), 100, 0).is_err());
+    assert!(WorkloadGenerator::new(zipf(0.5), 1, 0).is_err());
+    assert!(WorkloadGenerator::new(zipf(0.0), 2, 0).is_ok());
+    assert!(WorkloadGenerator::new(WorkloadSpec::new(AddressPattern::Uniform), 0, 0).is_err());
+
+    // JSON 설정도 같은 검사를 거침
+    let phase: Phase = serde_json::from_str(r#"{"workload": {"pattern": {"type": "zipfian", "theta": 1.0}}, "requests": 10}"#).unwrap();
+    assert!(Phased::new(&[phase], 100, 0).is_err());
+}
+
+#[test]
+fn zipfian_follows_the_power_law() {
+    let num_lbas = 1000;
+    let theta = 0.99;
+    let counts = histogram(WorkloadSpec::new(AddressPattern::Zipfian { theta }), num_lbas);
+
+    // P(rank k) = 1 / (k^theta * zeta(n))
+    let zetan: f64 = (1..=num_lbas).map(|i| 1.0 / (i as f64).powf(theta)).sum();
+    for rank in [0, 1] {
+        let expected = 1.0 / ((rank + 1) as f64).powf(theta) / zetan;
+        let observed = counts[rank] as f64 / SAMPLES as f64;
+        assert!((observed - expected).abs() < 0.01, "rank {}: {} vs {}", rank, observed, expected);
+    }
+    assert!(counts[0] > counts[1] && counts[1] > counts[10] && counts[10] > counts[500]);
+}
+
+#[test]
+fn zipfian_with_theta_zero_is_uniform() {
+    let counts = histogram(WorkloadSpec::new(AddressPattern::Zipfian { theta: 0.0 }), 100);
+    let mean = SAMPLES as f64 / 100.0;
+    assert!(counts.iter().all(|&c| (c as f64 - mean).abs() < mean * 0.15), "{:?}", counts);
+}
+
+// 여러 LBA짜리 요청도 마지막 시작 위치에 확률이 몰리지 않아야 함
+#[test]
+fn multi_lba_requests_do_not_pile_up_at_the_end() {
+    let num_lbas = 100;
+    let len = 10;
+    let max_start = num_lbas - len;
+
+    let uniform = histogram(sized(AddressPattern::Uniform, len), num_lbas);
+    let mean = SAMPLES as f64 / (max_start + 1) as f64;
+    assert!(uniform[..=max_start].iter().all(|&c| (c as f64 - mean).abs() < mean * 0.15));
+
+    let hot_cold = histogram(sized(AddressPattern::HotCold { hot_access_pct: 80.0, hot_lba_pct: 20.0 }, len), num_lbas);
+    let cold = &hot_cold[30..max_start];
+    let cold_mean = cold.iter().sum::<usize>() as f64 / cold.len() as f64;
+    assert!((hot_cold[max_start] as f64) < cold_mean * 1.3, "{} vs {}", hot_cold[max_start], cold_mean);
+    let hot: usize = hot_cold[..(max_start + 1) / 5].iter().sum();
+    assert!((hot as f64 / SAMPLES as f64 - 0.8).abs() < 0.01);
+
+    let zipf = histogram(sized(AddressPattern::Zipfian { theta: 0.5 }, len), num_lbas);
+    assert!(zipf[max_start] < zipf[max_start - 1] * 13 / 10, "{} vs {}", zipf[max_start], zipf[max_start - 1]);
+    assert!(zipf[0] > zipf[max_start]);
+}
+
+#[test]
+fn sequential_wraps_and_shifting_hotspot_moves() {
+    let requests: Vec<usize> = WorkloadGenerator::new(sized(AddressPattern::Sequential { start: 5 }, 3), 10, 0)
+        .unwrap()
+        .take(4)
+        .map(|r| r.lba)
+        .collect();
+    assert_eq!(requests, vec![5, 0, 3, 6]);
+
+    let spec = WorkloadSpec::new(AddressPattern::ShiftingHotspot { hot_access_pct: 100.0, hot_lba_pct: 10.0, shift_every: 1000 });
+    let lbas: Vec<usize> = WorkloadGenerator::new(spec, 100, 0).unwrap().take(2000).map(|r| r.lba).collect();
+    assert!(lbas[..1000].iter().all(|&lba| lba < 10));
+    assert!(lbas[1000..].iter().all(|&lba| (10..20).contains(&lba)));
+}
+
+#[test]
+fn same_seed_same_requests_and_mix_is_respected() {
+    let spec = WorkloadSpec {
+        mix: OpMix { read: 1.0, write: 2.0, trim: 1.0 },
+        size: RequestSize::Uniform { min: 1, max: 4 },
+        ..WorkloadSpec::new(AddressPattern::Uniform)
+    };
+    let a: Vec<_> = WorkloadGenerator::new(spec.clone(), 500, 9).unwrap().take(1000).collect();
+    let b: Vec<_> = WorkloadGenerator::new(spec.clone(), 500, 9).unwrap().take(1000).collect();
+    assert_eq!(a, b);
+
+    let writes = a.iter().filter(|r| r.op == IoOp::Write).count();
+    assert!((400..600).contains(&writes), "{}", writes);
+    assert!(a.iter().all(|r| (1..=4).contains(&r.len)));
+}
+
+#[test]
+fn phased_runs_each_phase_for_its_request_count() {
+    let phases = [
+        Phase { workload: WorkloadSpec::new(AddressPattern::Sequential { start: 0 }), requests: 50 },
+        Phase { workload: WorkloadSpec::new(AddressPattern::HotCold { hot_access_pct: 100.0, hot_lba_pct: 10.0 }), requests: 20 },
+    ];
+    let requests: Vec<_> = Phased::new(&phases, 100, 3).unwrap().collect();
+    assert_eq!(requests.len(), 70);
+    assert!(requests[..50].iter().enumerate().all(|(i, r)| r.lba == i));
+    assert!(requests[50..].iter().all(|r| r.lba < 10));
+}
@@ -0,0 +1,88 @@
+use ssd_rs::cell::CellMode;
+use ssd_rs::difftest;
+use ssd_rs::ssd_basic::SSD;
+use ssd_rs::write_buffer::{EvictionPolicy, WriteBufferConfig};
+
+fn buffered(num_blocks: usize, num_lbas: usize, capacity: usize, policy: EvictionPolicy) -> SSD {
+    let mut ssd = SSD::new(num_blocks, num_lbas);
+    ssd.set_write_buffer(Some(WriteBufferConfig { capacity, policy })).unwrap();
+    ssd.set_debug_checks(true);
+    ssd
+}
+
+#[test]
+fn every_policy_matches_oracle() {
+    for policy in [EvictionPolicy::Lru, EvictionPolicy::Fifo, EvictionPolicy::PaddingAware] {
+        if let Err(failure) = difftest::run(|| buffered(12, 500, 16, policy), 3000, 5) {
+            panic!("{:?}: {}", policy, failure);
+        }
+    }
+}
+
+#[test]
+fn overwrites_are_absorbed_until_flush() {
+    let mut ssd = buffered(8, 200, 8, EvictionPolicy::Lru);
+    for i in 0..100 {
+        ssd.write(7, i).unwrap();
+    }
+    assert_eq!(ssd.stats().nand_writes, 0);
+    assert_eq!(ssd.stats().buffer_absorbed, 99);
+    assert_eq!(ssd.read(7).unwrap(), Some(99));
+    assert_eq!(ssd.stats().buffer_read_hits, 1);
+
+    // MLC 프로그램 단위(2페이지)를 채우도록 더미 한 페이지 추가
+    ssd.flush().unwrap();
+    assert_eq!(ssd.stats().nand_writes, 2);
+    assert_eq!(ssd.stats().padding_pages, 1);
+    assert!(ssd.write_buffer().unwrap().is_empty());
+    assert_eq!(ssd.read(7).unwrap(), Some(99));
+}
+
+#[test]
+fn power_loss_drops_only_unflushed_data() {
+    let mut ssd = buffered(8, 200, 8, EvictionPolicy::Lru);
+    ssd.write(1, 10).unwrap();
+    ssd.flush().unwrap();
+    ssd.write(1, 11).unwrap();
+    ssd.write(2, 20).unwrap();
+    ssd.write_fua(3, 30).unwrap();
+
+    assert_eq!(ssd.power_loss(), 2);
+    assert_eq!(ssd.stats().buffer_lost, 2);
+    assert_eq!(ssd.read(1).unwrap(), Some(10));
+    assert_eq!(ssd.read(2).unwrap(), None);
+    assert_eq!(ssd.read(3).unwrap(), Some(30));
+    ssd.check_invariants().unwrap();
+}
+
+#[test]
+fn lru_and_fifo_evict_different_pages() {
+    let evicted = |policy| {
+        let mut ssd = buffered(8, 200, 2, policy);
+        ssd.write(0, 1).unwrap();
+        ssd.write(1, 1).unwrap();
+        ssd.write(0, 2).unwrap();
+        ssd.write(2, 1).unwrap();
+        (0..3).filter(|&lba| ssd.mapping_table().get(lba).is_some()).collect::<Vec<_>>()
+    };
+    assert_eq!(evicted(EvictionPolicy::Lru), vec![1]);
+    assert_eq!(evicted(EvictionPolicy::Fifo), vec![0]);
+}
+
+#[test]
+fn padding_aware_fills_program_units_with_buffered_data() {
+    let padding_after_fua = |policy| {
+        let mut ssd = buffered(8, 200, 16, policy);
+        for idx in 0..8 {
+            ssd.set_cell_mode(idx, CellMode::Tlc).unwrap();
+        }
+        for lba in 0..4 {
+            ssd.write(lba, 1).unwrap();
+        }
+        ssd.write_fua(10, 1).unwrap();
+        (ssd.stats().nand_writes, ssd.stats().padding_pages)
+    };
+    // TLC 단위 3페이지: FUA 1페이지 + 더미 2페이지 / 버퍼 데이터 2페이지
+    assert_eq!(padding_after_fua(EvictionPolicy::Lru), (3, 2));
+    assert_eq!(padding_after_fua(EvictionPolicy::PaddingAware), (3, 0));
+}